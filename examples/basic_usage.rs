@@ -163,7 +163,7 @@ fn main() {
     // 7. Hartley Transform
     println!("7. Hartley Transform");
     let hartley_input = vec![1.0, 2.0, 3.0, 4.0];
-    let hartley_result = dht(&hartley_input);
+    let hartley_result = dht(&hartley_input).unwrap();
     println!("   Input: {:?}", hartley_input);
     println!(
         "   DHT: {:?}",
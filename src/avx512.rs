@@ -0,0 +1,587 @@
+//! AVX-512F kernels for the x86_64 backend.
+//!
+//! Power-of-two transforms run a split-format Stockham FFT with 512-bit
+//! butterfly rows: 16 `f32` or 8 `f64` lanes per instruction. Two pass
+//! shapes are provided, radix-2 and radix-4; the radix-4 pipeline (with one
+//! radix-2 pass for odd powers of two) backs the `Radix4` and `Auto`
+//! strategies. The real-FFT packing stage that turns a half-length
+//! complex FFT into the spectrum of a real signal is vectorised as well.
+//!
+//! Every entry point checks [`available`] and defers to the scalar code when
+//! the running CPU lacks `avx512f`. Twiddles and work buffers live in the
+//! backend's [`SimdState`].
+
+use crate::fft::{FftError, FftImpl};
+use crate::num::{Complex, Complex32, Complex64, Float};
+use crate::simd_f64::SimdState;
+use core::arch::x86_64::*;
+
+/// Whether the running CPU supports AVX-512F.
+///
+/// Without `std` this reflects the compile-time target features.
+pub(crate) fn available() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::arch::is_x86_feature_detected!("avx512f")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        cfg!(target_feature = "avx512f")
+    }
+}
+
+/// One row of radix-2 butterflies: `out0 = a + w·b`, `out1 = a - w·b`.
+struct Radix2Row<T> {
+    input: [(*const T, *const T); 2],
+    output: [(*mut T, *mut T); 2],
+    len: usize,
+}
+
+/// One row of radix-4 butterflies. Inputs `1..4` are multiplied by the
+/// twiddles `w[0..3]` before the 4-point DFT.
+struct Radix4Row<T> {
+    input: [(*const T, *const T); 4],
+    output: [(*mut T, *mut T); 4],
+    len: usize,
+}
+
+/// Precision-specific pieces of the AVX-512 pipeline.
+///
+/// Row kernels process a prefix of the row and return how many elements
+/// they handled; the driver finishes the tail in scalar code.
+trait Avx512Float: Float {
+    /// # Safety
+    /// Pointers must be valid for `row.len` elements and AVX-512F available.
+    unsafe fn radix2_row(row: &Radix2Row<Self>, w: Complex<Self>) -> usize;
+    /// # Safety
+    /// Pointers must be valid for `row.len` elements and AVX-512F available.
+    unsafe fn radix4_row(row: &Radix4Row<Self>, w: [Complex<Self>; 3]) -> usize;
+    /// Real-FFT packing for bins `1..` of `dst`; returns the first bin left
+    /// for the scalar tail.
+    ///
+    /// # Safety
+    /// `src` and `twiddles` must hold at least `m` elements, `dst` at least
+    /// `m`, and AVX-512F must be available.
+    unsafe fn pack(
+        src: &[Complex<Self>],
+        dst: &mut [Complex<Self>],
+        twiddles: &[Complex<Self>],
+        m: usize,
+        inverse: bool,
+    ) -> usize;
+}
+
+impl Avx512Float for f32 {
+    unsafe fn radix2_row(row: &Radix2Row<f32>, w: Complex32) -> usize {
+        radix2_row_f32(row, w)
+    }
+    unsafe fn radix4_row(row: &Radix4Row<f32>, w: [Complex32; 3]) -> usize {
+        radix4_row_f32(row, w)
+    }
+    unsafe fn pack(
+        src: &[Complex32],
+        dst: &mut [Complex32],
+        twiddles: &[Complex32],
+        m: usize,
+        inverse: bool,
+    ) -> usize {
+        pack_f32(src, dst, twiddles, m, inverse)
+    }
+}
+
+impl Avx512Float for f64 {
+    unsafe fn radix2_row(row: &Radix2Row<f64>, w: Complex64) -> usize {
+        radix2_row_f64(row, w)
+    }
+    unsafe fn radix4_row(row: &Radix4Row<f64>, w: [Complex64; 3]) -> usize {
+        radix4_row_f64(row, w)
+    }
+    unsafe fn pack(
+        src: &[Complex64],
+        dst: &mut [Complex64],
+        twiddles: &[Complex64],
+        m: usize,
+        inverse: bool,
+    ) -> usize {
+        pack_f64(src, dst, twiddles, m, inverse)
+    }
+}
+
+/// In-place forward FFT. `radix4` selects the radix-4 pipeline, otherwise
+/// every pass is radix-2. Lengths that are not powers of two, tiny
+/// transforms and CPUs without AVX-512F use the state's scalar fallback.
+#[allow(private_bounds)]
+pub(crate) fn fft<T: Avx512Float>(
+    state: &SimdState<T>,
+    input: &mut [Complex<T>],
+    radix4: bool,
+) -> Result<(), FftError> {
+    let n = input.len();
+    if n == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if n <= 16 || !n.is_power_of_two() || !available() {
+        return state.scalar().fft(input);
+    }
+    // SAFETY: the state is only borrowed for the duration of this call and
+    // the scalar fallback above never reaches it.
+    let state = unsafe { state.get() };
+    let twiddles = state.twiddles(n, n);
+    let [re, im, scratch_re, scratch_im] = state.buffers(n);
+    for (c, (r, i)) in input.iter().zip(re.iter_mut().zip(im.iter_mut())) {
+        *r = c.re;
+        *i = c.im;
+    }
+
+    let mut src = (re, im);
+    let mut dst = (scratch_re, scratch_im);
+    let mut n1 = 1usize;
+    let mut n2 = n;
+    // An odd power of two needs one radix-2 pass before the radix-4 passes.
+    let mut radix2_passes = if radix4 {
+        n.trailing_zeros() as usize % 2
+    } else {
+        n.trailing_zeros() as usize
+    };
+    while n1 < n {
+        if radix2_passes > 0 {
+            radix2_passes -= 1;
+            n2 >>= 1;
+            radix2_pass(&mut src, &mut dst, &twiddles, n1, n2);
+            n1 <<= 1;
+        } else {
+            n2 >>= 2;
+            radix4_pass(&mut src, &mut dst, &twiddles, n1, n2);
+            n1 <<= 2;
+        }
+        core::mem::swap(&mut src, &mut dst);
+    }
+    for (c, (&r, &i)) in input.iter_mut().zip(src.0.iter().zip(src.1.iter())) {
+        *c = Complex::new(r, i);
+    }
+    Ok(())
+}
+
+/// In-place inverse FFT (scaled by `1/n`).
+#[allow(private_bounds)]
+pub(crate) fn ifft<T: Avx512Float>(
+    state: &SimdState<T>,
+    input: &mut [Complex<T>],
+    radix4: bool,
+) -> Result<(), FftError> {
+    for c in input.iter_mut() {
+        c.im = -c.im;
+    }
+    fft(state, input, radix4)?;
+    let scale = T::one() / T::from_f64(input.len() as f64);
+    for c in input.iter_mut() {
+        c.re = c.re * scale;
+        c.im = -c.im * scale;
+    }
+    Ok(())
+}
+
+type Split<'a, T> = (&'a mut [T], &'a mut [T]);
+
+fn radix2_pass<T: Avx512Float>(
+    src: &mut Split<'_, T>,
+    dst: &mut Split<'_, T>,
+    twiddles: &[Complex<T>],
+    n1: usize,
+    n2: usize,
+) {
+    for k in 0..n1 {
+        let w = twiddles[k * n2];
+        let a = 2 * k * n2;
+        let b = a + n2;
+        let out0 = k * n2;
+        let out1 = (k + n1) * n2;
+        let row = Radix2Row {
+            input: [
+                (src.0[a..].as_ptr(), src.1[a..].as_ptr()),
+                (src.0[b..].as_ptr(), src.1[b..].as_ptr()),
+            ],
+            output: [
+                (dst.0[out0..].as_mut_ptr(), dst.1[out0..].as_mut_ptr()),
+                (dst.0[out1..].as_mut_ptr(), dst.1[out1..].as_mut_ptr()),
+            ],
+            len: n2,
+        };
+        // SAFETY: each pointer addresses `n2` elements of its buffer and the
+        // caller checked for AVX-512F.
+        let done = unsafe { T::radix2_row(&row, w) };
+        for j in done..n2 {
+            let (ar, ai) = (src.0[a + j], src.1[a + j]);
+            let (br, bi) = (src.0[b + j], src.1[b + j]);
+            let t_re = br * w.re - bi * w.im;
+            let t_im = br * w.im + bi * w.re;
+            dst.0[out0 + j] = ar + t_re;
+            dst.1[out0 + j] = ai + t_im;
+            dst.0[out1 + j] = ar - t_re;
+            dst.1[out1 + j] = ai - t_im;
+        }
+    }
+}
+
+fn radix4_pass<T: Avx512Float>(
+    src: &mut Split<'_, T>,
+    dst: &mut Split<'_, T>,
+    twiddles: &[Complex<T>],
+    n1: usize,
+    n2: usize,
+) {
+    for k in 0..n1 {
+        let w = [twiddles[k * n2], twiddles[2 * k * n2], twiddles[3 * k * n2]];
+        let inp = [0, 1, 2, 3].map(|p| (4 * k + p) * n2);
+        let out = [0, 1, 2, 3].map(|q| (k + q * n1) * n2);
+        let row = Radix4Row {
+            input: inp.map(|i| (src.0[i..].as_ptr(), src.1[i..].as_ptr())),
+            output: out.map(|o| (dst.0[o..].as_mut_ptr(), dst.1[o..].as_mut_ptr())),
+            len: n2,
+        };
+        // SAFETY: as in `radix2_pass`; the four output rows are disjoint.
+        let done = unsafe { T::radix4_row(&row, w) };
+        for j in done..n2 {
+            let a = Complex::new(src.0[inp[0] + j], src.1[inp[0] + j]);
+            let b = Complex::new(src.0[inp[1] + j], src.1[inp[1] + j]).mul(w[0]);
+            let c = Complex::new(src.0[inp[2] + j], src.1[inp[2] + j]).mul(w[1]);
+            let d = Complex::new(src.0[inp[3] + j], src.1[inp[3] + j]).mul(w[2]);
+            let t0 = a.add(c);
+            let t1 = a.sub(c);
+            let t2 = b.add(d);
+            let bd = b.sub(d);
+            // -i·(b - d)
+            let t3 = Complex::new(bd.im, -bd.re);
+            let x = [t0.add(t2), t1.add(t3), t0.sub(t2), t1.sub(t3)];
+            for (q, v) in x.iter().enumerate() {
+                dst.0[out[q] + j] = v.re;
+                dst.1[out[q] + j] = v.im;
+            }
+        }
+    }
+}
+
+/// Real FFT of `input` (length `2m`) into `output` (length `m + 1`) using a
+/// half-length complex FFT and the vectorised packing stage.
+#[allow(private_bounds)]
+pub(crate) fn rfft<T: Avx512Float, F: FftImpl<T> + ?Sized>(
+    fft: &F,
+    input: &mut [T],
+    output: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+) -> Result<(), FftError> {
+    let n = input.len();
+    if n == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if !n.is_multiple_of(2) {
+        return Err(FftError::InvalidValue);
+    }
+    let m = n / 2;
+    if output.len() != m + 1 || scratch.len() < m {
+        return Err(FftError::MismatchedLengths);
+    }
+    for i in 0..m {
+        output[i] = Complex::new(input[2 * i], input[2 * i + 1]);
+    }
+    fft.fft(&mut output[..m])?;
+    scratch[..m].copy_from_slice(&output[..m]);
+    let y0 = scratch[0];
+    output[0] = Complex::new(y0.re + y0.im, T::zero());
+    output[m] = Complex::new(y0.re - y0.im, T::zero());
+    let start = if available() {
+        // SAFETY: lengths were checked above and AVX-512F is present.
+        unsafe { T::pack(scratch, output, twiddles, m, false) }
+    } else {
+        1
+    };
+    let half = T::from_f32(0.5);
+    for k in start..m {
+        let a = scratch[k];
+        let b = Complex::new(scratch[m - k].re, -scratch[m - k].im);
+        let sum = a.add(b);
+        let diff = a.sub(b);
+        let t = twiddles[k].mul(diff);
+        let temp = sum.add(Complex::new(t.im, -t.re));
+        output[k] = Complex::new(temp.re * half, temp.im * half);
+    }
+    Ok(())
+}
+
+/// Inverse of [`rfft`]: `input` holds `m + 1` bins, `output` receives `2m`
+/// samples.
+#[allow(private_bounds)]
+pub(crate) fn irfft<T: Avx512Float, F: FftImpl<T> + ?Sized>(
+    fft: &F,
+    input: &mut [Complex<T>],
+    output: &mut [T],
+    scratch: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+) -> Result<(), FftError> {
+    let n = output.len();
+    if n == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if !n.is_multiple_of(2) {
+        return Err(FftError::InvalidValue);
+    }
+    let m = n / 2;
+    if input.len() != m + 1 || scratch.len() < m {
+        return Err(FftError::MismatchedLengths);
+    }
+    let half = T::from_f32(0.5);
+    scratch[0] = Complex::new(
+        (input[0].re + input[m].re) * half,
+        (input[0].re - input[m].re) * half,
+    );
+    let start = if available() {
+        // SAFETY: lengths were checked above and AVX-512F is present.
+        unsafe { T::pack(input, scratch, twiddles, m, true) }
+    } else {
+        1
+    };
+    for k in start..m {
+        let a = input[k];
+        let b = Complex::new(input[m - k].re, -input[m - k].im);
+        let sum = a.add(b);
+        let diff = a.sub(b);
+        let w = Complex::new(twiddles[k].re, -twiddles[k].im);
+        let t = w.mul(diff);
+        let temp = sum.sub(Complex::new(t.im, -t.re));
+        scratch[k] = Complex::new(temp.re * half, temp.im * half);
+    }
+    fft.ifft(&mut scratch[..m])?;
+    for i in 0..m {
+        output[2 * i] = scratch[i].re;
+        output[2 * i + 1] = scratch[i].im;
+    }
+    Ok(())
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn radix2_row_f32(row: &Radix2Row<f32>, w: Complex32) -> usize {
+    let w_re = _mm512_set1_ps(w.re);
+    let w_im = _mm512_set1_ps(w.im);
+    let [(a_re, a_im), (b_re, b_im)] = row.input;
+    let [(o0_re, o0_im), (o1_re, o1_im)] = row.output;
+    let mut j = 0;
+    while j + 16 <= row.len {
+        let ar = _mm512_loadu_ps(a_re.add(j));
+        let ai = _mm512_loadu_ps(a_im.add(j));
+        let br = _mm512_loadu_ps(b_re.add(j));
+        let bi = _mm512_loadu_ps(b_im.add(j));
+        let t_re = _mm512_fmsub_ps(br, w_re, _mm512_mul_ps(bi, w_im));
+        let t_im = _mm512_fmadd_ps(br, w_im, _mm512_mul_ps(bi, w_re));
+        _mm512_storeu_ps(o0_re.add(j), _mm512_add_ps(ar, t_re));
+        _mm512_storeu_ps(o0_im.add(j), _mm512_add_ps(ai, t_im));
+        _mm512_storeu_ps(o1_re.add(j), _mm512_sub_ps(ar, t_re));
+        _mm512_storeu_ps(o1_im.add(j), _mm512_sub_ps(ai, t_im));
+        j += 16;
+    }
+    j
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn radix2_row_f64(row: &Radix2Row<f64>, w: Complex64) -> usize {
+    let w_re = _mm512_set1_pd(w.re);
+    let w_im = _mm512_set1_pd(w.im);
+    let [(a_re, a_im), (b_re, b_im)] = row.input;
+    let [(o0_re, o0_im), (o1_re, o1_im)] = row.output;
+    let mut j = 0;
+    while j + 8 <= row.len {
+        let ar = _mm512_loadu_pd(a_re.add(j));
+        let ai = _mm512_loadu_pd(a_im.add(j));
+        let br = _mm512_loadu_pd(b_re.add(j));
+        let bi = _mm512_loadu_pd(b_im.add(j));
+        let t_re = _mm512_fmsub_pd(br, w_re, _mm512_mul_pd(bi, w_im));
+        let t_im = _mm512_fmadd_pd(br, w_im, _mm512_mul_pd(bi, w_re));
+        _mm512_storeu_pd(o0_re.add(j), _mm512_add_pd(ar, t_re));
+        _mm512_storeu_pd(o0_im.add(j), _mm512_add_pd(ai, t_im));
+        _mm512_storeu_pd(o1_re.add(j), _mm512_sub_pd(ar, t_re));
+        _mm512_storeu_pd(o1_im.add(j), _mm512_sub_pd(ai, t_im));
+        j += 8;
+    }
+    j
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn radix4_row_f32(row: &Radix4Row<f32>, w: [Complex32; 3]) -> usize {
+    let w_re = w.map(|w| _mm512_set1_ps(w.re));
+    let w_im = w.map(|w| _mm512_set1_ps(w.im));
+    let mut j = 0;
+    while j + 16 <= row.len {
+        let ar = _mm512_loadu_ps(row.input[0].0.add(j));
+        let ai = _mm512_loadu_ps(row.input[0].1.add(j));
+        let mut xr = [ar; 3];
+        let mut xi = [ai; 3];
+        for p in 0..3 {
+            let r = _mm512_loadu_ps(row.input[p + 1].0.add(j));
+            let i = _mm512_loadu_ps(row.input[p + 1].1.add(j));
+            xr[p] = _mm512_fmsub_ps(r, w_re[p], _mm512_mul_ps(i, w_im[p]));
+            xi[p] = _mm512_fmadd_ps(r, w_im[p], _mm512_mul_ps(i, w_re[p]));
+        }
+        let [br, cr, dr] = xr;
+        let [bi, ci, di] = xi;
+        let t0r = _mm512_add_ps(ar, cr);
+        let t0i = _mm512_add_ps(ai, ci);
+        let t1r = _mm512_sub_ps(ar, cr);
+        let t1i = _mm512_sub_ps(ai, ci);
+        let t2r = _mm512_add_ps(br, dr);
+        let t2i = _mm512_add_ps(bi, di);
+        // t3 = -i·(b - d)
+        let t3r = _mm512_sub_ps(bi, di);
+        let t3i = _mm512_sub_ps(dr, br);
+        let out = &row.output;
+        _mm512_storeu_ps(out[0].0.add(j), _mm512_add_ps(t0r, t2r));
+        _mm512_storeu_ps(out[0].1.add(j), _mm512_add_ps(t0i, t2i));
+        _mm512_storeu_ps(out[1].0.add(j), _mm512_add_ps(t1r, t3r));
+        _mm512_storeu_ps(out[1].1.add(j), _mm512_add_ps(t1i, t3i));
+        _mm512_storeu_ps(out[2].0.add(j), _mm512_sub_ps(t0r, t2r));
+        _mm512_storeu_ps(out[2].1.add(j), _mm512_sub_ps(t0i, t2i));
+        _mm512_storeu_ps(out[3].0.add(j), _mm512_sub_ps(t1r, t3r));
+        _mm512_storeu_ps(out[3].1.add(j), _mm512_sub_ps(t1i, t3i));
+        j += 16;
+    }
+    j
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn radix4_row_f64(row: &Radix4Row<f64>, w: [Complex64; 3]) -> usize {
+    let w_re = w.map(|w| _mm512_set1_pd(w.re));
+    let w_im = w.map(|w| _mm512_set1_pd(w.im));
+    let mut j = 0;
+    while j + 8 <= row.len {
+        let ar = _mm512_loadu_pd(row.input[0].0.add(j));
+        let ai = _mm512_loadu_pd(row.input[0].1.add(j));
+        let mut xr = [ar; 3];
+        let mut xi = [ai; 3];
+        for p in 0..3 {
+            let r = _mm512_loadu_pd(row.input[p + 1].0.add(j));
+            let i = _mm512_loadu_pd(row.input[p + 1].1.add(j));
+            xr[p] = _mm512_fmsub_pd(r, w_re[p], _mm512_mul_pd(i, w_im[p]));
+            xi[p] = _mm512_fmadd_pd(r, w_im[p], _mm512_mul_pd(i, w_re[p]));
+        }
+        let [br, cr, dr] = xr;
+        let [bi, ci, di] = xi;
+        let t0r = _mm512_add_pd(ar, cr);
+        let t0i = _mm512_add_pd(ai, ci);
+        let t1r = _mm512_sub_pd(ar, cr);
+        let t1i = _mm512_sub_pd(ai, ci);
+        let t2r = _mm512_add_pd(br, dr);
+        let t2i = _mm512_add_pd(bi, di);
+        // t3 = -i·(b - d)
+        let t3r = _mm512_sub_pd(bi, di);
+        let t3i = _mm512_sub_pd(dr, br);
+        let out = &row.output;
+        _mm512_storeu_pd(out[0].0.add(j), _mm512_add_pd(t0r, t2r));
+        _mm512_storeu_pd(out[0].1.add(j), _mm512_add_pd(t0i, t2i));
+        _mm512_storeu_pd(out[1].0.add(j), _mm512_add_pd(t1r, t3r));
+        _mm512_storeu_pd(out[1].1.add(j), _mm512_add_pd(t1i, t3i));
+        _mm512_storeu_pd(out[2].0.add(j), _mm512_sub_pd(t0r, t2r));
+        _mm512_storeu_pd(out[2].1.add(j), _mm512_sub_pd(t0i, t2i));
+        _mm512_storeu_pd(out[3].0.add(j), _mm512_sub_pd(t1r, t3r));
+        _mm512_storeu_pd(out[3].1.add(j), _mm512_sub_pd(t1i, t3i));
+        j += 8;
+    }
+    j
+}
+
+// The packing kernels work on interleaved `[re, im, re, im, ...]` vectors.
+// `b` holds the mirrored bins `src[m - k - i]`, loaded as one contiguous
+// block and reversed pairwise. With `sum = a + conj(b)` and
+// `diff = a - conj(b)`:
+//
+//   forward: dst[k] = (sum + rot(w·diff)) / 2
+//   inverse: dst[k] = (sum - rot(conj(w)·diff)) / 2
+//
+// where `rot(t) = (t.im, -t.re)`.
+
+#[target_feature(enable = "avx512f")]
+unsafe fn pack_f32(
+    src: &[Complex32],
+    dst: &mut [Complex32],
+    twiddles: &[Complex32],
+    m: usize,
+    inverse: bool,
+) -> usize {
+    const ODD: __mmask16 = 0xAAAA;
+    let reverse = _mm512_setr_epi32(14, 15, 12, 13, 10, 11, 8, 9, 6, 7, 4, 5, 2, 3, 0, 1);
+    let half = _mm512_set1_ps(0.5);
+    let zero = _mm512_setzero_ps();
+    let src_ptr = src.as_ptr() as *const f32;
+    let dst_ptr = dst.as_mut_ptr() as *mut f32;
+    let tw_ptr = twiddles.as_ptr() as *const f32;
+    let mut k = 1;
+    while k + 8 <= m {
+        let a = _mm512_loadu_ps(src_ptr.add(2 * k));
+        let b = _mm512_loadu_ps(src_ptr.add(2 * (m - k - 7)));
+        let b = _mm512_permutexvar_ps(reverse, b);
+        let b_conj = _mm512_mask_sub_ps(b, ODD, zero, b);
+        let sum = _mm512_add_ps(a, b_conj);
+        let diff = _mm512_sub_ps(a, b_conj);
+        let mut w = _mm512_loadu_ps(tw_ptr.add(2 * k));
+        if inverse {
+            w = _mm512_mask_sub_ps(w, ODD, zero, w);
+        }
+        // (w.re·d.re - w.im·d.im, w.re·d.im + w.im·d.re)
+        let w_re = _mm512_moveldup_ps(w);
+        let w_im = _mm512_movehdup_ps(w);
+        let diff_swap = _mm512_permute_ps(diff, 0xB1);
+        let t = _mm512_fmaddsub_ps(w_re, diff, _mm512_mul_ps(w_im, diff_swap));
+        let t_swap = _mm512_permute_ps(t, 0xB1);
+        let rot = _mm512_mask_sub_ps(t_swap, ODD, zero, t_swap);
+        let res = if inverse {
+            _mm512_sub_ps(sum, rot)
+        } else {
+            _mm512_add_ps(sum, rot)
+        };
+        _mm512_storeu_ps(dst_ptr.add(2 * k), _mm512_mul_ps(res, half));
+        k += 8;
+    }
+    k
+}
+
+#[target_feature(enable = "avx512f")]
+unsafe fn pack_f64(
+    src: &[Complex64],
+    dst: &mut [Complex64],
+    twiddles: &[Complex64],
+    m: usize,
+    inverse: bool,
+) -> usize {
+    const ODD: __mmask8 = 0xAA;
+    let reverse = _mm512_setr_epi64(6, 7, 4, 5, 2, 3, 0, 1);
+    let half = _mm512_set1_pd(0.5);
+    let zero = _mm512_setzero_pd();
+    let src_ptr = src.as_ptr() as *const f64;
+    let dst_ptr = dst.as_mut_ptr() as *mut f64;
+    let tw_ptr = twiddles.as_ptr() as *const f64;
+    let mut k = 1;
+    while k + 4 <= m {
+        let a = _mm512_loadu_pd(src_ptr.add(2 * k));
+        let b = _mm512_loadu_pd(src_ptr.add(2 * (m - k - 3)));
+        let b = _mm512_permutexvar_pd(reverse, b);
+        let b_conj = _mm512_mask_sub_pd(b, ODD, zero, b);
+        let sum = _mm512_add_pd(a, b_conj);
+        let diff = _mm512_sub_pd(a, b_conj);
+        let mut w = _mm512_loadu_pd(tw_ptr.add(2 * k));
+        if inverse {
+            w = _mm512_mask_sub_pd(w, ODD, zero, w);
+        }
+        let w_re = _mm512_movedup_pd(w);
+        let w_im = _mm512_permute_pd(w, 0xFF);
+        let diff_swap = _mm512_permute_pd(diff, 0x55);
+        let t = _mm512_fmaddsub_pd(w_re, diff, _mm512_mul_pd(w_im, diff_swap));
+        let t_swap = _mm512_permute_pd(t, 0x55);
+        let rot = _mm512_mask_sub_pd(t_swap, ODD, zero, t_swap);
+        let res = if inverse {
+            _mm512_sub_pd(sum, rot)
+        } else {
+            _mm512_add_pd(sum, rot)
+        };
+        _mm512_storeu_pd(dst_ptr.add(2 * k), _mm512_mul_pd(res, half));
+        k += 4;
+    }
+    k
+}
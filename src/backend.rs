@@ -0,0 +1,361 @@
+//! Runtime FFT backend registry.
+//!
+//! [`backends`] lists every backend compiled into this build together with
+//! its capabilities and whether the running CPU supports it. [`active`]
+//! reports the backend that [`new_fft_impl`](crate::fft::new_fft_impl)
+//! returns. The choice is made in this order:
+//!
+//! 1. a backend forced with [`force`];
+//! 2. the [`BACKEND_ENV`] environment variable (`std` only, read once per
+//!    process), when it names an available backend;
+//! 3. runtime CPU detection.
+//!
+//! ```
+//! use kofft::backend;
+//!
+//! for info in backend::backends() {
+//!     println!("{} available={}", info.name, info.available);
+//! }
+//! backend::force("scalar").unwrap();
+//! assert_eq!(backend::active().name, "scalar");
+//! backend::clear_forced();
+//! ```
+
+use crate::fft::{FftImpl, FftStrategy, ScalarFftImpl};
+use crate::num::Float;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Environment variable naming the backend `new_fft_impl` should use,
+/// e.g. `KOFFT_BACKEND=scalar`. Unknown or unavailable names are ignored.
+pub const BACKEND_ENV: &str = "KOFFT_BACKEND";
+
+/// Floating-point precision supported by a backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    F32,
+    F64,
+}
+
+/// Capabilities of one FFT backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendInfo {
+    /// Name accepted by [`force`], [`create`] and [`BACKEND_ENV`].
+    pub name: &'static str,
+    /// Short human-readable description.
+    pub description: &'static str,
+    /// Precisions the backend implements.
+    pub precisions: &'static [Precision],
+    /// Vector register width in bits used by the `f32` kernels on this CPU;
+    /// `0` when `f32` transforms run scalar code.
+    pub simd_width_f32: u32,
+    /// Vector register width in bits used by the `f64` kernels on this CPU;
+    /// `0` when `f64` transforms run scalar code.
+    pub simd_width_f64: u32,
+    /// Strategies run by the backend's own kernels for power-of-two lengths.
+    /// Everything else is delegated to the scalar implementation.
+    pub strategies: &'static [FftStrategy],
+    /// Whether the running CPU supports the backend.
+    pub available: bool,
+}
+
+/// Errors returned when selecting a backend by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendError {
+    /// No backend with this name is compiled into this build.
+    Unknown(String),
+    /// The backend exists but the running CPU does not support it.
+    Unavailable(&'static str),
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendError::Unknown(name) => write!(f, "unknown FFT backend `{name}`"),
+            BackendError::Unavailable(name) => {
+                write!(f, "FFT backend `{name}` is not supported by this CPU")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BackendError {}
+
+const BOTH: &[Precision] = &[Precision::F32, Precision::F64];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    X86_64,
+    #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+    Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Aarch64,
+    #[cfg(target_arch = "wasm32")]
+    Wasm,
+}
+
+/// Every backend compiled into this build, fastest last.
+const KINDS: &[Kind] = &[
+    Kind::Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Kind::X86_64,
+    #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+    Kind::Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Kind::Aarch64,
+    #[cfg(target_arch = "wasm32")]
+    Kind::Wasm,
+];
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Scalar => "scalar",
+            #[cfg(target_arch = "x86_64")]
+            Kind::X86_64 => "x86_64",
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            Kind::Avx512 => "avx512",
+            #[cfg(target_arch = "aarch64")]
+            Kind::Aarch64 => "aarch64",
+            #[cfg(target_arch = "wasm32")]
+            Kind::Wasm => "wasm",
+        }
+    }
+
+    fn available(self) -> bool {
+        match self {
+            Kind::Scalar => true,
+            // SSE2 is part of the x86_64 baseline.
+            #[cfg(target_arch = "x86_64")]
+            Kind::X86_64 => true,
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            Kind::Avx512 => crate::avx512::available(),
+            #[cfg(target_arch = "aarch64")]
+            Kind::Aarch64 => {
+                #[cfg(feature = "std")]
+                {
+                    std::arch::is_aarch64_feature_detected!("neon")
+                }
+                #[cfg(not(feature = "std"))]
+                {
+                    cfg!(target_feature = "neon")
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            Kind::Wasm => cfg!(target_feature = "simd128"),
+        }
+    }
+
+    fn info(self) -> BackendInfo {
+        let (description, (simd_width_f32, simd_width_f64), strategies): (
+            _,
+            _,
+            &'static [FftStrategy],
+        ) = match self {
+            Kind::Scalar => (
+                "portable scalar code",
+                (0, 0),
+                &[
+                    FftStrategy::Radix2,
+                    FftStrategy::Radix4,
+                    FftStrategy::SplitRadix,
+                    FftStrategy::MixedRadix,
+                    FftStrategy::Bluestein,
+                    FftStrategy::FourStep,
+                    FftStrategy::Auto,
+                ],
+            ),
+            #[cfg(target_arch = "x86_64")]
+            Kind::X86_64 => {
+                #[cfg(feature = "std")]
+                let wide = std::arch::is_x86_feature_detected!("avx2")
+                    && std::arch::is_x86_feature_detected!("fma");
+                #[cfg(not(feature = "std"))]
+                let wide = cfg!(all(target_feature = "avx2", target_feature = "fma"));
+                (
+                    "x86_64 AVX2/FMA with SSE2 fallback",
+                    // the `f32` path is the scalar Stockham FFT
+                    (0, if wide { 256 } else { 128 }),
+                    &[FftStrategy::Radix2],
+                )
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            Kind::Avx512 => (
+                "x86_64 AVX-512F",
+                (512, 512),
+                // no split-radix kernel: `SplitRadix` runs the scalar code
+                &[FftStrategy::Radix2, FftStrategy::Radix4, FftStrategy::Auto],
+            ),
+            #[cfg(target_arch = "aarch64")]
+            Kind::Aarch64 => ("AArch64 NEON", (0, 128), &[FftStrategy::Radix2]),
+            #[cfg(target_arch = "wasm32")]
+            Kind::Wasm => (
+                "WebAssembly SIMD128",
+                (
+                    0,
+                    if cfg!(target_feature = "simd128") {
+                        128
+                    } else {
+                        0
+                    },
+                ),
+                &[FftStrategy::Radix2],
+            ),
+        };
+        BackendInfo {
+            name: self.name(),
+            description,
+            precisions: BOTH,
+            simd_width_f32,
+            simd_width_f64,
+            strategies,
+            available: self.available(),
+        }
+    }
+
+    fn build<T: Float>(self) -> Box<dyn FftImpl<T>> {
+        match self {
+            Kind::Scalar => Box::new(ScalarFftImpl::<T>::default()),
+            #[cfg(target_arch = "x86_64")]
+            Kind::X86_64 => {
+                crate::fft::boxed_backend::<T, _>(crate::fft::SimdFftX86_64Impl::default())
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            Kind::Avx512 => {
+                crate::fft::boxed_backend::<T, _>(crate::fft::SimdFftAvx512Impl::default())
+            }
+            #[cfg(target_arch = "aarch64")]
+            Kind::Aarch64 => {
+                crate::fft::boxed_backend::<T, _>(crate::fft::SimdFftAarch64Impl::default())
+            }
+            #[cfg(target_arch = "wasm32")]
+            Kind::Wasm => crate::fft::boxed_backend::<T, _>(crate::fft::SimdFftWasmImpl::default()),
+        }
+    }
+}
+
+fn lookup(name: &str) -> Result<Kind, BackendError> {
+    let kind = KINDS
+        .iter()
+        .copied()
+        .find(|k| k.name().eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| BackendError::Unknown(name.to_string()))?;
+    if kind.available() {
+        Ok(kind)
+    } else {
+        Err(BackendError::Unavailable(kind.name()))
+    }
+}
+
+/// Index into `KINDS` plus one; `0` means nothing is forced.
+static FORCED: AtomicU8 = AtomicU8::new(0);
+
+/// All backends compiled into this build, including ones the running CPU
+/// cannot use (`available == false`).
+pub fn backends() -> Vec<BackendInfo> {
+    KINDS.iter().map(|k| k.info()).collect()
+}
+
+/// Capabilities of the backend with the given name (case-insensitive).
+pub fn info(name: &str) -> Option<BackendInfo> {
+    KINDS
+        .iter()
+        .find(|k| k.name().eq_ignore_ascii_case(name.trim()))
+        .map(|k| k.info())
+}
+
+/// Make [`new_fft_impl`](crate::fft::new_fft_impl) return the named
+/// backend for the rest of the process, overriding [`BACKEND_ENV`] and CPU
+/// detection.
+pub fn force(name: &str) -> Result<(), BackendError> {
+    let kind = lookup(name)?;
+    let index = KINDS.iter().position(|&k| k == kind).unwrap_or(0);
+    FORCED.store(index as u8 + 1, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Undo [`force`] and return to environment/CPU based selection.
+pub fn clear_forced() {
+    FORCED.store(0, Ordering::Relaxed);
+}
+
+/// Name of the backend set with [`force`], if any.
+pub fn forced() -> Option<&'static str> {
+    match FORCED.load(Ordering::Relaxed) {
+        0 => None,
+        i => KINDS.get(i as usize - 1).map(|k| k.name()),
+    }
+}
+
+/// Build the named backend regardless of the current selection.
+pub fn create<T: Float>(name: &str) -> Result<Box<dyn FftImpl<T>>, BackendError> {
+    lookup(name).map(|k| k.build::<T>())
+}
+
+/// The backend [`new_fft_impl`](crate::fft::new_fft_impl) currently returns.
+pub fn active() -> BackendInfo {
+    active_kind().info()
+}
+
+pub(crate) fn new_active<T: Float>() -> Box<dyn FftImpl<T>> {
+    active_kind().build::<T>()
+}
+
+fn active_kind() -> Kind {
+    if let Some(kind) = forced().and_then(|name| lookup(name).ok()) {
+        return kind;
+    }
+    #[cfg(feature = "std")]
+    if let Some(kind) = env_kind() {
+        return kind;
+    }
+    detect()
+}
+
+/// Backend named by [`BACKEND_ENV`], read once per process so the hot
+/// paths that consult [`active_kind`] do not allocate.
+#[cfg(feature = "std")]
+fn env_kind() -> Option<Kind> {
+    static ENV: std::sync::OnceLock<Option<Kind>> = std::sync::OnceLock::new();
+    *ENV.get_or_init(|| {
+        std::env::var(BACKEND_ENV)
+            .ok()
+            .and_then(|name| lookup(&name).ok())
+    })
+}
+
+/// Whether the AVX-512 kernels should run, i.e. the active backend is
+/// `avx512`. Lets code outside `new_fft_impl`, such as the real-FFT
+/// packing, honour [`force`] and [`BACKEND_ENV`].
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+pub(crate) fn avx512_active() -> bool {
+    crate::avx512::available() && active_kind() == Kind::Avx512
+}
+
+/// Best backend for the running CPU.
+fn detect() -> Kind {
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    {
+        #[cfg(feature = "avx512")]
+        if Kind::Avx512.available() {
+            return Kind::Avx512;
+        }
+        return Kind::X86_64;
+    }
+    #[cfg(target_arch = "aarch64")]
+    if Kind::Aarch64.available() {
+        return Kind::Aarch64;
+    }
+    #[cfg(target_arch = "wasm32")]
+    if Kind::Wasm.available() {
+        return Kind::Wasm;
+    }
+    #[allow(unreachable_code)]
+    Kind::Scalar
+}
@@ -1,19 +1,425 @@
 //! Chirp Z-Transform (CZT) module
 //! Arbitrary frequency resolution DFT
 //! no_std + alloc compatible
+//!
+//! The CZT evaluates `X[k] = Σ x[n] · a^-n · w^(nk)` for `k = 0..m`, i.e. the
+//! z-transform of `x` on the spiral contour `z_k = a · w^-k`.  [`CztPlan`]
+//! computes it in `O((N + M) log (N + M))` with Bluestein's algorithm: the
+//! chirps and the FFT of the convolution kernel are precomputed once per plan
+//! (in `f64`, so accuracy does not degrade with `k`) and reused on every call.
+//! [`IcztPlan`] inverts the square (`m == n`) transform and [`zoom_fft`]
+//! wraps the common "zoom into a frequency band" use case.
 
 extern crate alloc;
+use crate::fft::{Complex, Complex32, Complex64, FftError, FftImpl, ScalarFftImpl};
+use crate::num::Float;
 use alloc::vec;
 use alloc::vec::Vec;
 
-// no additional libm functions needed
+/// A nonzero complex number stored as `(ln |z|, arg z)` so that `z^e` can be
+/// evaluated directly for any real exponent instead of by repeated
+/// multiplication.
+#[derive(Clone, Copy, Debug)]
+struct LogComplex {
+    ln_r: f64,
+    theta: f64,
+}
+
+impl LogComplex {
+    fn new(z: Complex64) -> Result<Self, FftError> {
+        let r = libm::hypot(z.re, z.im);
+        if r == 0.0 || !r.is_finite() {
+            return Err(FftError::InvalidValue);
+        }
+        Ok(Self {
+            ln_r: libm::log(r),
+            theta: libm::atan2(z.im, z.re),
+        })
+    }
+
+    /// `z^e` on the principal branch.
+    fn pow(self, e: f64) -> Complex64 {
+        let mag = libm::exp(e * self.ln_r);
+        let phase = e * self.theta;
+        Complex64::new(mag * libm::cos(phase), mag * libm::sin(phase))
+    }
+}
+
+fn cdiv(a: Complex64, b: Complex64) -> Complex64 {
+    let d = b.re * b.re + b.im * b.im;
+    Complex64::new(
+        (a.re * b.re + a.im * b.im) / d,
+        (a.im * b.re - a.re * b.im) / d,
+    )
+}
+
+fn to_t<T: Float>(c: Complex64) -> Complex<T> {
+    Complex::new(T::from_f64(c.re), T::from_f64(c.im))
+}
+
+/// Circular convolution of `buf` with a kernel whose FFT is `kernel`.
+fn convolve<T: Float>(
+    fft: &ScalarFftImpl<T>,
+    buf: &mut [Complex<T>],
+    kernel: &[Complex<T>],
+) -> Result<(), FftError> {
+    fft.fft(buf)?;
+    for (b, &k) in buf.iter_mut().zip(kernel.iter()) {
+        *b = b.mul(k);
+    }
+    fft.ifft(buf)
+}
+
+/// Precomputed Bluestein plan for an `n`-point input and `m`-point CZT.
+pub struct CztPlan<T: Float> {
+    n: usize,
+    m: usize,
+    /// `a^-j · w^(j²/2)` applied to the input.
+    pre: Vec<Complex<T>>,
+    /// `w^(k²/2)` applied to the output.
+    post: Vec<Complex<T>>,
+    /// FFT of the circular chirp `w^(-j²/2)`.
+    kernel: Vec<Complex<T>>,
+    fft: ScalarFftImpl<T>,
+    scratch: Vec<Complex<T>>,
+}
+
+impl<T: Float> CztPlan<T> {
+    /// Plan a CZT of `n` input samples evaluated at `m` points
+    /// `z_k = a · w^-k`.
+    ///
+    /// `w` and `a` are given in `f64` so the chirps are accurate for every
+    /// precision `T`.  Returns [`FftError::InvalidValue`] if either is zero
+    /// or not finite.
+    pub fn new(n: usize, m: usize, w: Complex64, a: Complex64) -> Result<Self, FftError> {
+        if n == 0 || m == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        let lw = LogComplex::new(w)?;
+        let la = LogComplex::new(a)?;
+        let len = (n + m - 1).next_power_of_two();
+        let pre = (0..n)
+            .map(|j| {
+                let j = j as f64;
+                to_t(la.pow(-j).mul(lw.pow(j * j / 2.0)))
+            })
+            .collect();
+        let post = (0..m).map(|k| to_t(lw.pow((k * k) as f64 / 2.0))).collect();
+        let mut kernel = vec![Complex::zero(); len];
+        for (j, v) in kernel.iter_mut().enumerate().take(m) {
+            *v = to_t(lw.pow(-((j * j) as f64) / 2.0));
+        }
+        for j in 1..n {
+            kernel[len - j] = to_t(lw.pow(-((j * j) as f64) / 2.0));
+        }
+        let fft = ScalarFftImpl::default();
+        fft.fft(&mut kernel)?;
+        Ok(Self {
+            n,
+            m,
+            pre,
+            post,
+            kernel,
+            fft,
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Number of input samples.
+    pub fn input_len(&self) -> usize {
+        self.n
+    }
+
+    /// Number of output points.
+    pub fn output_len(&self) -> usize {
+        self.m
+    }
+
+    /// Length of the scratch buffer required by [`Self::czt_with_scratch`].
+    pub fn scratch_len(&self) -> usize {
+        self.kernel.len()
+    }
+
+    /// Compute the CZT using caller-provided scratch of at least
+    /// [`Self::scratch_len`] elements.
+    pub fn czt_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        if input.len() != self.n || output.len() != self.m {
+            return Err(FftError::MismatchedLengths);
+        }
+        let len = self.kernel.len();
+        if scratch.len() < len {
+            return Err(FftError::MismatchedLengths);
+        }
+        let buf = &mut scratch[..len];
+        for ((b, &x), &p) in buf.iter_mut().zip(input.iter()).zip(self.pre.iter()) {
+            *b = x.mul(p);
+        }
+        buf[self.n..].fill(Complex::zero());
+        convolve(&self.fft, buf, &self.kernel)?;
+        for ((out, &b), &p) in output.iter_mut().zip(buf.iter()).zip(self.post.iter()) {
+            *out = b.mul(p);
+        }
+        Ok(())
+    }
+
+    /// Compute the CZT, reusing an internal scratch buffer.
+    pub fn czt(&mut self, input: &[Complex<T>], output: &mut [Complex<T>]) -> Result<(), FftError> {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.resize(self.scratch_len(), Complex::zero());
+        let res = self.czt_with_scratch(input, output, &mut scratch);
+        self.scratch = scratch;
+        res
+    }
+}
+
+/// Precomputed plan for the inverse of an `n`-point square CZT.
+///
+/// Recovering `x` from `X` amounts to solving a Vandermonde system on the
+/// nodes `w^k`.  After the Bluestein substitution this becomes a symmetric
+/// Toeplitz system whose inverse has a closed-form first column, so the
+/// Gohberg–Semencul formula applies it with four FFT convolutions
+/// (Sukhoy & Stoytchev, 2019).  The problem is well conditioned on the unit
+/// circle; contours that spiral in or out lose accuracy quickly as `n` grows.
+pub struct IcztPlan<T: Float> {
+    n: usize,
+    /// `w^(-k²/2)` applied to the input spectrum.
+    pre: Vec<Complex<T>>,
+    /// `a^j · w^(-j²/2) / u_0` applied to the output.
+    post: Vec<Complex<T>>,
+    /// FFT of the first column `u` of the inverse Toeplitz matrix.
+    kernel_u: Vec<Complex<T>>,
+    /// FFT of `[0, u_(n-1), .., u_1]`.
+    kernel_s: Vec<Complex<T>>,
+    fft: ScalarFftImpl<T>,
+    scratch: Vec<Complex<T>>,
+}
+
+impl<T: Float> IcztPlan<T> {
+    /// Plan the inverse of an `n`-point CZT with parameters `w` and `a`.
+    ///
+    /// Returns [`FftError::InvalidValue`] if `w` or `a` is zero or not
+    /// finite, or if the contour repeats a point (`w^k == 1` for some
+    /// `0 < k < n`), in which case the transform is not invertible.
+    pub fn new(n: usize, w: Complex64, a: Complex64) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        let lw = LogComplex::new(w)?;
+        let la = LogComplex::new(a)?;
+        let one = Complex64::new(1.0, 0.0);
+        // p[k] = Π_{j=1..k} (1 - w^j)
+        let mut p = vec![one; n];
+        for k in 1..n {
+            let d = one.sub(lw.pow(k as f64));
+            if libm::hypot(d.re, d.im) < 1e-12 {
+                return Err(FftError::InvalidValue);
+            }
+            p[k] = p[k - 1].mul(d);
+        }
+        let mut u = Vec::with_capacity(n);
+        for i in 0..n {
+            let r = n - 1 - i;
+            let sign = if r.is_multiple_of(2) { 1.0 } else { -1.0 };
+            let num = lw
+                .pow((i * i) as f64 / 2.0 + (r * (r + 1)) as f64 / 2.0)
+                .mul(Complex64::new(sign, 0.0));
+            let v = cdiv(num, p[r].mul(p[n - 1 - r]));
+            if !(v.re.is_finite() && v.im.is_finite()) {
+                return Err(FftError::InvalidValue);
+            }
+            u.push(v);
+        }
+        let u0 = u[0];
+        if u0.re == 0.0 && u0.im == 0.0 {
+            return Err(FftError::InvalidValue);
+        }
+        let len = (2 * n - 1).next_power_of_two();
+        let mut kernel_u = vec![Complex::zero(); len];
+        let mut kernel_s = vec![Complex::zero(); len];
+        for i in 0..n {
+            kernel_u[i] = to_t(u[i]);
+            if i > 0 {
+                kernel_s[i] = to_t(u[n - i]);
+            }
+        }
+        let fft = ScalarFftImpl::default();
+        fft.fft(&mut kernel_u)?;
+        fft.fft(&mut kernel_s)?;
+        let pre = (0..n)
+            .map(|k| to_t(lw.pow(-((k * k) as f64) / 2.0)))
+            .collect();
+        let post = (0..n)
+            .map(|j| {
+                let jf = j as f64;
+                to_t(cdiv(la.pow(jf).mul(lw.pow(-jf * jf / 2.0)), u0))
+            })
+            .collect();
+        Ok(Self {
+            n,
+            pre,
+            post,
+            kernel_u,
+            kernel_s,
+            fft,
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Transform length.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Always `false`; [`new`](Self::new) rejects `n == 0`.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Length of the scratch buffer required by [`Self::iczt_with_scratch`].
+    pub fn scratch_len(&self) -> usize {
+        self.kernel_u.len()
+    }
+
+    /// Apply `L(g) · J · L(g) · J` to the pre-twiddled input, leaving the
+    /// result in `buf[..n]`. `L(g)` is the lower-triangular Toeplitz matrix
+    /// with first column `g` and `J` reverses the order.
+    fn apply_pair(
+        &self,
+        input: &[Complex<T>],
+        buf: &mut [Complex<T>],
+        kernel: &[Complex<T>],
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        for (k, b) in buf.iter_mut().take(n).enumerate() {
+            let j = n - 1 - k;
+            *b = input[j].mul(self.pre[j]);
+        }
+        buf[n..].fill(Complex::zero());
+        convolve(&self.fft, buf, kernel)?;
+        buf[..n].reverse();
+        buf[n..].fill(Complex::zero());
+        convolve(&self.fft, buf, kernel)
+    }
+
+    /// Compute the inverse CZT using caller-provided scratch of at least
+    /// [`Self::scratch_len`] elements.
+    pub fn iczt_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        if input.len() != self.n || output.len() != self.n {
+            return Err(FftError::MismatchedLengths);
+        }
+        let len = self.kernel_u.len();
+        if scratch.len() < len {
+            return Err(FftError::MismatchedLengths);
+        }
+        let buf = &mut scratch[..len];
+        self.apply_pair(input, buf, &self.kernel_u)?;
+        output.copy_from_slice(&buf[..self.n]);
+        self.apply_pair(input, buf, &self.kernel_s)?;
+        for ((out, &b), &p) in output.iter_mut().zip(buf.iter()).zip(self.post.iter()) {
+            *out = out.sub(b).mul(p);
+        }
+        Ok(())
+    }
+
+    /// Compute the inverse CZT, reusing an internal scratch buffer.
+    pub fn iczt(
+        &mut self,
+        input: &[Complex<T>],
+        output: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.resize(self.scratch_len(), Complex::zero());
+        let res = self.iczt_with_scratch(input, output, &mut scratch);
+        self.scratch = scratch;
+        res
+    }
+}
+
+/// Compute the `m`-point CZT of `input` along `z_k = a · w^-k`.
+pub fn czt<T: Float>(
+    input: &[Complex<T>],
+    m: usize,
+    w: Complex64,
+    a: Complex64,
+) -> Result<Vec<Complex<T>>, FftError> {
+    let mut plan = CztPlan::new(input.len(), m, w, a)?;
+    let mut output = vec![Complex::zero(); m];
+    plan.czt(input, &mut output)?;
+    Ok(output)
+}
+
+/// Invert a square CZT computed with the same `w` and `a`.
+pub fn iczt<T: Float>(
+    input: &[Complex<T>],
+    w: Complex64,
+    a: Complex64,
+) -> Result<Vec<Complex<T>>, FftError> {
+    let mut plan = IcztPlan::new(input.len(), w, a)?;
+    let mut output = vec![Complex::zero(); input.len()];
+    plan.iczt(input, &mut output)?;
+    Ok(output)
+}
+
+/// Evaluate the spectrum of `signal` at `m` frequencies
+/// `f_start + k · (f_end - f_start) / m` (in Hz, `k = 0..m`).
+///
+/// This is a CZT along an arc of the unit circle, giving a much finer
+/// frequency grid over a narrow band than a zero-padded FFT of comparable
+/// cost.  Like `scipy.signal.zoom_fft`, `f_end` itself is excluded.
+pub fn zoom_fft<T: Float>(
+    signal: &[Complex<T>],
+    f_start: f64,
+    f_end: f64,
+    m: usize,
+    sample_rate: f64,
+) -> Result<Vec<Complex<T>>, FftError> {
+    if !(sample_rate > 0.0 && sample_rate.is_finite() && f_start.is_finite() && f_end.is_finite()) {
+        return Err(FftError::InvalidValue);
+    }
+    if m == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    let tau = 2.0 * core::f64::consts::PI;
+    let a_phase = tau * f_start / sample_rate;
+    let w_phase = -tau * (f_end - f_start) / (m as f64 * sample_rate);
+    let a = Complex64::new(libm::cos(a_phase), libm::sin(a_phase));
+    let w = Complex64::new(libm::cos(w_phase), libm::sin(w_phase));
+    czt(signal, m, w, a)
+}
 
 /// Compute the CZT of a real signal at M output bins
 /// - `input`: real-valued signal
 /// - `m`: number of output bins
 /// - `w`: complex ratio between bins (e.g., exp(-j*2pi/M))
 /// - `a`: complex starting point (e.g., 1.0)
+///
+/// Uses [`CztPlan`] internally; degenerate parameters (`a` or `w` equal to
+/// zero) fall back to direct evaluation.
 pub fn czt_f32(input: &[f32], m: usize, w: (f32, f32), a: (f32, f32)) -> Vec<(f32, f32)> {
+    if input.is_empty() || m == 0 {
+        return vec![(0.0, 0.0); m];
+    }
+    let signal: Vec<Complex32> = input.iter().map(|&x| Complex32::new(x, 0.0)).collect();
+    let w64 = Complex64::new(w.0 as f64, w.1 as f64);
+    let a64 = Complex64::new(a.0 as f64, a.1 as f64);
+    match czt(&signal, m, w64, a64) {
+        Ok(out) => out.iter().map(|c| (c.re, c.im)).collect(),
+        Err(_) => czt_direct(input, m, w, a),
+    }
+}
+
+/// Direct `O(N·M)` evaluation used for degenerate contour parameters.
+fn czt_direct(input: &[f32], m: usize, w: (f32, f32), a: (f32, f32)) -> Vec<(f32, f32)> {
     let (wr, wi) = w;
     let (ar, ai) = a;
     let denom = ar * ar + ai * ai;
@@ -79,4 +485,121 @@ mod tests {
         assert!((y[0].0 - 2.0).abs() < 1e-5 && y[0].1.abs() < 1e-5);
         assert!((y[1].0).abs() < 1e-5 && (y[1].1 - 2.0).abs() < 1e-5);
     }
+
+    fn naive(x: &[Complex64], m: usize, w: Complex64, a: Complex64) -> Vec<Complex64> {
+        let (lw, la) = (LogComplex::new(w).unwrap(), LogComplex::new(a).unwrap());
+        (0..m)
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .fold(Complex64::zero(), |acc, (j, &v)| {
+                        let z = la.pow(-(j as f64)).mul(lw.pow((j * k) as f64));
+                        acc.add(v.mul(z))
+                    })
+            })
+            .collect()
+    }
+
+    fn signal(n: usize) -> Vec<Complex64> {
+        (0..n)
+            .map(|i| Complex64::new((i as f64 * 0.7).sin(), (i as f64 * 0.3).cos()))
+            .collect()
+    }
+
+    #[test]
+    fn test_czt_plan_matches_naive() {
+        let w = Complex64::new(0.99, -0.12);
+        let a = Complex64::new(0.95, 0.2);
+        for &(n, m) in &[(1usize, 1usize), (5, 9), (16, 16), (33, 7)] {
+            let x = signal(n);
+            let y = czt(&x, m, w, a).unwrap();
+            for (p, q) in y.iter().zip(naive(&x, m, w, a).iter()) {
+                assert!(
+                    (p.re - q.re).abs() < 1e-9 && (p.im - q.im).abs() < 1e-9,
+                    "n={} m={} {:?} vs {:?}",
+                    n,
+                    m,
+                    p,
+                    q
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_czt_matches_fft_f32() {
+        let n = 12;
+        let x: Vec<Complex32> = signal(n)
+            .iter()
+            .map(|c| Complex32::new(c.re as f32, c.im as f32))
+            .collect();
+        let angle = -2.0 * core::f64::consts::PI / n as f64;
+        let w = Complex64::new(angle.cos(), angle.sin());
+        let y = czt(&x, n, w, Complex64::new(1.0, 0.0)).unwrap();
+        let mut expected = x.clone();
+        ScalarFftImpl::<f32>::default().fft(&mut expected).unwrap();
+        for (p, q) in y.iter().zip(expected.iter()) {
+            assert!((p.re - q.re).abs() < 1e-4 && (p.im - q.im).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_iczt_roundtrip() {
+        let cases = [
+            (64, Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)),
+            (10, Complex64::new(0.0, -0.3), Complex64::new(0.9, 0.2)),
+        ];
+        for &(n, w_log, a) in &cases {
+            let w = if w_log.im == 0.0 {
+                let t = -2.0 * core::f64::consts::PI / n as f64;
+                Complex64::new(t.cos(), t.sin())
+            } else {
+                Complex64::new(1.01 * w_log.im.cos(), 1.01 * w_log.im.sin())
+            };
+            let x = signal(n);
+            let y = czt(&x, n, w, a).unwrap();
+            let back = iczt(&y, w, a).unwrap();
+            for (p, q) in x.iter().zip(back.iter()) {
+                assert!((p.re - q.re).abs() < 1e-8 && (p.im - q.im).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iczt_rejects_repeated_nodes() {
+        // w = -1 visits z = 1 twice for n > 2.
+        assert!(
+            IcztPlan::<f64>::new(4, Complex64::new(-1.0, 0.0), Complex64::new(1.0, 0.0)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_zoom_fft_resolves_tone() {
+        let fs = 1000.0;
+        let f0 = 123.4;
+        let x: Vec<Complex32> = (0..256)
+            .map(|i| {
+                let t = i as f64 / fs;
+                Complex32::new((2.0 * core::f64::consts::PI * f0 * t).cos() as f32, 0.0)
+            })
+            .collect();
+        let (f_start, f_end, m) = (120.0, 127.0, 70);
+        let y = zoom_fft(&x, f_start, f_end, m, fs).unwrap();
+        let peak = y
+            .iter()
+            .enumerate()
+            .max_by(|a, b| {
+                let ma = a.1.re * a.1.re + a.1.im * a.1.im;
+                let mb = b.1.re * b.1.re + b.1.im * b.1.im;
+                ma.partial_cmp(&mb).unwrap()
+            })
+            .unwrap()
+            .0;
+        let f_peak = f_start + peak as f64 * (f_end - f_start) / m as f64;
+        assert!((f_peak - f0).abs() <= 0.1, "peak at {}", f_peak);
+        assert_eq!(
+            zoom_fft(&x, 0.0, 1.0, 4, 0.0).unwrap_err(),
+            FftError::InvalidValue
+        );
+    }
 }
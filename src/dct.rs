@@ -3,27 +3,61 @@
 //! no_std + alloc compatible
 
 extern crate alloc;
-use crate::fft::{Complex32, FftError, ScalarFftImpl};
-#[cfg(not(feature = "std"))]
+use crate::fft::{Complex32, FftError, FftImpl, Normalization, ScalarFftImpl, SharedFftPlan};
+use crate::num::as_real;
+#[cfg(not(any(feature = "std", feature = "parallel")))]
 use crate::num::Float;
-use crate::rfft::RfftPlanner;
+#[cfg(feature = "parallel")]
+use crate::parallel::Parallelism;
+use crate::rfft::{RealFftPlan, RfftPlanner};
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::f32::consts::PI;
+use core::f32::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
 use hashbrown::HashMap;
 
-/// Planner that caches cosine tables for FFT-based DCT routines.
+pub use crate::mcu::dct::{dct1_inplace_stack, dct2_inplace_stack};
+
+/// `exp(-iπk/2N)` for `k = 0..n`.
+fn quarter_wave_table(n: usize) -> Arc<[Complex32]> {
+    (0..n)
+        .map(|k| {
+            let angle = PI * k as f32 / (2.0 * n as f32);
+            Complex32::new(angle.cos(), -angle.sin())
+        })
+        .collect()
+}
+
+/// `exp(-iπ(2k+1)/4N)` for `k = 0..n`.
+fn dct4_post_table(n: usize) -> Arc<[Complex32]> {
+    (0..n)
+        .map(|k| {
+            let angle = PI * (2 * k + 1) as f32 / (4.0 * n as f32);
+            Complex32::new(angle.cos(), -angle.sin())
+        })
+        .collect()
+}
+
+/// Planner that caches twiddle tables for FFT-based DCT routines.
 ///
 /// This mirrors the [`RfftPlanner`] used for real FFTs but stores
-/// cosine factors keyed by transform length.  The planned closures reuse
+/// twiddle factors keyed by transform length.  The planned closures reuse
 /// both the trigonometric tables and the internal [`RfftPlanner`]
 /// to avoid redundant allocations on repeated transforms.
+///
+/// All four DCT types are computed in `O(n log n)` for any length (even or
+/// odd) and match the reference [`dct1`], [`dct2`], [`dct3`] and [`dct4`]
+/// functions, including their scaling.
 pub struct DctPlanner {
-    /// Cosine tables indexed by transform length.
-    cache: HashMap<usize, Arc<[f32]>>,
+    /// Quarter-wave twiddles `exp(-iπk/2N)` indexed by transform length.
+    cache: HashMap<usize, Arc<[Complex32]>>,
+    /// DCT-IV post-twiddles `exp(-iπ(2k+1)/4N)` indexed by transform length.
+    cache4: HashMap<usize, Arc<[Complex32]>>,
     /// Underlying real-FFT planner used by the kernels.
     rfft: RfftPlanner<f32>,
+    /// Complex FFT shared by every planned transform so its own twiddle and
+    /// Bluestein caches survive between calls.
+    fft: ScalarFftImpl<f32>,
     /// Reusable work buffers.
     buf: Vec<f32>,
     spectrum: Vec<Complex32>,
@@ -40,67 +74,522 @@ impl DctPlanner {
     pub fn new() -> Self {
         Self {
             cache: HashMap::new(),
+            cache4: HashMap::new(),
             rfft: RfftPlanner::new(),
+            fft: ScalarFftImpl::default(),
             buf: Vec::new(),
             spectrum: Vec::new(),
         }
     }
 
-    /// Retrieve or build the cosine table for length `n`.
-    fn get_cos_table(&mut self, n: usize) -> Arc<[f32]> {
-        if !self.cache.contains_key(&n) {
-            let mut table = Vec::with_capacity(n);
-            for k in 0..n {
-                table.push((PI * k as f32 / (2.0 * n as f32)).cos());
-            }
-            self.cache.insert(n, Arc::from(table));
+    /// Retrieve or build the `exp(-iπk/2N)` table for length `n`.
+    fn get_twiddles(&mut self, n: usize) -> Arc<[Complex32]> {
+        Arc::clone(self.cache.entry(n).or_insert_with(|| quarter_wave_table(n)))
+    }
+
+    /// Retrieve or build the `exp(-iπ(2k+1)/4N)` table for length `n`.
+    fn get_dct4_twiddles(&mut self, n: usize) -> Arc<[Complex32]> {
+        Arc::clone(self.cache4.entry(n).or_insert_with(|| dct4_post_table(n)))
+    }
+
+    /// Make sure the work buffers hold at least `real` and `complex` samples.
+    fn reserve(&mut self, real: usize, complex: usize) {
+        if self.buf.len() < real {
+            self.buf.resize(real, 0.0);
+        }
+        if self.spectrum.len() < complex {
+            self.spectrum.resize(complex, Complex32::zero());
         }
-        Arc::clone(self.cache.get(&n).unwrap())
     }
 
-    /// DCT-II kernel using a precomputed cosine table and the internal
+    /// DCT-I kernel: the DCT-I of `x` is the real FFT of its even extension
+    /// `[x0, x1, .., x(n-1), x(n-2), .., x1]` of length `2(n - 1)`.
+    fn dct1_kernel(&mut self, input: &[f32], output: &mut [f32]) -> Result<(), FftError> {
+        let n = input.len();
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if output.len() != n {
+            return Err(FftError::MismatchedLengths);
+        }
+        if n == 1 {
+            output[0] = 2.0 * input[0];
+            return Ok(());
+        }
+        let m = 2 * (n - 1);
+        self.reserve(m, n);
+        let buf = &mut self.buf[..m];
+        buf[..n].copy_from_slice(input);
+        for i in 1..n - 1 {
+            buf[m - i] = input[i];
+        }
+        let spec = &mut self.spectrum[..n];
+        self.rfft.rfft(&self.fft, buf, spec)?;
+        for (out, s) in output.iter_mut().zip(spec.iter()) {
+            *out = s.re;
+        }
+        Ok(())
+    }
+
+    /// DCT-II kernel using a precomputed twiddle table and the internal
     /// [`RfftPlanner`].
     fn dct2_with_table(
         &mut self,
-        input: &mut [f32],
+        input: &[f32],
         output: &mut [f32],
-        cos: &[f32],
+        tw: &[Complex32],
     ) -> Result<(), FftError> {
         let n = input.len();
-        if output.len() != n {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if output.len() != n || tw.len() != n {
             return Err(FftError::MismatchedLengths);
         }
         let m = 2 * n;
-        if self.buf.len() < m {
-            self.buf.resize(m, 0.0);
-        }
+        self.reserve(m, n + 1);
         let buf = &mut self.buf[..m];
         for i in 0..n {
             buf[i] = input[i];
             buf[m - 1 - i] = input[i];
         }
-        if self.spectrum.len() < n + 1 {
-            self.spectrum.resize(n + 1, Complex32::zero());
+        let spec = &mut self.spectrum[..n + 1];
+        self.rfft.rfft(&self.fft, buf, spec)?;
+        for k in 0..n {
+            output[k] = 0.5 * (spec[k].re * tw[k].re - spec[k].im * tw[k].im);
+        }
+        Ok(())
+    }
+
+    /// DCT-III kernel: builds the Hermitian half-spectrum whose inverse real
+    /// FFT of length `2n` yields the DCT-III in its first `n` samples.
+    fn dct3_with_table(
+        &mut self,
+        input: &[f32],
+        output: &mut [f32],
+        tw: &[Complex32],
+    ) -> Result<(), FftError> {
+        let n = input.len();
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if output.len() != n || tw.len() != n {
+            return Err(FftError::MismatchedLengths);
         }
+        let m = 2 * n;
+        self.reserve(m, n + 1);
         let spec = &mut self.spectrum[..n + 1];
-        let fft = ScalarFftImpl::<f32>::default();
-        self.rfft.rfft(&fft, buf, spec)?;
+        spec[0] = Complex32::new(input[0], 0.0);
+        for k in 1..n {
+            spec[k] = Complex32::new(input[k] * tw[k].re, -input[k] * tw[k].im);
+        }
+        spec[n] = Complex32::zero();
+        let buf = &mut self.buf[..m];
+        self.rfft.irfft(&self.fft, spec, buf)?;
+        let scale = n as f32;
+        for (out, &b) in output.iter_mut().zip(buf.iter()) {
+            *out = b * scale;
+        }
+        Ok(())
+    }
+
+    /// DCT-IV kernel: pre-twiddles the input by `exp(-iπn/2N)`, runs a
+    /// zero-padded complex FFT of length `2n` and post-twiddles by
+    /// `exp(-iπ(2k+1)/4N)`.
+    fn dct4_with_tables(
+        &mut self,
+        input: &[f32],
+        output: &mut [f32],
+        pre: &[Complex32],
+        post: &[Complex32],
+    ) -> Result<(), FftError> {
+        let n = input.len();
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if output.len() != n || pre.len() != n || post.len() != n {
+            return Err(FftError::MismatchedLengths);
+        }
+        let m = 2 * n;
+        self.reserve(0, m);
+        let spec = &mut self.spectrum[..m];
+        for i in 0..n {
+            spec[i] = Complex32::new(input[i] * pre[i].re, input[i] * pre[i].im);
+        }
+        spec[n..].fill(Complex32::zero());
+        self.fft.fft(spec)?;
         for k in 0..n {
-            let angle = PI * k as f32 / (2.0 * n as f32);
-            let sin = angle.sin();
-            output[k] = 0.5 * (spec[k].re * cos[k] + spec[k].im * sin);
+            output[k] = spec[k].re * post[k].re - spec[k].im * post[k].im;
         }
         Ok(())
     }
 
+    /// Plan a DCT-I of length `len`, returning a closure that
+    /// reuses the internal real-FFT planner for subsequent executions.
+    pub fn plan_dct1(
+        &mut self,
+        len: usize,
+    ) -> impl FnMut(&mut [f32], &mut [f32]) -> Result<(), FftError> + '_ {
+        move |input, output| {
+            if input.len() != len {
+                return Err(FftError::MismatchedLengths);
+            }
+            self.dct1_kernel(input, output)
+        }
+    }
+
     /// Plan a DCT-II of length `len`, returning a closure that
     /// reuses cached factors for subsequent executions.
     pub fn plan_dct2(
         &mut self,
         len: usize,
     ) -> impl FnMut(&mut [f32], &mut [f32]) -> Result<(), FftError> + '_ {
-        let cos = self.get_cos_table(len);
-        move |input, output| self.dct2_with_table(input, output, &cos)
+        let tw = self.get_twiddles(len);
+        move |input, output| {
+            if input.len() != len {
+                return Err(FftError::MismatchedLengths);
+            }
+            self.dct2_with_table(input, output, &tw)
+        }
+    }
+
+    /// Plan a DCT-III of length `len`, returning a closure that
+    /// reuses cached factors for subsequent executions.
+    pub fn plan_dct3(
+        &mut self,
+        len: usize,
+    ) -> impl FnMut(&mut [f32], &mut [f32]) -> Result<(), FftError> + '_ {
+        let tw = self.get_twiddles(len);
+        move |input, output| {
+            if input.len() != len {
+                return Err(FftError::MismatchedLengths);
+            }
+            self.dct3_with_table(input, output, &tw)
+        }
+    }
+
+    /// Plan a DCT-IV of length `len`, returning a closure that
+    /// reuses cached factors for subsequent executions.
+    pub fn plan_dct4(
+        &mut self,
+        len: usize,
+    ) -> impl FnMut(&mut [f32], &mut [f32]) -> Result<(), FftError> + '_ {
+        let pre = self.get_twiddles(len);
+        let post = self.get_dct4_twiddles(len);
+        move |input, output| {
+            if input.len() != len {
+                return Err(FftError::MismatchedLengths);
+            }
+            self.dct4_with_tables(input, output, &pre, &post)
+        }
+    }
+}
+
+/// DCT variant computed by a [`DctPlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DctType {
+    I,
+    II,
+    III,
+    IV,
+}
+
+impl DctType {
+    /// Type that inverts this one up to scaling: DCT-II and DCT-III invert
+    /// each other, DCT-I and DCT-IV are their own inverses.
+    pub fn inverse(self) -> Self {
+        match self {
+            DctType::II => DctType::III,
+            DctType::III => DctType::II,
+            other => other,
+        }
+    }
+
+    /// Factor by which the unscaled transform followed by its
+    /// [`inverse`](Self::inverse) multiplies a length-`n` signal.
+    fn gain(self, n: usize) -> f64 {
+        match self {
+            // dct1 of a single sample doubles it
+            DctType::I if n == 1 => 4.0,
+            DctType::I => 2.0 * (n - 1) as f64,
+            _ => n as f64 / 2.0,
+        }
+    }
+}
+
+enum DctKernel {
+    /// DCT-I of a single sample.
+    Single,
+    /// DCT-I: real FFT of the length `2(n - 1)` even extension.
+    Even(RealFftPlan<f32>),
+    /// DCT-II and DCT-III: length `2n` real FFT plus quarter-wave twiddles.
+    Real(RealFftPlan<f32>, Arc<[Complex32]>),
+    /// DCT-IV: zero-padded length `2n` complex FFT with pre/post twiddles.
+    Complex {
+        fft: SharedFftPlan<f32>,
+        pre: Arc<[Complex32]>,
+        post: Arc<[Complex32]>,
+    },
+}
+
+/// Immutable, precomputed DCT of one type and length.
+///
+/// Under the default [`Normalization::Backward`] it produces the same values
+/// as [`dct1`]–[`dct4`] and the [`DctPlanner`] closures, but executes
+/// through [`execute_with_scratch`](Self::execute_with_scratch) without
+/// touching the allocator. [`inverse_with_scratch`](Self::inverse_with_scratch)
+/// undoes it exactly. The plan is `Send + Sync`.
+///
+/// With [`Normalization::Ortho`] the transform matrix is orthogonal, as in
+/// scipy's `norm="ortho"`: DCT-I weights its first and last samples by `√2`
+/// on input and `1/√2` on output, DCT-II scales output `0` by `1/√2` and
+/// DCT-III scales input `0` by `√2`.
+///
+/// ```
+/// use kofft::dct::{DctPlan, DctType};
+/// use kofft::fft::Complex32;
+///
+/// let plan = DctPlan::new(DctType::II, 12).unwrap();
+/// let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+/// let input = [1.0f32; 12];
+/// let mut output = [0.0f32; 12];
+/// plan.execute_with_scratch(&input, &mut output, &mut scratch).unwrap();
+/// assert!((output[0] - 12.0).abs() < 1e-4);
+/// ```
+pub struct DctPlan {
+    kind: DctType,
+    n: usize,
+    kernel: DctKernel,
+    norm: Normalization,
+    #[cfg(feature = "parallel")]
+    parallelism: Parallelism,
+}
+
+impl DctPlan {
+    /// Precompute a DCT of type `kind` and length `n`.
+    pub fn new(kind: DctType, n: usize) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        let kernel = match kind {
+            DctType::I if n == 1 => DctKernel::Single,
+            DctType::I => DctKernel::Even(RealFftPlan::new(2 * (n - 1))?),
+            DctType::II | DctType::III => {
+                DctKernel::Real(RealFftPlan::new(2 * n)?, quarter_wave_table(n))
+            }
+            DctType::IV => DctKernel::Complex {
+                fft: SharedFftPlan::new(2 * n)?,
+                pre: quarter_wave_table(n),
+                post: dct4_post_table(n),
+            },
+        };
+        Ok(Self {
+            kind,
+            n,
+            kernel,
+            norm: Normalization::Backward,
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
+        })
+    }
+
+    /// Use `norm` instead of the default [`Normalization::Backward`].
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Normalization applied by the forward and inverse transforms.
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Spread the buffers of batch APIs given this plan over `parallelism`
+    /// instead of Rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Workers used by the batch APIs this plan is passed to.
+    #[cfg(feature = "parallel")]
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// DCT type of this plan.
+    pub fn kind(&self) -> DctType {
+        self.kind
+    }
+
+    /// Transform length.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Exact scratch length, in complex samples, for
+    /// [`execute_with_scratch`](Self::execute_with_scratch) and
+    /// [`inverse_with_scratch`](Self::inverse_with_scratch).
+    pub fn scratch_len(&self) -> usize {
+        let n = self.n;
+        match &self.kernel {
+            DctKernel::Single => 0,
+            // real buffer of 2(n-1) samples and n spectrum bins
+            DctKernel::Even(rfft) => (n - 1) + n + rfft.scratch_len(),
+            // real buffer of 2n samples and n+1 spectrum bins
+            DctKernel::Real(rfft, _) => n + (n + 1) + rfft.scratch_len(),
+            DctKernel::Complex { fft, .. } => 2 * n + fft.scratch_len(),
+        }
+    }
+
+    /// Transform `input` into `output` (both of length [`size`](Self::size))
+    /// using `scratch` of at least [`scratch_len`](Self::scratch_len).
+    pub fn execute_with_scratch(
+        &self,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        self.run(self.kind, false, input, output, scratch)
+    }
+
+    /// Invert [`execute_with_scratch`](Self::execute_with_scratch) under the
+    /// plan's normalization, running the DCT of type
+    /// [`kind().inverse()`](DctType::inverse).
+    pub fn inverse_with_scratch(
+        &self,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        self.run(self.kind.inverse(), true, input, output, scratch)
+    }
+
+    fn run(
+        &self,
+        kind: DctType,
+        inverse: bool,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        if input.len() != n || output.len() != n || scratch.len() < self.scratch_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        self.execute_unscaled(kind, input, output, scratch)?;
+        if self.norm == Normalization::Ortho {
+            orthogonalize(kind, input, output);
+        }
+        let (forward, backward) = self.norm.factors::<f32>(kind.gain(n));
+        let scale = if inverse { backward } else { forward };
+        if scale != 1.0 {
+            for out in output.iter_mut() {
+                *out *= scale;
+            }
+        }
+        Ok(())
+    }
+
+    /// Unnormalized DCT of type `kind`, which must share this plan's kernel
+    /// (`II` and `III` do). Lengths are checked by the caller.
+    pub(crate) fn execute_unscaled(
+        &self,
+        kind: DctType,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        match &self.kernel {
+            DctKernel::Single => output[0] = 2.0 * input[0],
+            DctKernel::Even(rfft) => {
+                let m = 2 * (n - 1);
+                let (buf, rest) = scratch.split_at_mut(n - 1);
+                let (spec, rest) = rest.split_at_mut(n);
+                let buf = as_real(buf);
+                buf[..n].copy_from_slice(input);
+                for i in 1..n - 1 {
+                    buf[m - i] = input[i];
+                }
+                rfft.rfft_with_scratch(buf, spec, rest)?;
+                for (out, s) in output.iter_mut().zip(spec.iter()) {
+                    *out = s.re;
+                }
+            }
+            DctKernel::Real(rfft, tw) if kind == DctType::II => {
+                let m = 2 * n;
+                let (buf, rest) = scratch.split_at_mut(n);
+                let (spec, rest) = rest.split_at_mut(n + 1);
+                let buf = as_real(buf);
+                for i in 0..n {
+                    buf[i] = input[i];
+                    buf[m - 1 - i] = input[i];
+                }
+                rfft.rfft_with_scratch(buf, spec, rest)?;
+                for k in 0..n {
+                    output[k] = 0.5 * (spec[k].re * tw[k].re - spec[k].im * tw[k].im);
+                }
+            }
+            DctKernel::Real(rfft, tw) => {
+                let (buf, rest) = scratch.split_at_mut(n);
+                let (spec, rest) = rest.split_at_mut(n + 1);
+                spec[0] = Complex32::new(input[0], 0.0);
+                for k in 1..n {
+                    spec[k] = Complex32::new(input[k] * tw[k].re, -input[k] * tw[k].im);
+                }
+                spec[n] = Complex32::zero();
+                let buf = as_real(buf);
+                rfft.irfft_with_scratch(spec, buf, rest)?;
+                let scale = n as f32;
+                for (out, &b) in output.iter_mut().zip(buf.iter()) {
+                    *out = b * scale;
+                }
+            }
+            DctKernel::Complex { fft, pre, post } => {
+                let (spec, rest) = scratch.split_at_mut(2 * n);
+                for i in 0..n {
+                    spec[i] = Complex32::new(input[i] * pre[i].re, input[i] * pre[i].im);
+                }
+                spec[n..].fill(Complex32::zero());
+                fft.fft(spec, rest)?;
+                for k in 0..n {
+                    output[k] = spec[k].re * post[k].re - spec[k].im * post[k].im;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Apply the boundary weights that make the unscaled DCT of type `kind`
+/// orthogonal; the uniform `1/√gain` factor is left to the caller.
+///
+/// Input weights are folded in afterwards by linearity: every affected
+/// column of the unscaled matrix is `±1` or `1/2`.
+fn orthogonalize(kind: DctType, input: &[f32], output: &mut [f32]) {
+    let n = output.len();
+    match kind {
+        // A single sample is already orthonormal once scaled by 1/2.
+        DctType::I if n == 1 => {}
+        DctType::I => {
+            let first = (SQRT_2 - 1.0) * input[0];
+            let last = (SQRT_2 - 1.0) * input[n - 1];
+            for (k, out) in output.iter_mut().enumerate() {
+                *out += first + if k % 2 == 0 { last } else { -last };
+            }
+            output[0] *= FRAC_1_SQRT_2;
+            output[n - 1] *= FRAC_1_SQRT_2;
+        }
+        DctType::II => output[0] *= FRAC_1_SQRT_2,
+        DctType::III => {
+            let first = 0.5 * (SQRT_2 - 1.0) * input[0];
+            for out in output.iter_mut() {
+                *out += first;
+            }
+        }
+        DctType::IV => {}
     }
 }
 
@@ -179,6 +668,19 @@ pub fn dct4(input: &[f32]) -> Vec<f32> {
 mod planner_tests {
     use super::*;
 
+    fn signal(n: usize) -> Vec<f32> {
+        (0..n)
+            .map(|i| ((i * 7 + 3) % 11) as f32 - 5.0 + 0.25 * i as f32)
+            .collect()
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32], tol: f32) {
+        let scale = expected.iter().fold(1.0f32, |m, v| m.max(v.abs()));
+        for (a, b) in actual.iter().zip(expected.iter()) {
+            assert!((a - b).abs() <= tol * scale, "{} vs {}", a, b);
+        }
+    }
+
     #[test]
     fn test_dct2_planner_matches_naive() {
         let mut planner = DctPlanner::new();
@@ -192,6 +694,72 @@ mod planner_tests {
             assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
         }
     }
+
+    #[test]
+    fn test_all_planned_types_match_naive_even_and_odd() {
+        let mut planner = DctPlanner::new();
+        for &n in &[1usize, 2, 3, 5, 8, 9, 16, 30, 31, 64, 100] {
+            let x = signal(n);
+            let mut input = x.clone();
+            let mut output = vec![0.0f32; n];
+
+            planner.plan_dct1(n)(&mut input, &mut output).unwrap();
+            assert_close(&output, &dct1(&x), 1e-4);
+            planner.plan_dct2(n)(&mut input, &mut output).unwrap();
+            assert_close(&output, &dct2(&x), 1e-4);
+            planner.plan_dct3(n)(&mut input, &mut output).unwrap();
+            assert_close(&output, &dct3(&x), 1e-4);
+            planner.plan_dct4(n)(&mut input, &mut output).unwrap();
+            assert_close(&output, &dct4(&x), 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_planned_roundtrips_large() {
+        let n = 4096;
+        let x = signal(n);
+        let mut planner = DctPlanner::new();
+        let mut input = x.clone();
+        let mut spec = vec![0.0f32; n];
+        let mut back = vec![0.0f32; n];
+        planner.plan_dct2(n)(&mut input, &mut spec).unwrap();
+        planner.plan_dct3(n)(&mut spec, &mut back).unwrap();
+        let scale = n as f32 / 2.0;
+        let scaled: Vec<f32> = back.iter().map(|b| b / scale).collect();
+        assert_close(&scaled, &x, 1e-3);
+        let mut plan = planner.plan_dct4(n);
+        plan(&mut input, &mut spec).unwrap();
+        plan(&mut spec, &mut back).unwrap();
+        let scaled: Vec<f32> = back.iter().map(|b| b / scale).collect();
+        assert_close(&scaled, &x, 1e-3);
+    }
+
+    #[test]
+    fn test_planned_length_mismatch() {
+        let mut planner = DctPlanner::new();
+        let mut input = vec![1.0f32; 4];
+        let mut output = vec![0.0f32; 3];
+        assert_eq!(
+            planner.plan_dct3(4)(&mut input, &mut output).unwrap_err(),
+            FftError::MismatchedLengths
+        );
+        assert_eq!(
+            planner.plan_dct1(5)(&mut input, &mut [0.0; 4]).unwrap_err(),
+            FftError::MismatchedLengths
+        );
+        let err = planner.plan_dct2(5)(&mut input, &mut [0.0; 4]).unwrap_err();
+        assert_eq!(err, FftError::MismatchedLengths);
+        let err = planner.plan_dct3(5)(&mut input, &mut [0.0; 4]).unwrap_err();
+        assert_eq!(err, FftError::MismatchedLengths);
+        let err = planner.plan_dct4(5)(&mut input, &mut [0.0; 4]).unwrap_err();
+        assert_eq!(err, FftError::MismatchedLengths);
+        let err = planner.plan_dct2(0)(&mut [], &mut []).unwrap_err();
+        assert_eq!(err, FftError::EmptyInput);
+        let err = planner.plan_dct3(0)(&mut [], &mut []).unwrap_err();
+        assert_eq!(err, FftError::EmptyInput);
+        let err = planner.plan_dct4(0)(&mut [], &mut []).unwrap_err();
+        assert_eq!(err, FftError::EmptyInput);
+    }
 }
 
 #[cfg(feature = "slow")]
@@ -212,53 +780,6 @@ pub mod slow {
     }
 }
 
-/// MCU/stack-only, const-generic, in-place DCT-I for power-of-two sizes (no heap, no alloc).
-///
-/// `N` must be a positive even power of two. Returns an error otherwise.
-pub fn dct1_inplace_stack<const N: usize>(
-    input: &[f32; N],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N < 2 || !N.is_multiple_of(2) || !N.is_power_of_two() {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    let factor = core::f32::consts::PI / (N as f32 - 1.0);
-    for (k, out) in output.iter_mut().enumerate() {
-        let mut sum = input[0]
-            + if k.is_multiple_of(2) {
-                input[N - 1]
-            } else {
-                -input[N - 1]
-            };
-        for (i, &x) in input.iter().take(N - 1).enumerate().skip(1) {
-            sum += 2.0 * x * (factor * i as f32 * k as f32).cos();
-        }
-        *out = sum;
-    }
-    Ok(())
-}
-
-/// MCU/stack-only, const-generic, in-place DCT-II for power-of-two sizes (no heap, no alloc).
-///
-/// `N` must be a positive even power of two. Returns an error otherwise.
-pub fn dct2_inplace_stack<const N: usize>(
-    input: &[f32; N],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    let factor = core::f32::consts::PI / N as f32;
-    for (k, out) in output.iter_mut().enumerate() {
-        let mut sum = 0.0;
-        for (i, &x) in input.iter().enumerate() {
-            sum += x * (factor * (i as f32 + 0.5) * k as f32).cos();
-        }
-        *out = sum;
-    }
-    Ok(())
-}
-
 /// Batch DCT-I
 pub fn batch_i(batches: &mut [Vec<f32>]) {
     for batch in batches.iter_mut() {
@@ -287,6 +808,55 @@ pub fn batch_iv(batches: &mut [Vec<f32>]) {
         batch.copy_from_slice(&out);
     }
 }
+
+/// Parallel batch DCT: transform every buffer in place with `plan` (of any
+/// [`DctType`]), spreading contiguous runs of buffers over
+/// [`plan.parallelism()`](DctPlan::parallelism).
+///
+/// Each worker allocates its scratch once and every buffer must have the
+/// plan's length. The output is bit-identical to running
+/// [`DctPlan::execute_with_scratch`] on each buffer in turn, whatever the pool.
+#[cfg(feature = "parallel")]
+pub fn batch_parallel(plan: &DctPlan, batches: &mut [Vec<f32>]) -> Result<(), FftError> {
+    batch_with_plan(plan, batches, DctPlan::execute_with_scratch)
+}
+
+/// Parallel batch inverse with [`DctPlan::inverse_with_scratch`]; see
+/// [`batch_parallel`].
+#[cfg(feature = "parallel")]
+pub fn batch_inverse_parallel(plan: &DctPlan, batches: &mut [Vec<f32>]) -> Result<(), FftError> {
+    batch_with_plan(plan, batches, DctPlan::inverse_with_scratch)
+}
+
+#[cfg(feature = "parallel")]
+type PlanFn = fn(&DctPlan, &[f32], &mut [f32], &mut [Complex32]) -> Result<(), FftError>;
+
+#[cfg(feature = "parallel")]
+fn batch_with_plan(
+    plan: &DctPlan,
+    batches: &mut [Vec<f32>],
+    transform: PlanFn,
+) -> Result<(), FftError> {
+    let par = plan.parallelism();
+    par.try_for_each_chunk_mut(
+        batches,
+        par.batch_chunk(batches.len()),
+        || {
+            (
+                vec![0.0; plan.size()],
+                vec![Complex32::zero(); plan.scratch_len()],
+            )
+        },
+        |(out, scratch), _, run| {
+            for batch in run {
+                transform(plan, batch, out, scratch)?;
+                batch.copy_from_slice(out);
+            }
+            Ok(())
+        },
+    )
+}
+
 /// Multi-channel DCT-I
 pub fn multi_channel_i(channels: &mut [Vec<f32>]) {
     batch_i(channels)
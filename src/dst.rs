@@ -3,12 +3,20 @@
 //! no_std + alloc compatible
 
 extern crate alloc;
-use crate::fft::FftError;
-use crate::num::Float;
+use crate::dct::{DctPlan, DctType};
+use crate::fft::{Complex32, FftError, Normalization};
+use crate::num::{as_real, Float};
+#[cfg(feature = "parallel")]
+use crate::parallel::Parallelism;
+use crate::rfft::RealFftPlan;
 use alloc::{sync::Arc, vec, vec::Vec};
-use core::f32::consts::PI;
+use core::f32::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
 use hashbrown::HashMap;
 
+pub use crate::mcu::dst::{
+    dst2_inplace_stack, dst2_inplace_stack_fft, dst3_inplace_stack_fft, dst4_inplace_stack,
+};
+
 /// Planner that caches sine tables for various DST types.
 ///
 /// Each table is indexed by the transform length and reused across calls
@@ -85,6 +93,251 @@ impl<T: Float> DstPlanner<T> {
     }
 }
 
+/// DST variant computed by a [`DstPlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DstType {
+    I,
+    II,
+    III,
+    IV,
+}
+
+impl DstType {
+    /// Type that inverts this one up to scaling: DST-II and DST-III invert
+    /// each other, DST-I and DST-IV are their own inverses.
+    pub fn inverse(self) -> Self {
+        match self {
+            DstType::II => DstType::III,
+            DstType::III => DstType::II,
+            other => other,
+        }
+    }
+
+    /// Factor by which the unscaled transform followed by its
+    /// [`inverse`](Self::inverse) multiplies a length-`n` signal.
+    fn gain(self, n: usize) -> f64 {
+        match self {
+            DstType::I => (n + 1) as f64 / 2.0,
+            _ => n as f64 / 2.0,
+        }
+    }
+}
+
+enum DstKernel {
+    /// DST-I: real FFT of the length `2(n + 1)` odd extension.
+    Odd(RealFftPlan<f32>),
+    /// DST-II/III/IV: the matching DCT applied to sign-flipped or reversed
+    /// input.
+    Dct(DctPlan),
+}
+
+/// Immutable, precomputed DST of one type and length.
+///
+/// Under the default [`Normalization::Backward`] it produces the same values
+/// as [`dst1`]–[`dst4`] in `O(n log n)`, executes through
+/// [`execute_with_scratch`](Self::execute_with_scratch) without touching
+/// the allocator, and [`inverse_with_scratch`](Self::inverse_with_scratch)
+/// undoes it exactly. The plan is `Send + Sync`.
+///
+/// With [`Normalization::Ortho`] the transform matrix is orthogonal:
+/// DST-II scales output `n-1` by `1/√2` and DST-III scales input `n-1` by
+/// `√2`.
+///
+/// DST-II, DST-III and DST-IV reuse the DCT of the same type:
+/// `DST-II(x)[k] = DCT-II((-1)^i x_i)[n-1-k]`, and DST-III and DST-IV are
+/// `(-1)^k` times the DCT of the same type of the reversed input.
+pub struct DstPlan {
+    kind: DstType,
+    n: usize,
+    kernel: DstKernel,
+    norm: Normalization,
+    #[cfg(feature = "parallel")]
+    parallelism: Parallelism,
+}
+
+impl DstPlan {
+    /// Precompute a DST of type `kind` and length `n`.
+    pub fn new(kind: DstType, n: usize) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        let kernel = match kind {
+            DstType::I => DstKernel::Odd(RealFftPlan::new(2 * (n + 1))?),
+            DstType::II => DstKernel::Dct(DctPlan::new(DctType::II, n)?),
+            DstType::III => DstKernel::Dct(DctPlan::new(DctType::III, n)?),
+            DstType::IV => DstKernel::Dct(DctPlan::new(DctType::IV, n)?),
+        };
+        Ok(Self {
+            kind,
+            n,
+            kernel,
+            norm: Normalization::Backward,
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
+        })
+    }
+
+    /// Use `norm` instead of the default [`Normalization::Backward`].
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Normalization applied by the forward and inverse transforms.
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Spread the buffers of batch APIs given this plan over `parallelism`
+    /// instead of Rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Workers used by the batch APIs this plan is passed to.
+    #[cfg(feature = "parallel")]
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// DST type of this plan.
+    pub fn kind(&self) -> DstType {
+        self.kind
+    }
+
+    /// Transform length.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Exact scratch length, in complex samples, for
+    /// [`execute_with_scratch`](Self::execute_with_scratch) and
+    /// [`inverse_with_scratch`](Self::inverse_with_scratch).
+    pub fn scratch_len(&self) -> usize {
+        let n = self.n;
+        match &self.kernel {
+            // real buffer of 2(n+1) samples and n+2 spectrum bins
+            DstKernel::Odd(rfft) => (n + 1) + (n + 2) + rfft.scratch_len(),
+            // real buffer for the rearranged input
+            DstKernel::Dct(dct) => n.div_ceil(2) + dct.scratch_len(),
+        }
+    }
+
+    /// Transform `input` into `output` (both of length [`size`](Self::size))
+    /// using `scratch` of at least [`scratch_len`](Self::scratch_len).
+    pub fn execute_with_scratch(
+        &self,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        self.run(self.kind, false, input, output, scratch)
+    }
+
+    /// Invert [`execute_with_scratch`](Self::execute_with_scratch) under the
+    /// plan's normalization, running the DST of type
+    /// [`kind().inverse()`](DstType::inverse).
+    pub fn inverse_with_scratch(
+        &self,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        self.run(self.kind.inverse(), true, input, output, scratch)
+    }
+
+    fn run(
+        &self,
+        kind: DstType,
+        inverse: bool,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        if input.len() != n || output.len() != n || scratch.len() < self.scratch_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        self.execute_unscaled(kind, input, output, scratch)?;
+        if self.norm == Normalization::Ortho {
+            match kind {
+                DstType::II => output[n - 1] *= FRAC_1_SQRT_2,
+                DstType::III => {
+                    // √2 on input n-1, whose column is (-1)^k / 2
+                    let last = 0.5 * (SQRT_2 - 1.0) * input[n - 1];
+                    for (k, out) in output.iter_mut().enumerate() {
+                        *out += if k % 2 == 0 { last } else { -last };
+                    }
+                }
+                DstType::I | DstType::IV => {}
+            }
+        }
+        let (forward, backward) = self.norm.factors::<f32>(kind.gain(n));
+        let scale = if inverse { backward } else { forward };
+        if scale != 1.0 {
+            for out in output.iter_mut() {
+                *out *= scale;
+            }
+        }
+        Ok(())
+    }
+
+    fn execute_unscaled(
+        &self,
+        kind: DstType,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        match &self.kernel {
+            DstKernel::Odd(rfft) => {
+                let m = 2 * (n + 1);
+                let (buf, rest) = scratch.split_at_mut(n + 1);
+                let (spec, rest) = rest.split_at_mut(n + 2);
+                let buf = as_real(buf);
+                buf[0] = 0.0;
+                buf[n + 1] = 0.0;
+                for (i, &x) in input.iter().enumerate() {
+                    buf[i + 1] = x;
+                    buf[m - 1 - i] = -x;
+                }
+                rfft.rfft_with_scratch(buf, spec, rest)?;
+                for (k, out) in output.iter_mut().enumerate() {
+                    *out = -0.5 * spec[k + 1].im;
+                }
+            }
+            DstKernel::Dct(dct) => {
+                let (buf, rest) = scratch.split_at_mut(n.div_ceil(2));
+                let buf = &mut as_real(buf)[..n];
+                if kind == DstType::II {
+                    for (i, (b, &x)) in buf.iter_mut().zip(input).enumerate() {
+                        *b = if i % 2 == 0 { x } else { -x };
+                    }
+                    dct.execute_unscaled(DctType::II, buf, output, rest)?;
+                    output.reverse();
+                } else {
+                    let dct_kind = if kind == DstType::III {
+                        DctType::III
+                    } else {
+                        DctType::IV
+                    };
+                    for (b, &x) in buf.iter_mut().zip(input.iter().rev()) {
+                        *b = x;
+                    }
+                    dct.execute_unscaled(dct_kind, buf, output, rest)?;
+                    for out in output.iter_mut().skip(1).step_by(2) {
+                        *out = -*out;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// DST-I (sine transform, odd symmetry)
 pub fn dst1(input: &[f32]) -> Vec<f32> {
     let n = input.len();
@@ -115,15 +368,16 @@ pub fn dst2(input: &[f32]) -> Vec<f32> {
     output
 }
 
-/// DST-III (inverse of DST-II, up to scaling)
+/// DST-III (inverse of DST-II, up to a factor of `n/2`)
 pub fn dst3(input: &[f32]) -> Vec<f32> {
     let n = input.len();
     let mut output = vec![0.0; n];
     let factor = PI / n as f32;
     for (k, out) in output.iter_mut().enumerate() {
-        let mut sum = input[0] / 2.0;
-        for (i, &x) in input.iter().enumerate().skip(1) {
-            sum += x * (factor * (k as f32 + 0.5) * i as f32).sin();
+        let half = input[n - 1] / 2.0;
+        let mut sum = if k % 2 == 0 { half } else { -half };
+        for (i, &x) in input.iter().take(n - 1).enumerate() {
+            sum += x * (factor * (k as f32 + 0.5) * (i + 1) as f32).sin();
         }
         *out = sum;
     }
@@ -173,6 +427,55 @@ pub fn batch_iv(batches: &mut [Vec<f32>]) {
         batch.copy_from_slice(&out);
     }
 }
+
+/// Parallel batch DST: transform every buffer in place with `plan` (of any
+/// [`DstType`]), spreading contiguous runs of buffers over
+/// [`plan.parallelism()`](DstPlan::parallelism).
+///
+/// Each worker allocates its scratch once and every buffer must have the
+/// plan's length. The output is bit-identical to running
+/// [`DstPlan::execute_with_scratch`] on each buffer in turn, whatever the pool.
+#[cfg(feature = "parallel")]
+pub fn batch_parallel(plan: &DstPlan, batches: &mut [Vec<f32>]) -> Result<(), FftError> {
+    batch_with_plan(plan, batches, DstPlan::execute_with_scratch)
+}
+
+/// Parallel batch inverse with [`DstPlan::inverse_with_scratch`]; see
+/// [`batch_parallel`].
+#[cfg(feature = "parallel")]
+pub fn batch_inverse_parallel(plan: &DstPlan, batches: &mut [Vec<f32>]) -> Result<(), FftError> {
+    batch_with_plan(plan, batches, DstPlan::inverse_with_scratch)
+}
+
+#[cfg(feature = "parallel")]
+type PlanFn = fn(&DstPlan, &[f32], &mut [f32], &mut [Complex32]) -> Result<(), FftError>;
+
+#[cfg(feature = "parallel")]
+fn batch_with_plan(
+    plan: &DstPlan,
+    batches: &mut [Vec<f32>],
+    transform: PlanFn,
+) -> Result<(), FftError> {
+    let par = plan.parallelism();
+    par.try_for_each_chunk_mut(
+        batches,
+        par.batch_chunk(batches.len()),
+        || {
+            (
+                vec![0.0; plan.size()],
+                vec![Complex32::zero(); plan.scratch_len()],
+            )
+        },
+        |(out, scratch), _, run| {
+            for batch in run {
+                transform(plan, batch, out, scratch)?;
+                batch.copy_from_slice(out);
+            }
+            Ok(())
+        },
+    )
+}
+
 /// Multi-channel DST-I
 pub fn multi_channel_i(channels: &mut [Vec<f32>]) {
     batch_i(channels)
@@ -190,91 +493,6 @@ pub fn multi_channel_iv(channels: &mut [Vec<f32>]) {
     batch_iv(channels)
 }
 
-/// MCU/stack-only, const-generic, in-place DST-II for power-of-two sizes (no heap, no alloc)
-///
-/// `N` must be a positive even power of two. Returns an error otherwise.
-pub fn dst2_inplace_stack<const N: usize>(
-    input: &[f32; N],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    let factor = core::f32::consts::PI / N as f32;
-    for (k, out) in output.iter_mut().enumerate().take(N) {
-        let mut sum = 0.0;
-        for (i, &x) in input.iter().enumerate().take(N) {
-            sum += x * (factor * (i as f32 + 0.5) * (k as f32 + 1.0)).sin();
-        }
-        *out = sum;
-    }
-    Ok(())
-}
-
-/// MCU/stack-only, FFT-based, in-place DST-II using only stack memory.
-///
-/// `N` must be a positive power of two. Returns an error otherwise.
-pub fn dst2_inplace_stack_fft<const N: usize>(
-    input: &[f32; N],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    let factor = PI / N as f32;
-    for (k, out) in output.iter_mut().enumerate() {
-        let mut sum = 0.0;
-        for (i, &x) in input.iter().enumerate() {
-            sum += x * (factor * (i as f32 + 0.5) * (k as f32 + 1.0)).sin();
-        }
-        *out = sum;
-    }
-    Ok(())
-}
-
-/// MCU/stack-only, FFT-based, in-place DST-III using only stack memory.
-///
-/// `N` must be a positive even power of two. Returns an error otherwise.
-pub fn dst3_inplace_stack_fft<const N: usize>(
-    input: &[f32; N],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    // Direct computation using stack memory to ensure parity with heap-based version.
-    let factor = PI / N as f32;
-    for (k, out) in output.iter_mut().enumerate() {
-        let mut sum = input[0] / 2.0;
-        for (n, &x) in input.iter().enumerate().skip(1) {
-            sum += x * (factor * (k as f32 + 0.5) * n as f32).sin();
-        }
-        *out = sum;
-    }
-    Ok(())
-}
-
-/// MCU/stack-only, const-generic, in-place DST-IV for power-of-two sizes (no heap, no alloc)
-///
-/// `N` must be a positive even power of two. Returns an error otherwise.
-pub fn dst4_inplace_stack<const N: usize>(
-    input: &[f32; N],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    let factor = core::f32::consts::PI / N as f32;
-    for (k, out) in output.iter_mut().enumerate().take(N) {
-        let mut sum = 0.0;
-        for (i, &x) in input.iter().enumerate().take(N) {
-            sum += x * (factor * (i as f32 + 0.5) * (k as f32 + 0.5)).sin();
-        }
-        *out = sum;
-    }
-    Ok(())
-}
-
 #[cfg(all(feature = "internal-tests", test))]
 mod tests {
     use super::*;
@@ -0,0 +1,10 @@
+/// Errors returned by the transforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FftError {
+    EmptyInput,
+    NonPowerOfTwoNoStd,
+    MismatchedLengths,
+    InvalidStride,
+    InvalidHopSize,
+    InvalidValue,
+}
@@ -6,8 +6,6 @@
 //! (`x86_64`, `sse`, `aarch64`, `wasm`) accelerate computation, and both in-place and
 //! out-of-place APIs are provided for single or batched transforms.
 
-use core::f32::consts::PI;
-
 use alloc::boxed::Box;
 use alloc::sync::Arc;
 #[cfg(all(feature = "parallel", feature = "std"))]
@@ -33,11 +31,22 @@ mod precomputed_twiddles {
 #[cfg(feature = "precomputed-twiddles")]
 use precomputed_twiddles::{lookup_f32, lookup_f64};
 
-use crate::fft_kernels::{fft2, fft4};
+use crate::fft_kernels::{fft2, fft4, stockham_radix2};
+use crate::four_step::{FourStepPlan, FOUR_STEP_MIN_LEN};
+#[cfg(feature = "std")]
+use crate::mixed_radix::is_rader_smooth;
+use crate::mixed_radix::MixedRadixPlan;
 #[cfg(feature = "parallel")]
-use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::parallel::Parallelism;
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "wasm32"
+))]
+use crate::simd_f64;
+use crate::twiddles::TwiddleTable;
 #[cfg(feature = "parallel")]
-use rayon::prelude::*;
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(all(feature = "parallel", feature = "std"))]
 use num_cpus;
@@ -69,8 +78,9 @@ static ENV_PER_CORE_WORK: OnceLock<usize> = OnceLock::new();
 static ENV_BLOCK_SIZE: OnceLock<usize> = OnceLock::new();
 #[cfg(all(feature = "parallel", feature = "std"))]
 static ENV_THREADS: OnceLock<usize> = OnceLock::new();
+/// Measured per-core throughput; `0` until calibrated or imported.
 #[cfg(all(feature = "parallel", feature = "std"))]
-static CALIBRATED_PER_CORE_WORK: OnceLock<usize> = OnceLock::new();
+static CALIBRATED_PER_CORE_WORK: AtomicUsize = AtomicUsize::new(0);
 #[cfg(all(feature = "parallel", feature = "std"))]
 static PARALLEL_FFT_THRESHOLD: OnceLock<usize> = OnceLock::new();
 
@@ -127,16 +137,20 @@ fn env_parallel_fft_threads() -> usize {
 #[cfg(all(feature = "parallel", feature = "std"))]
 fn calibrated_per_core_work() -> usize {
     use std::time::Instant;
-    *CALIBRATED_PER_CORE_WORK.get_or_init(|| {
-        let n = 1 << 20; // 1MB
-        let a = vec![0u8; n];
-        let mut b = vec![0u8; n];
-        let start = Instant::now();
-        b.copy_from_slice(&a);
-        let elapsed = start.elapsed().as_nanos().max(1) as usize;
-        let elems = n / core::mem::size_of::<crate::num::Complex32>();
-        ((elems * 1_000_000_000) / elapsed).max(4096)
-    })
+    let cached = CALIBRATED_PER_CORE_WORK.load(Ordering::Relaxed);
+    if cached != 0 {
+        return cached;
+    }
+    let n = 1 << 20; // 1MB
+    let a = vec![0u8; n];
+    let mut b = vec![0u8; n];
+    let start = Instant::now();
+    b.copy_from_slice(&a);
+    let elapsed = start.elapsed().as_nanos().max(1) as usize;
+    let elems = n / core::mem::size_of::<crate::num::Complex32>();
+    let work = ((elems * 1_000_000_000) / elapsed).max(4096);
+    CALIBRATED_PER_CORE_WORK.store(work, Ordering::Relaxed);
+    work
 }
 
 #[cfg(all(feature = "parallel", feature = "std"))]
@@ -208,9 +222,66 @@ pub fn set_parallel_fft_block_size(size: usize) {
     PARALLEL_FFT_BLOCK_SIZE_OVERRIDE.store(size, Ordering::Relaxed);
 }
 
+/// Snapshot of the parallel FFT tuning knobs.
+///
+/// Every field uses `0` for "not set", matching the `set_parallel_fft_*`
+/// functions. `calibrated_per_core_work` is the measured per-core throughput;
+/// restoring a non-zero value skips the calibration run.
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParallelFftConfig {
+    pub threshold: usize,
+    pub l1_cache_bytes: usize,
+    pub per_core_work: usize,
+    pub block_size: usize,
+    pub threads: usize,
+    pub calibrated_per_core_work: usize,
+}
+
+#[cfg(feature = "parallel")]
+/// Read the current parallel FFT overrides and calibration.
+pub fn parallel_fft_config() -> ParallelFftConfig {
+    ParallelFftConfig {
+        threshold: PARALLEL_FFT_THRESHOLD_OVERRIDE.load(Ordering::Relaxed),
+        l1_cache_bytes: PARALLEL_FFT_CACHE_BYTES_OVERRIDE.load(Ordering::Relaxed),
+        per_core_work: PARALLEL_FFT_PER_CORE_WORK_OVERRIDE.load(Ordering::Relaxed),
+        block_size: PARALLEL_FFT_BLOCK_SIZE_OVERRIDE.load(Ordering::Relaxed),
+        threads: PARALLEL_FFT_THREAD_OVERRIDE.load(Ordering::Relaxed),
+        #[cfg(feature = "std")]
+        calibrated_per_core_work: CALIBRATED_PER_CORE_WORK.load(Ordering::Relaxed),
+        #[cfg(not(feature = "std"))]
+        calibrated_per_core_work: 0,
+    }
+}
+
+#[cfg(feature = "parallel")]
+/// Apply every knob in `config` at once. A `calibrated_per_core_work` of `0`
+/// leaves the current calibration untouched.
+pub fn set_parallel_fft_config(config: &ParallelFftConfig) {
+    set_parallel_fft_threshold(config.threshold);
+    set_parallel_fft_l1_cache(config.l1_cache_bytes);
+    set_parallel_fft_per_core_work(config.per_core_work);
+    set_parallel_fft_block_size(config.block_size);
+    set_parallel_fft_threads(config.threads);
+    #[cfg(feature = "std")]
+    if config.calibrated_per_core_work != 0 {
+        CALIBRATED_PER_CORE_WORK.store(config.calibrated_per_core_work, Ordering::Relaxed);
+    }
+}
+
+/// `local` if set, else the global override in `global`.
+#[cfg(feature = "parallel")]
+fn knob(local: usize, global: &AtomicUsize) -> usize {
+    if local != 0 {
+        local
+    } else {
+        global.load(Ordering::Relaxed)
+    }
+}
+
 #[cfg(feature = "parallel")]
-fn parallel_fft_threads() -> usize {
-    let override_thr = PARALLEL_FFT_THREAD_OVERRIDE.load(Ordering::Relaxed);
+fn parallel_fft_threads(config: &ParallelFftConfig) -> usize {
+    let override_thr = knob(config.threads, &PARALLEL_FFT_THREAD_OVERRIDE);
     if override_thr != 0 {
         return override_thr;
     }
@@ -225,8 +296,8 @@ fn parallel_fft_threads() -> usize {
 }
 
 #[cfg(feature = "parallel")]
-fn parallel_fft_block_size() -> usize {
-    let override_size = PARALLEL_FFT_BLOCK_SIZE_OVERRIDE.load(Ordering::Relaxed);
+fn parallel_fft_block_size(config: &ParallelFftConfig) -> usize {
+    let override_size = knob(config.block_size, &PARALLEL_FFT_BLOCK_SIZE_OVERRIDE);
     if override_size != 0 {
         return override_size;
     }
@@ -240,9 +311,30 @@ fn parallel_fft_block_size() -> usize {
     }
 }
 
+/// Parallel decision for a [`Parallelism`](crate::parallel::Parallelism)
+/// whose tuning is `config`. An all-default `config` behaves exactly like
+/// the process-wide heuristic; any heuristic field it sets replaces the
+/// cached environment threshold with a fresh estimate.
+#[cfg(feature = "parallel")]
+pub(crate) fn should_parallelize_in(n: usize, config: &ParallelFftConfig) -> bool {
+    #[cfg(feature = "std")]
+    let base = if config.l1_cache_bytes != 0
+        || config.per_core_work != 0
+        || config.block_size != 0
+        || config.threads != 0
+    {
+        0
+    } else {
+        parallel_fft_threshold()
+    };
+    #[cfg(not(feature = "std"))]
+    let base = 0;
+    should_parallelize_with(n, base, config)
+}
+
 #[cfg(feature = "parallel")]
-fn should_parallelize_fft(n: usize, base_threshold: usize) -> bool {
-    let override_thr = PARALLEL_FFT_THRESHOLD_OVERRIDE.load(Ordering::Relaxed);
+fn should_parallelize_with(n: usize, base_threshold: usize, config: &ParallelFftConfig) -> bool {
+    let override_thr = knob(config.threshold, &PARALLEL_FFT_THRESHOLD_OVERRIDE);
     let threshold = if override_thr != 0 {
         override_thr
     } else {
@@ -254,7 +346,7 @@ fn should_parallelize_fft(n: usize, base_threshold: usize) -> bool {
     #[cfg(feature = "std")]
     {
         let cache_bytes = {
-            let override_bytes = PARALLEL_FFT_CACHE_BYTES_OVERRIDE.load(Ordering::Relaxed);
+            let override_bytes = knob(config.l1_cache_bytes, &PARALLEL_FFT_CACHE_BYTES_OVERRIDE);
             if override_bytes != 0 {
                 override_bytes
             } else {
@@ -262,7 +354,7 @@ fn should_parallelize_fft(n: usize, base_threshold: usize) -> bool {
             }
         };
         let per_core_work = {
-            let override_work = PARALLEL_FFT_PER_CORE_WORK_OVERRIDE.load(Ordering::Relaxed);
+            let override_work = knob(config.per_core_work, &PARALLEL_FFT_PER_CORE_WORK_OVERRIDE);
             if override_work != 0 {
                 override_work
             } else {
@@ -274,14 +366,14 @@ fn should_parallelize_fft(n: usize, base_threshold: usize) -> bool {
         let cache_elems = cache_bytes / bytes_per_elem;
         let per_core_min = core::cmp::max(
             cache_elems,
-            core::cmp::max(per_core_work, parallel_fft_block_size()),
+            core::cmp::max(per_core_work, parallel_fft_block_size(config)),
         );
-        n >= per_core_min * parallel_fft_threads()
+        n >= per_core_min * parallel_fft_threads(config)
     }
     #[cfg(not(feature = "std"))]
     {
         let cache_bytes = {
-            let override_bytes = PARALLEL_FFT_CACHE_BYTES_OVERRIDE.load(Ordering::Relaxed);
+            let override_bytes = knob(config.l1_cache_bytes, &PARALLEL_FFT_CACHE_BYTES_OVERRIDE);
             if override_bytes != 0 {
                 override_bytes
             } else {
@@ -289,7 +381,7 @@ fn should_parallelize_fft(n: usize, base_threshold: usize) -> bool {
             }
         };
         let per_core_work = {
-            let override_work = PARALLEL_FFT_PER_CORE_WORK_OVERRIDE.load(Ordering::Relaxed);
+            let override_work = knob(config.per_core_work, &PARALLEL_FFT_PER_CORE_WORK_OVERRIDE);
             if override_work != 0 {
                 override_work
             } else {
@@ -300,13 +392,15 @@ fn should_parallelize_fft(n: usize, base_threshold: usize) -> bool {
         let cache_elems = cache_bytes / bytes_per_elem;
         let per_core_min = core::cmp::max(
             cache_elems,
-            core::cmp::max(per_core_work, parallel_fft_block_size()),
+            core::cmp::max(per_core_work, parallel_fft_block_size(config)),
         );
-        n >= per_core_min * parallel_fft_threads()
+        n >= per_core_min * parallel_fft_threads(config)
     }
 }
 
-pub use crate::fft_kernels::{fft16, fft8};
+pub use crate::error::FftError;
+pub use crate::fft_kernels::{fft11, fft13, fft16, fft3, fft5, fft7, fft8};
+pub use crate::mcu::fft::{fft_inplace_stack, ifft_inplace_stack};
 pub use crate::num::{
     copy_from_complex, copy_to_complex, Complex, Complex32, Complex64, Float, SplitComplex,
     SplitComplex32, SplitComplex64,
@@ -329,20 +423,59 @@ pub fn split_to_complex32(split: &SplitComplex<'_, f32>, out: &mut [Complex32])
 
 type BluesteinPair<T> = (Arc<[Complex<T>]>, Arc<[Complex<T>]>);
 
+/// A cached twiddle table: built at run time and shared between plans, or
+/// borrowed from a compile-time [`TwiddleTable`] without copying.
+#[derive(Clone, Debug)]
+pub enum Twiddles<T: Float> {
+    /// Evaluated by the planner.
+    Shared(Arc<[Complex<T>]>),
+    /// Borrowed from a table in read-only data, e.g. `kofft::twiddles!`.
+    Static(&'static [Complex<T>]),
+}
+
+impl<T: Float> core::ops::Deref for Twiddles<T> {
+    type Target = [Complex<T>];
+
+    fn deref(&self) -> &[Complex<T>] {
+        match self {
+            Twiddles::Shared(table) => table,
+            Twiddles::Static(table) => table,
+        }
+    }
+}
+
+impl<T: Float> AsRef<[Complex<T>]> for Twiddles<T> {
+    fn as_ref(&self) -> &[Complex<T>] {
+        self
+    }
+}
+
 pub struct FftPlanner<T: Float> {
     /// Cache of per-stage twiddle tables. Each entry contains the twiddle
     /// factors for a particular butterfly size (`len`), stored contiguously so
     /// that callers can load them without striding through a length-`n`
     /// table. The table for size `len` has `len/2` elements representing
     /// `exp(-2πi k / len)` for `k = 0..len/2`.
-    cache: HashMap<usize, Arc<[Complex<T>]>>,
+    cache: HashMap<usize, Twiddles<T>>,
     bluestein_cache: HashMap<usize, BluesteinPair<T>>,
+    mixed_cache: HashMap<usize, Arc<MixedRadixPlan<T>>>,
+    /// Path [`ScalarFftImpl::fft`] takes for each length, resolved once from
+    /// `strategies` and the length's factorisation.
+    routes: HashMap<usize, Route>,
+    four_step_cache: HashMap<usize, Arc<FourStepPlan<T>>>,
     scratch: Vec<Complex<T>>,
     bluestein_scratch: Vec<Complex<T>>,
+    mixed_scratch: Vec<Complex<T>>,
+    mode: PlanMode,
+    /// Strategies chosen by measurement, keyed by transform length.
+    strategies: HashMap<usize, FftStrategy>,
     split_re: Vec<T>,
     split_im: Vec<T>,
     split_scratch_re: Vec<T>,
     split_scratch_im: Vec<T>,
+    /// Workers for transforms large enough to split.
+    #[cfg(feature = "parallel")]
+    parallelism: Parallelism,
 }
 
 impl<T: Float> Default for FftPlanner<T> {
@@ -356,18 +489,29 @@ impl<T: Float> FftPlanner<T> {
         Self {
             cache: HashMap::new(),
             bluestein_cache: HashMap::new(),
+            mixed_cache: HashMap::new(),
+            routes: HashMap::new(),
+            four_step_cache: HashMap::new(),
             scratch: Vec::new(),
             bluestein_scratch: Vec::new(),
+            mixed_scratch: Vec::new(),
+            mode: PlanMode::Estimate,
+            strategies: HashMap::new(),
             split_re: Vec::new(),
             split_im: Vec::new(),
             split_scratch_re: Vec::new(),
             split_scratch_im: Vec::new(),
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
         }
     }
     /// Retrieve a contiguous table of twiddle factors for a given stage size
     /// `n`. The returned slice has length `n/2` and contains
     /// `exp(-2πi * k / n)` for `k = 0..n/2-1`.
-    pub fn get_twiddles(&mut self, n: usize) -> Arc<[Complex<T>]> {
+    pub fn get_twiddles(&mut self, n: usize) -> Twiddles<T> {
+        if let Some(table) = self.cache.get(&n) {
+            return table.clone();
+        }
         #[cfg(feature = "precomputed-twiddles")]
         {
             if TypeId::of::<T>() == TypeId::of::<f32>() {
@@ -376,35 +520,51 @@ impl<T: Float> FftPlanner<T> {
                     let arc = unsafe {
                         core::mem::transmute::<Arc<[crate::num::Complex32]>, Arc<[Complex<T>]>>(tab)
                     };
-                    return arc;
+                    return Twiddles::Shared(arc);
                 }
             } else if TypeId::of::<T>() == TypeId::of::<f64>() {
                 if let Some(tab) = lookup_f64(n) {
                     let arc = unsafe {
                         core::mem::transmute::<Arc<[crate::num::Complex64]>, Arc<[Complex<T>]>>(tab)
                     };
-                    return arc;
+                    return Twiddles::Shared(arc);
                 }
             }
         }
 
-        if !self.cache.contains_key(&n) {
-            let half = n / 2;
-            let angle = -T::from_f32(2.0) * T::pi() / T::from_f32(n as f32);
-            let (sin_step, cos_step) = angle.sin_cos();
-
-            let mut table: Vec<Complex<T>> = Vec::with_capacity(half);
-            let mut w_re = T::one();
-            let mut w_im = T::zero();
-            for _ in 0..half {
-                table.push(Complex::new(w_re, w_im));
-                let tmp = w_re;
-                w_re = w_re.mul_add(cos_step, -(w_im * sin_step));
-                w_im = w_im.mul_add(cos_step, tmp * sin_step);
-            }
-            self.cache.insert(n, Arc::from(table));
+        // Evaluate each factor directly in f64: a running product
+        // accumulates O(n) rounding error in f32.
+        let half = n / 2;
+        let table: Vec<Complex<T>> = (0..half)
+            .map(|k| {
+                let angle = -2.0 * core::f64::consts::PI * k as f64 / n as f64;
+                let (s, c) = libm::sincos(angle);
+                Complex::new(T::from_f64(c), T::from_f64(s))
+            })
+            .collect();
+        let table = Twiddles::Shared(Arc::from(table));
+        self.cache.insert(n, table.clone());
+        table
+    }
+
+    /// Seed the twiddle cache for length `N` from a compile-time table such
+    /// as `kofft::twiddles!(f32, 4096)`, instead of evaluating it on first
+    /// use. The table is borrowed in place, not copied to the heap.
+    ///
+    /// Returns [`FftError::NonPowerOfTwoNoStd`] unless `N` is a power of two
+    /// and [`FftError::InvalidValue`] if the table's period is not `N`.
+    pub fn load_twiddles<const N: usize>(
+        &mut self,
+        table: &'static TwiddleTable<T, N>,
+    ) -> Result<(), FftError> {
+        if !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
         }
-        Arc::clone(self.cache.get(&n).unwrap())
+        if table.period() != N {
+            return Err(FftError::InvalidValue);
+        }
+        self.cache.insert(N, Twiddles::Static(table.half()));
+        Ok(())
     }
 
     #[cfg(feature = "std")]
@@ -414,7 +574,9 @@ impl<T: Float> FftPlanner<T> {
             let mut chirp: Vec<Complex<T>> = Vec::with_capacity(n);
             let mut b: Vec<Complex<T>> = Vec::with_capacity(m);
             for i in 0..n {
-                let angle = T::pi() * T::from_f32((i * i) as f32) / T::from_f32(n as f32);
+                // Reduce i² modulo 2n first so the angle stays exact for large n.
+                let angle =
+                    T::from_f64(core::f64::consts::PI * ((i * i) % (2 * n)) as f64 / n as f64);
                 chirp.push(Complex::expi(-angle));
                 b.push(Complex::expi(angle));
             }
@@ -432,36 +594,348 @@ impl<T: Float> FftPlanner<T> {
         (Arc::clone(&pair.0), Arc::clone(&pair.1))
     }
 
+    /// Retrieve the cached mixed-radix/Rader plan for a length `n`.
+    pub(crate) fn get_mixed_radix(&mut self, n: usize) -> Arc<MixedRadixPlan<T>> {
+        Arc::clone(
+            self.mixed_cache
+                .entry(n)
+                .or_insert_with(|| Arc::new(MixedRadixPlan::new(n))),
+        )
+    }
+
+    /// The path a transform of length `n > 16` takes, resolving the
+    /// remembered strategy and trial-factorising `n` only on the first call.
+    fn route(&mut self, n: usize) -> Route {
+        let strategies = &self.strategies;
+        *self
+            .routes
+            .entry(n)
+            .or_insert_with(|| Route::resolve(n, strategies.get(&n).copied()))
+    }
+
+    /// Retrieve the cached four-step plan for a composite length `n`, or
+    /// `None` for prime `n`.
+    pub(crate) fn get_four_step(&mut self, n: usize) -> Option<Arc<FourStepPlan<T>>> {
+        if let Some(plan) = self.four_step_cache.get(&n) {
+            return Some(Arc::clone(plan));
+        }
+        let plan = Arc::new(FourStepPlan::new(n).ok()?);
+        self.four_step_cache.insert(n, Arc::clone(&plan));
+        Some(plan)
+    }
+
+    /// Create a planner that selects strategies using `mode`.
+    pub fn with_mode(mode: PlanMode) -> Self {
+        let mut planner = Self::new();
+        planner.mode = mode;
+        planner
+    }
+
+    /// Planning mode used by [`plan_strategy`](Self::plan_strategy).
+    pub fn mode(&self) -> PlanMode {
+        self.mode
+    }
+
+    /// Run the parallel paths of the owning [`ScalarFftImpl`] on
+    /// `parallelism` instead of Rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn set_parallelism(&mut self, parallelism: Parallelism) {
+        self.parallelism = parallelism;
+    }
+
+    /// Workers used for transforms large enough to split.
+    #[cfg(feature = "parallel")]
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// Change the planning mode. Strategies measured earlier are kept.
+    pub fn set_mode(&mut self, mode: PlanMode) {
+        self.mode = mode;
+    }
+
+    /// The strategy previously measured for length `n`, if any.
+    pub fn remembered_strategy(&self, n: usize) -> Option<FftStrategy> {
+        self.strategies.get(&n).copied()
+    }
+
+    /// Record `strategy` for length `n`, as if it had been measured.
+    ///
+    /// `Auto` is not a concrete choice and clears any stored entry instead.
+    pub fn remember_strategy(&mut self, n: usize, strategy: FftStrategy) {
+        if strategy == FftStrategy::Auto {
+            self.strategies.remove(&n);
+        } else {
+            self.strategies.insert(n, strategy);
+        }
+        self.routes.remove(&n);
+    }
+
+    /// All remembered strategies, sorted by length.
+    pub fn strategies(&self) -> Vec<(usize, FftStrategy)> {
+        let mut out: Vec<_> = self.strategies.iter().map(|(&n, &s)| (n, s)).collect();
+        out.sort_unstable_by_key(|&(n, _)| n);
+        out
+    }
+
+    /// Forget all measured strategies.
+    pub fn clear_strategies(&mut self) {
+        self.strategies.clear();
+        self.routes.clear();
+    }
+
     /// Determine an FFT strategy based on the input length.
     ///
-    /// Returns `SplitRadix` for power-of-two sizes and `Auto` otherwise.
+    /// In [`PlanMode::Estimate`] this returns `SplitRadix` for power-of-two
+    /// sizes, `FourStep` from [`FOUR_STEP_MIN_LEN`] on, and `Auto` otherwise. In [`PlanMode::Measure`] and
+    /// [`PlanMode::Exhaustive`] the candidate algorithms for `n` are timed on
+    /// this machine and the fastest one is remembered, so later calls (and
+    /// transforms run through a [`ScalarFftImpl`] owning this planner) reuse
+    /// it without measuring again. Without the `std` feature there is no
+    /// clock and every mode behaves like `Estimate`.
     pub fn plan_strategy(&mut self, n: usize) -> FftStrategy {
-        if n.is_power_of_two() && n > 1 {
+        if let Some(strategy) = self.remembered_strategy(n) {
+            return strategy;
+        }
+        #[cfg(feature = "std")]
+        if self.mode != PlanMode::Estimate && n > 1 {
+            let strategy = measure_strategy::<T>(n, self.mode == PlanMode::Exhaustive);
+            self.remember_strategy(n, strategy);
+            return strategy;
+        }
+        if n.is_power_of_two() && n >= FOUR_STEP_MIN_LEN {
+            FftStrategy::FourStep
+        } else if n.is_power_of_two() && n > 1 {
             FftStrategy::SplitRadix
         } else {
             FftStrategy::Auto
         }
     }
 }
+
+/// Time each applicable strategy for length `n` and return the fastest.
+///
+/// Candidates run on a private [`ScalarFftImpl`] so that measurement never
+/// recurses into the caller's planner. Each candidate is warmed up once, then
+/// timed over a repetition count calibrated to a minimum round duration and
+/// the best round wins, which filters out scheduler noise.
+#[cfg(feature = "std")]
+fn measure_strategy<T: Float>(n: usize, exhaustive: bool) -> FftStrategy {
+    use std::time::{Duration, Instant};
+
+    let mut candidates = Vec::with_capacity(6);
+    if n.is_power_of_two() {
+        candidates.push(FftStrategy::SplitRadix);
+        candidates.push(FftStrategy::Radix2);
+        if n.trailing_zeros().is_multiple_of(2) {
+            candidates.push(FftStrategy::Radix4);
+        }
+        candidates.push(FftStrategy::MixedRadix);
+        if exhaustive {
+            candidates.push(FftStrategy::Bluestein);
+        }
+        if n >= FOUR_STEP_MIN_LEN >> 6 {
+            candidates.push(FftStrategy::FourStep);
+        }
+    } else {
+        candidates.push(FftStrategy::MixedRadix);
+        candidates.push(FftStrategy::Bluestein);
+        if exhaustive && n >= FOUR_STEP_MIN_LEN >> 6 {
+            candidates.push(FftStrategy::FourStep);
+        }
+    }
+    let (rounds, min_round) = if exhaustive {
+        (7, Duration::from_millis(5))
+    } else {
+        (3, Duration::from_millis(1))
+    };
+
+    let fft = ScalarFftImpl::<T>::default();
+    let input: Vec<Complex<T>> = (0..n)
+        .map(|i| Complex::new(T::from_f32((i % 7) as f32), T::from_f32((i % 3) as f32)))
+        .collect();
+    let mut buf = input.clone();
+    let mut run = |strategy: FftStrategy, reps: u32| -> Option<Duration> {
+        let start = Instant::now();
+        for _ in 0..reps {
+            // Restore the input so repeated transforms cannot overflow.
+            buf.copy_from_slice(&input);
+            fft.fft_with_strategy(&mut buf, strategy).ok()?;
+        }
+        Some(start.elapsed())
+    };
+
+    let mut best = (candidates[0], f64::INFINITY);
+    for &strategy in &candidates {
+        // Warm-up also populates the twiddle and plan caches.
+        if run(strategy, 1).is_none() {
+            continue;
+        }
+        let mut reps = 1u32;
+        while reps < (1 << 20) {
+            match run(strategy, reps) {
+                Some(t) if t < min_round => reps *= 2,
+                _ => break,
+            }
+        }
+        let fastest = (0..rounds)
+            .filter_map(|_| run(strategy, reps))
+            .min()
+            .unwrap_or(Duration::MAX);
+        let per_call = fastest.as_secs_f64() / f64::from(reps);
+        if per_call < best.1 {
+            best = (strategy, per_call);
+        }
+    }
+    best.0
+}
+
+/// Concrete algorithm [`ScalarFftImpl::fft`] runs for a length, cached per
+/// length by [`FftPlanner`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum FftError {
-    EmptyInput,
-    NonPowerOfTwoNoStd,
-    MismatchedLengths,
-    InvalidStride,
-    InvalidHopSize,
-    InvalidValue,
+enum Route {
+    Stockham,
+    Radix2,
+    #[cfg(feature = "std")]
+    Radix4,
+    MixedRadix,
+    #[cfg(feature = "std")]
+    Bluestein,
+    FourStep,
+}
+
+impl Route {
+    /// Honour a strategy measured for length `n`, otherwise pick from the
+    /// length alone.
+    fn resolve(n: usize, remembered: Option<FftStrategy>) -> Self {
+        match remembered {
+            Some(FftStrategy::Radix2) if n.is_power_of_two() => return Route::Radix2,
+            Some(FftStrategy::MixedRadix) => return Route::MixedRadix,
+            Some(FftStrategy::FourStep) => return Route::FourStep,
+            #[cfg(feature = "std")]
+            Some(FftStrategy::Bluestein) => return Route::Bluestein,
+            #[cfg(feature = "std")]
+            Some(FftStrategy::Radix4)
+                if n.is_power_of_two() && n.trailing_zeros().is_multiple_of(2) =>
+            {
+                return Route::Radix4
+            }
+            _ => {}
+        }
+        if n.is_power_of_two() {
+            return if n >= FOUR_STEP_MIN_LEN {
+                Route::FourStep
+            } else {
+                Route::Stockham
+            };
+        }
+        // Bluestein is only cheaper when a large prime factor would force a
+        // zero-padded Rader convolution; everything else runs mixed radix.
+        #[cfg(feature = "std")]
+        if !is_rader_smooth(n) {
+            return Route::Bluestein;
+        }
+        Route::MixedRadix
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub enum FftStrategy {
     Radix2,
     Radix4,
     SplitRadix,
+    /// Radix-2/3/4/5/7/11/13 butterflies with Rader's algorithm for larger
+    /// prime factors. Works for every length.
+    MixedRadix,
+    /// Bluestein's chirp-z algorithm. Requires the `std` feature and falls
+    /// back to `MixedRadix` without it.
+    Bluestein,
+    /// Bailey's cache-blocked four-step algorithm ([`crate::four_step`]),
+    /// chosen automatically for power-of-two lengths of at least
+    /// [`FOUR_STEP_MIN_LEN`]. Prime lengths fall back to `Auto`.
+    FourStep,
     #[default]
     Auto,
 }
 
+/// How much effort [`FftPlanner::plan_strategy`] spends choosing a strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlanMode {
+    /// Pick a strategy from the length alone, without running anything.
+    #[default]
+    Estimate,
+    /// Time the main candidate algorithms and remember the fastest.
+    Measure,
+    /// Time every applicable algorithm with longer runs and remember the
+    /// fastest.
+    Exhaustive,
+}
+
+/// Where a forward/inverse transform pair puts its scale factor, following
+/// numpy's `norm=` argument.
+///
+/// For a pair whose unscaled round trip multiplies the data by `g` (`n` for
+/// the FFT, `n/2` for most DCT/DST types):
+///
+/// | variant    | forward  | inverse  |
+/// |------------|----------|----------|
+/// | `Backward` | `1`      | `1/g`    |
+/// | `Forward`  | `1/g`    | `1`      |
+/// | `Ortho`    | `1/√g`   | `1/√g`   |
+/// | `None`     | `1`      | `1`      |
+///
+/// `Backward` is the default and matches the behaviour of the plain
+/// `fft`/`ifft` methods. With `Ortho` the DCT/DST plans additionally weight
+/// their boundary samples so the transform matrix is orthogonal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalization {
+    #[default]
+    Backward,
+    Forward,
+    Ortho,
+    None,
+}
+
+impl Normalization {
+    /// Forward and inverse scale factors for a pair with round-trip gain
+    /// `gain`.
+    pub fn factors<T: Float>(self, gain: f64) -> (T, T) {
+        match self {
+            Normalization::Backward => (T::one(), T::one() / T::from_f64(gain)),
+            Normalization::Forward => (T::one() / T::from_f64(gain), T::one()),
+            Normalization::Ortho => {
+                let s = T::from_f64(1.0 / libm::sqrt(gain));
+                (s, s)
+            }
+            Normalization::None => (T::one(), T::one()),
+        }
+    }
+}
+
+/// Multiply both halves of split-format data by `scale`, skipping the pass
+/// when it is `1`.
+#[cfg(feature = "std")]
+fn scale_split<T: Float>(re: &mut [T], im: &mut [T], scale: T) {
+    if scale == T::one() {
+        return;
+    }
+    for v in re.iter_mut().chain(im.iter_mut()) {
+        *v = *v * scale;
+    }
+}
+
+/// Multiply every sample by `scale`, skipping the pass when it is `1`.
+pub(crate) fn scale_complex<T: Float>(data: &mut [Complex<T>], scale: T) {
+    if scale == T::one() {
+        return;
+    }
+    for c in data.iter_mut() {
+        c.re = c.re * scale;
+        c.im = c.im * scale;
+    }
+}
+
 // Refactor FftImpl and ScalarFftImpl to be generic over T: Float
 pub trait FftImpl<T: Float>: Any {
     fn fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError>;
@@ -685,8 +1159,8 @@ impl<T: Float> ScalarFftImpl<T> {
         if core::any::TypeId::of::<T>() == core::any::TypeId::of::<f32>() {
             let this = unsafe { &*(self as *const _ as *const ScalarFftImpl<f32>) };
             let input32 = unsafe { &mut *(input as *mut [Complex<T>] as *mut [Complex32]) };
-            let re32 = unsafe { &mut *(split_re.as_mut_slice() as *mut [T] as *mut [f32]) };
-            let im32 = unsafe { &mut *(split_im.as_mut_slice() as *mut [T] as *mut [f32]) };
+            let re32 = unsafe { &mut *(&mut split_re[..n] as *mut [T] as *mut [f32]) };
+            let im32 = unsafe { &mut *(&mut split_im[..n] as *mut [T] as *mut [f32]) };
             for (c, (r, i)) in input32.iter().zip(re32.iter_mut().zip(im32.iter_mut())) {
                 *r = c.re;
                 *i = c.im;
@@ -707,8 +1181,8 @@ impl<T: Float> ScalarFftImpl<T> {
         if core::any::TypeId::of::<T>() == core::any::TypeId::of::<f64>() {
             let this = unsafe { &*(self as *const _ as *const ScalarFftImpl<f64>) };
             let input64 = unsafe { &mut *(input as *mut [Complex<T>] as *mut [Complex64]) };
-            let re64 = unsafe { &mut *(split_re.as_mut_slice() as *mut [T] as *mut [f64]) };
-            let im64 = unsafe { &mut *(split_im.as_mut_slice() as *mut [T] as *mut [f64]) };
+            let re64 = unsafe { &mut *(&mut split_re[..n] as *mut [T] as *mut [f64]) };
+            let im64 = unsafe { &mut *(&mut split_im[..n] as *mut [T] as *mut [f64]) };
             for (c, (r, i)) in input64.iter().zip(re64.iter_mut().zip(im64.iter_mut())) {
                 *r = c.re;
                 *i = c.im;
@@ -727,52 +1201,7 @@ impl<T: Float> ScalarFftImpl<T> {
             return Ok(());
         }
 
-        // Keep raw ptr so we can copy back if the final output ends up in scratch.
-        let input_ptr = input.as_mut_ptr();
-        let mut src: &mut [Complex<T>] = input;
-        let mut dst: &mut [Complex<T>] = &mut scratch[..n];
-
-        // n1 = number of groups, n2 = size of each group in this pass.
-        let mut n1 = 1usize;
-        let mut n2 = n;
-        while n1 < n {
-            n2 >>= 1;
-
-            for k in 0..n1 {
-                // Twiddle for this group: exp(-2πi * k / (2*n1)) = table[k * n2]
-                let w = twiddles[k * n2];
-                let base0 = 2 * k * n2;
-                let base1 = base0 + n2;
-
-                debug_assert!(base1 + n2 <= src.len());
-                debug_assert!((k + 1) * n2 <= dst.len());
-                debug_assert!((k + n1 + 1) * n2 <= dst.len());
-
-                unsafe {
-                    let base0_ptr = src.as_ptr().add(base0);
-                    let base1_ptr = src.as_ptr().add(base1);
-                    let dst0_ptr = dst.as_mut_ptr().add(k * n2);
-                    let dst1_ptr = dst.as_mut_ptr().add((k + n1) * n2);
-
-                    let mut j = 0;
-                    while j < n2 {
-                        let u = *base0_ptr.add(j);
-                        let v = (*base1_ptr.add(j)).mul(w);
-                        *dst0_ptr.add(j) = u.add(v);
-                        *dst1_ptr.add(j) = u.sub(v);
-                        j += 1;
-                    }
-                }
-            }
-
-            core::mem::swap(&mut src, &mut dst);
-            n1 <<= 1;
-        }
-
-        // If result is in scratch, copy back to input.
-        if src.as_ptr() != input_ptr {
-            unsafe { core::ptr::copy_nonoverlapping(src.as_ptr(), input_ptr, n) };
-        }
+        stockham_radix2(input, &mut scratch[..n], &twiddles, 1);
 
         // Return scratch to planner for reuse.
         {
@@ -1050,6 +1479,144 @@ impl ScalarFftImpl<f64> {
     }
 }
 
+impl<T: Float> ScalarFftImpl<T> {
+    /// Interleaved radix-2 Stockham FFT for power-of-two lengths, without the
+    /// split-format SIMD pass [`stockham_fft`](Self::stockham_fft) uses.
+    fn radix2_fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
+        let n = input.len();
+        if n <= 16 || !n.is_power_of_two() {
+            return self.fft(input);
+        }
+        let (twiddles, mut scratch) = {
+            let planner = unsafe { self.planner_mut() };
+            (
+                planner.get_twiddles(n),
+                core::mem::take(&mut planner.scratch),
+            )
+        };
+        if scratch.len() < n {
+            scratch.resize(n, Complex::zero());
+        }
+        stockham_radix2(input, &mut scratch[..n], &twiddles, 1);
+        {
+            let planner = unsafe { self.planner_mut() };
+            planner.scratch = scratch;
+        }
+        Ok(())
+    }
+
+    /// Mixed-radix FFT with hand-written radix-2/3/4/5/7/11/13 butterflies and
+    /// Rader's algorithm for larger prime factors.
+    fn mixed_radix_fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
+        let n = input.len();
+        let (plan, mut scratch) = {
+            let planner = unsafe { self.planner_mut() };
+            let plan = planner.get_mixed_radix(n);
+            (plan, core::mem::take(&mut planner.mixed_scratch))
+        };
+        let need = plan.scratch_len();
+        if scratch.len() < need {
+            scratch.resize(need, Complex::zero());
+        }
+        plan.execute(input, &mut scratch[..need]);
+        {
+            let planner = unsafe { self.planner_mut() };
+            planner.mixed_scratch = scratch;
+        }
+        Ok(())
+    }
+
+    /// Bailey's four-step FFT; prime lengths fall back to [`fft`](FftImpl::fft).
+    fn four_step_fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
+        let n = input.len();
+        let (plan, mut scratch) = {
+            let planner = unsafe { self.planner_mut() };
+            let Some(plan) = planner.get_four_step(n) else {
+                // Forget the strategy so `fft` does not come straight back.
+                planner.remember_strategy(n, FftStrategy::Auto);
+                return self.fft(input);
+            };
+            (plan, core::mem::take(&mut planner.scratch))
+        };
+        #[cfg(feature = "parallel")]
+        {
+            let par = &unsafe { self.planner_mut() }.parallelism;
+            let result = if par.should_parallelize(n) {
+                if scratch.len() < n {
+                    scratch.resize(n, Complex::zero());
+                }
+                four_step_parallel(&plan, par, input, &mut scratch)
+            } else {
+                None
+            };
+            if let Some(result) = result {
+                unsafe { self.planner_mut() }.scratch = scratch;
+                return result;
+            }
+        }
+        if scratch.len() < plan.scratch_len() {
+            scratch.resize(plan.scratch_len(), Complex::zero());
+        }
+        let result = plan.fft(input, &mut scratch);
+        {
+            let planner = unsafe { self.planner_mut() };
+            planner.scratch = scratch;
+        }
+        result
+    }
+
+    /// Bluestein's chirp-z FFT for arbitrary lengths.
+    #[cfg(feature = "std")]
+    fn bluestein_fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
+        let n = input.len();
+        let (chirp_arc, fft_b_arc, mut a) = {
+            let planner = unsafe { self.planner_mut() };
+            let (chirp, fft_b) = planner.get_bluestein(n);
+            let scratch = core::mem::take(&mut planner.bluestein_scratch);
+            (chirp, fft_b, scratch)
+        };
+        let chirp = chirp_arc.as_ref();
+        let fft_b = fft_b_arc.as_ref();
+        let m = fft_b.len();
+        if a.len() < m {
+            a.resize(m, Complex::zero());
+        }
+        // The shared scratch may be longer than `m` after a larger
+        // transform, so only operate on the first `m` samples.
+        let buf = &mut a[..m];
+        for (i, &val) in input.iter().take(n).enumerate() {
+            buf[i] = val.mul(chirp[i]);
+        }
+        for v in &mut buf[n..] {
+            *v = Complex::zero();
+        }
+        self.fft(buf)?;
+        for (ai, &bi) in buf.iter_mut().zip(fft_b.iter()) {
+            *ai = ai.mul(bi);
+        }
+        for c in buf.iter_mut() {
+            c.im = -c.im;
+        }
+        self.fft(buf)?;
+        for c in buf.iter_mut() {
+            c.im = -c.im;
+        }
+        let scale = T::one() / T::from_f32(m as f32);
+        for c in buf.iter_mut() {
+            c.re = c.re * scale;
+            c.im = c.im * scale;
+        }
+        for (i, out) in input.iter_mut().take(n).enumerate() {
+            *out = buf[i].mul(chirp[i]);
+        }
+        {
+            let planner = unsafe { self.planner_mut() };
+            planner.bluestein_scratch = a;
+        }
+        Ok(())
+    }
+}
+
 impl<T: Float> FftImpl<T> for ScalarFftImpl<T> {
     fn fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
         let n = input.len();
@@ -1069,66 +1636,24 @@ impl<T: Float> FftImpl<T> for ScalarFftImpl<T> {
             }
             return Ok(());
         }
-        if (n & (n - 1)) == 0 {
-            // Power of two: use Stockham FFT
-            #[cfg(all(feature = "parallel", feature = "std"))]
-            {
-                return self.stockham_fft_with_threshold(input, parallel_fft_threshold());
-            }
-            #[cfg(not(all(feature = "parallel", feature = "std")))]
-            {
-                return self.stockham_fft_with_threshold(input, 0);
-            }
-        }
-        // Bluestein's algorithm for non-power-of-two
-        #[cfg(not(feature = "std"))]
-        {
-            return Err(FftError::NonPowerOfTwoNoStd);
-        }
-        #[cfg(feature = "std")]
-        {
-            let (chirp_arc, fft_b_arc, mut a) = {
-                let planner = unsafe { self.planner_mut() };
-                let (chirp, fft_b) = planner.get_bluestein(n);
-                let scratch = core::mem::take(&mut planner.bluestein_scratch);
-                (chirp, fft_b, scratch)
-            };
-            let chirp = chirp_arc.as_ref();
-            let fft_b = fft_b_arc.as_ref();
-            let m = fft_b.len();
-            if a.len() < m {
-                a.resize(m, Complex::zero());
-            }
-            for (i, &val) in input.iter().take(n).enumerate() {
-                a[i] = val.mul(chirp[i]);
-            }
-            for v in &mut a[n..m] {
-                *v = Complex::zero();
-            }
-            self.fft(&mut a)?;
-            for (ai, &bi) in a.iter_mut().zip(fft_b.iter()) {
-                *ai = ai.mul(bi);
-            }
-            for c in a.iter_mut() {
-                c.im = -c.im;
-            }
-            self.fft(&mut a)?;
-            for c in a.iter_mut() {
-                c.im = -c.im;
-            }
-            let scale = T::one() / T::from_f32(m as f32);
-            for c in a.iter_mut() {
-                c.re = c.re * scale;
-                c.im = c.im * scale;
-            }
-            for (i, out) in input.iter_mut().take(n).enumerate() {
-                *out = a[i].mul(chirp[i]);
-            }
-            {
-                let planner = unsafe { self.planner_mut() };
-                planner.bluestein_scratch = a;
+        match unsafe { self.planner_mut() }.route(n) {
+            Route::Radix2 => self.radix2_fft(input),
+            Route::MixedRadix => self.mixed_radix_fft(input),
+            Route::FourStep => self.four_step_fft(input),
+            #[cfg(feature = "std")]
+            Route::Bluestein => self.bluestein_fft(input),
+            #[cfg(feature = "std")]
+            Route::Radix4 => self.fft_radix4(input),
+            Route::Stockham => {
+                #[cfg(all(feature = "parallel", feature = "std"))]
+                {
+                    self.stockham_fft_with_threshold(input, parallel_fft_threshold())
+                }
+                #[cfg(not(all(feature = "parallel", feature = "std")))]
+                {
+                    self.stockham_fft_with_threshold(input, 0)
+                }
             }
-            Ok(())
         }
     }
     fn ifft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
@@ -1141,22 +1666,37 @@ impl<T: Float> FftImpl<T> for ScalarFftImpl<T> {
         }
         #[cfg(feature = "parallel")]
         {
-            #[cfg(all(feature = "parallel", feature = "std"))]
-            let threshold = parallel_fft_threshold();
-            #[cfg(not(all(feature = "parallel", feature = "std")))]
-            let threshold = 0;
-            if should_parallelize_fft(n, threshold)
+            let par = unsafe { self.planner_mut() }.parallelism.clone();
+            if par.should_parallelize(n)
                 && core::any::TypeId::of::<T>() == core::any::TypeId::of::<f32>()
             {
                 let input32 = unsafe { &mut *(input as *mut [Complex<T>] as *mut [Complex32]) };
-                input32.par_iter_mut().for_each(|c| c.im = -c.im);
+                let chunk = n.div_ceil(par.threads());
+                par.try_for_each_chunk_mut(
+                    input32,
+                    chunk,
+                    || (),
+                    |_, _, part| {
+                        part.iter_mut().for_each(|c| c.im = -c.im);
+                        Ok(())
+                    },
+                )?;
                 self.fft(input)?;
+                let input32 = unsafe { &mut *(input as *mut [Complex<T>] as *mut [Complex32]) };
                 let scale = 1.0 / n as f32;
-                input32.par_iter_mut().for_each(|c| {
-                    c.im = -c.im;
-                    c.re *= scale;
-                    c.im *= scale;
-                });
+                par.try_for_each_chunk_mut(
+                    input32,
+                    chunk,
+                    || (),
+                    |_, _, part| {
+                        for c in part {
+                            c.im = -c.im;
+                            c.re *= scale;
+                            c.im *= scale;
+                        }
+                        Ok(())
+                    },
+                )?;
                 return Ok(());
             }
         }
@@ -1352,12 +1892,18 @@ impl<T: Float> FftImpl<T> for ScalarFftImpl<T> {
             strategy
         };
         match chosen {
-            FftStrategy::Radix2 => self.fft(input),
+            FftStrategy::Radix2 => self.radix2_fft(input),
             #[cfg(feature = "std")]
             FftStrategy::Radix4 => self.fft_radix4(input),
             #[cfg(not(feature = "std"))]
             FftStrategy::Radix4 => self.fft(input),
             FftStrategy::SplitRadix => self.stockham_fft(input),
+            FftStrategy::MixedRadix => self.mixed_radix_fft(input),
+            FftStrategy::FourStep => self.four_step_fft(input),
+            #[cfg(feature = "std")]
+            FftStrategy::Bluestein => self.bluestein_fft(input),
+            #[cfg(not(feature = "std"))]
+            FftStrategy::Bluestein => self.mixed_radix_fft(input),
             FftStrategy::Auto => self.fft(input),
         }
     }
@@ -1458,15 +2004,14 @@ impl<T: Float> ScalarFftImpl<T> {
             // Fallback to generic FFT if not power of four
             return self.fft(input);
         }
-        // Bit-reversal for radix-4
-        let mut j = 0usize;
+        // Base-4 digit reversal
+        let digits = n.trailing_zeros() / 2;
         for i in 1..n {
-            let mut bit = n >> 2;
-            while j & bit != 0 {
-                j ^= bit;
-                bit >>= 2;
+            let (mut x, mut j) = (i, 0usize);
+            for _ in 0..digits {
+                j = (j << 2) | (x & 3);
+                x >>= 2;
             }
-            j ^= bit;
             if i < j {
                 input.swap(i, j);
             }
@@ -1560,7 +2105,7 @@ impl TwiddleFactorBuffer {
     pub fn new(n: usize) -> Self {
         let mut twiddles = alloc::vec::Vec::with_capacity(n / 2);
         for k in 0..(n / 2) {
-            let ang = -2.0 * PI * (k as f32) / (n as f32);
+            let ang = -2.0 * core::f32::consts::PI * (k as f32) / (n as f32);
             twiddles.push(Complex32::expi(ang));
         }
         Self { n, twiddles }
@@ -1700,8 +2245,8 @@ impl ScalarFftImpl<f32> {
                 self.fft_radix2(input)
             }
         } else {
-            // Fallback to Bluestein's for unsupported factors
-            self.fft(input)
+            // Radix-3/5/7/11/13 butterflies and Rader's algorithm
+            self.mixed_radix_fft(input)
         }
     }
     fn fft_radix2(&self, input: &mut [Complex32]) -> Result<(), FftError> {
@@ -1712,8 +2257,12 @@ impl ScalarFftImpl<f32> {
 // SIMD FFT implementations (feature-gated)
 
 #[cfg(target_arch = "x86_64")]
+/// Double-precision transforms keep their twiddles and work buffers in the
+/// instance, so like [`ScalarFftImpl`] it is `Send` but not `Sync`.
 #[derive(Default)]
-pub struct SimdFftX86_64Impl;
+pub struct SimdFftX86_64Impl {
+    f64_state: simd_f64::SimdState<f64>,
+}
 
 #[cfg(target_arch = "x86_64")]
 impl SimdFftX86_64Impl {
@@ -1792,9 +2341,160 @@ impl FftImpl<f32> for SimdFftX86_64Impl {
     }
 }
 
+#[cfg(target_arch = "x86_64")]
+/// Double precision uses AVX2/FMA when available and SSE2 otherwise.
+impl FftImpl<f64> for SimdFftX86_64Impl {
+    fn fft(&self, input: &mut [Complex64]) -> Result<(), FftError> {
+        simd_f64::fft(&self.f64_state, input, simd_f64::x86_64_kernel())
+    }
+    fn ifft(&self, input: &mut [Complex64]) -> Result<(), FftError> {
+        simd_f64::ifft(&self.f64_state, input, simd_f64::x86_64_kernel())
+    }
+    fn fft_strided(
+        &self,
+        input: &mut [Complex64],
+        stride: usize,
+        scratch: &mut [Complex64],
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().fft_strided(input, stride, scratch)
+    }
+    fn ifft_strided(
+        &self,
+        input: &mut [Complex64],
+        stride: usize,
+        scratch: &mut [Complex64],
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().ifft_strided(input, stride, scratch)
+    }
+    fn fft_out_of_place_strided(
+        &self,
+        input: &[Complex64],
+        in_stride: usize,
+        output: &mut [Complex64],
+        out_stride: usize,
+    ) -> Result<(), FftError> {
+        self.f64_state
+            .scalar()
+            .fft_out_of_place_strided(input, in_stride, output, out_stride)
+    }
+    fn ifft_out_of_place_strided(
+        &self,
+        input: &[Complex64],
+        in_stride: usize,
+        output: &mut [Complex64],
+        out_stride: usize,
+    ) -> Result<(), FftError> {
+        self.f64_state
+            .scalar()
+            .ifft_out_of_place_strided(input, in_stride, output, out_stride)
+    }
+    fn fft_with_strategy(
+        &self,
+        input: &mut [Complex64],
+        strategy: FftStrategy,
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().fft_with_strategy(input, strategy)
+    }
+}
+
+/// AVX-512F backend with 512-bit radix-2 and radix-4 butterflies for
+/// power-of-two lengths.
+///
+/// `Radix2` runs radix-2 passes only; `Radix4` and `Auto` run the radix-4
+/// pipeline. There is no AVX-512 split-radix kernel: `SplitRadix` runs the
+/// scalar split-radix FFT, as do the other strategies. Other lengths, and
+/// CPUs without `avx512f`, also use the instance's [`ScalarFftImpl`].
+///
+/// Twiddles and work buffers are kept in the instance, so like
+/// [`ScalarFftImpl`] it is `Send` but not `Sync`.
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[derive(Default)]
+pub struct SimdFftAvx512Impl {
+    f32_state: simd_f64::SimdState<f32>,
+    f64_state: simd_f64::SimdState<f64>,
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+macro_rules! avx512_backend {
+    ($t:ty, $state:ident) => {
+        impl FftImpl<$t> for SimdFftAvx512Impl {
+            fn fft(&self, input: &mut [Complex<$t>]) -> Result<(), FftError> {
+                crate::avx512::fft(&self.$state, input, true)
+            }
+            fn ifft(&self, input: &mut [Complex<$t>]) -> Result<(), FftError> {
+                crate::avx512::ifft(&self.$state, input, true)
+            }
+            fn fft_strided(
+                &self,
+                input: &mut [Complex<$t>],
+                stride: usize,
+                scratch: &mut [Complex<$t>],
+            ) -> Result<(), FftError> {
+                self.$state.scalar().fft_strided(input, stride, scratch)
+            }
+            fn ifft_strided(
+                &self,
+                input: &mut [Complex<$t>],
+                stride: usize,
+                scratch: &mut [Complex<$t>],
+            ) -> Result<(), FftError> {
+                self.$state.scalar().ifft_strided(input, stride, scratch)
+            }
+            fn fft_out_of_place_strided(
+                &self,
+                input: &[Complex<$t>],
+                in_stride: usize,
+                output: &mut [Complex<$t>],
+                out_stride: usize,
+            ) -> Result<(), FftError> {
+                self.$state
+                    .scalar()
+                    .fft_out_of_place_strided(input, in_stride, output, out_stride)
+            }
+            fn ifft_out_of_place_strided(
+                &self,
+                input: &[Complex<$t>],
+                in_stride: usize,
+                output: &mut [Complex<$t>],
+                out_stride: usize,
+            ) -> Result<(), FftError> {
+                self.$state
+                    .scalar()
+                    .ifft_out_of_place_strided(input, in_stride, output, out_stride)
+            }
+            fn fft_with_strategy(
+                &self,
+                input: &mut [Complex<$t>],
+                strategy: FftStrategy,
+            ) -> Result<(), FftError> {
+                match strategy {
+                    FftStrategy::Radix2 => crate::avx512::fft(&self.$state, input, false),
+                    FftStrategy::Radix4 | FftStrategy::Auto => {
+                        crate::avx512::fft(&self.$state, input, true)
+                    }
+                    // no AVX-512 split-radix kernel; see the type docs
+                    FftStrategy::SplitRadix => {
+                        self.$state.scalar().fft_with_strategy(input, strategy)
+                    }
+                    _ => self.$state.scalar().fft_with_strategy(input, strategy),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+avx512_backend!(f32, f32_state);
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+avx512_backend!(f64, f64_state);
+
 #[cfg(target_arch = "aarch64")]
+/// Double-precision transforms keep their twiddles and work buffers in the
+/// instance, so like [`ScalarFftImpl`] it is `Send` but not `Sync`.
 #[derive(Default)]
-pub struct SimdFftAarch64Impl;
+pub struct SimdFftAarch64Impl {
+    f64_state: simd_f64::SimdState<f64>,
+}
 
 #[cfg(target_arch = "aarch64")]
 impl SimdFftAarch64Impl {
@@ -1871,9 +2571,69 @@ impl FftImpl<f32> for SimdFftAarch64Impl {
     }
 }
 
+#[cfg(target_arch = "aarch64")]
+/// Double precision uses NEON `float64x2_t` butterflies.
+impl FftImpl<f64> for SimdFftAarch64Impl {
+    fn fft(&self, input: &mut [Complex64]) -> Result<(), FftError> {
+        simd_f64::fft(&self.f64_state, input, simd_f64::row_neon)
+    }
+    fn ifft(&self, input: &mut [Complex64]) -> Result<(), FftError> {
+        simd_f64::ifft(&self.f64_state, input, simd_f64::row_neon)
+    }
+    fn fft_strided(
+        &self,
+        input: &mut [Complex64],
+        stride: usize,
+        scratch: &mut [Complex64],
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().fft_strided(input, stride, scratch)
+    }
+    fn ifft_strided(
+        &self,
+        input: &mut [Complex64],
+        stride: usize,
+        scratch: &mut [Complex64],
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().ifft_strided(input, stride, scratch)
+    }
+    fn fft_out_of_place_strided(
+        &self,
+        input: &[Complex64],
+        in_stride: usize,
+        output: &mut [Complex64],
+        out_stride: usize,
+    ) -> Result<(), FftError> {
+        self.f64_state
+            .scalar()
+            .fft_out_of_place_strided(input, in_stride, output, out_stride)
+    }
+    fn ifft_out_of_place_strided(
+        &self,
+        input: &[Complex64],
+        in_stride: usize,
+        output: &mut [Complex64],
+        out_stride: usize,
+    ) -> Result<(), FftError> {
+        self.f64_state
+            .scalar()
+            .ifft_out_of_place_strided(input, in_stride, output, out_stride)
+    }
+    fn fft_with_strategy(
+        &self,
+        input: &mut [Complex64],
+        strategy: FftStrategy,
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().fft_with_strategy(input, strategy)
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
+/// Double-precision transforms keep their twiddles and work buffers in the
+/// instance, so like [`ScalarFftImpl`] it is `Send` but not `Sync`.
 #[derive(Default)]
-pub struct SimdFftWasmImpl;
+pub struct SimdFftWasmImpl {
+    f64_state: simd_f64::SimdState<f64>,
+}
 
 #[cfg(target_arch = "wasm32")]
 impl SimdFftWasmImpl {
@@ -1950,38 +2710,355 @@ impl FftImpl<f32> for SimdFftWasmImpl {
     }
 }
 
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+const WASM_F64_KERNEL: simd_f64::RowKernel = simd_f64::row_simd128;
+#[cfg(all(target_arch = "wasm32", not(target_feature = "simd128")))]
+const WASM_F64_KERNEL: simd_f64::RowKernel = simd_f64::row_scalar;
+
+#[cfg(target_arch = "wasm32")]
+/// Double precision uses SIMD128 `f64x2` butterflies when compiled with
+/// `simd128`, and scalar rows otherwise.
+impl FftImpl<f64> for SimdFftWasmImpl {
+    fn fft(&self, input: &mut [Complex64]) -> Result<(), FftError> {
+        simd_f64::fft(&self.f64_state, input, WASM_F64_KERNEL)
+    }
+    fn ifft(&self, input: &mut [Complex64]) -> Result<(), FftError> {
+        simd_f64::ifft(&self.f64_state, input, WASM_F64_KERNEL)
+    }
+    fn fft_strided(
+        &self,
+        input: &mut [Complex64],
+        stride: usize,
+        scratch: &mut [Complex64],
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().fft_strided(input, stride, scratch)
+    }
+    fn ifft_strided(
+        &self,
+        input: &mut [Complex64],
+        stride: usize,
+        scratch: &mut [Complex64],
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().ifft_strided(input, stride, scratch)
+    }
+    fn fft_out_of_place_strided(
+        &self,
+        input: &[Complex64],
+        in_stride: usize,
+        output: &mut [Complex64],
+        out_stride: usize,
+    ) -> Result<(), FftError> {
+        self.f64_state
+            .scalar()
+            .fft_out_of_place_strided(input, in_stride, output, out_stride)
+    }
+    fn ifft_out_of_place_strided(
+        &self,
+        input: &[Complex64],
+        in_stride: usize,
+        output: &mut [Complex64],
+        out_stride: usize,
+    ) -> Result<(), FftError> {
+        self.f64_state
+            .scalar()
+            .ifft_out_of_place_strided(input, in_stride, output, out_stride)
+    }
+    fn fft_with_strategy(
+        &self,
+        input: &mut [Complex64],
+        strategy: FftStrategy,
+    ) -> Result<(), FftError> {
+        self.f64_state.scalar().fft_with_strategy(input, strategy)
+    }
+}
+
+/// Box a SIMD backend as an `FftImpl<T>` for `T = f32` or `T = f64`.
+///
+/// Other `Float` types fall back to the scalar implementation.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "wasm32"
+))]
+pub(crate) fn boxed_backend<T: Float, B>(backend: B) -> Box<dyn FftImpl<T>>
+where
+    B: FftImpl<f32> + FftImpl<f64>,
+{
+    let boxed: Box<dyn Any> = if core::any::TypeId::of::<T>() == core::any::TypeId::of::<f32>() {
+        Box::new(Box::new(backend) as Box<dyn FftImpl<f32>>)
+    } else {
+        Box::new(Box::new(backend) as Box<dyn FftImpl<f64>>)
+    };
+    match boxed.downcast::<Box<dyn FftImpl<T>>>() {
+        Ok(fft) => *fft,
+        Err(_) => Box::new(ScalarFftImpl::<T>::default()),
+    }
+}
+
 /// Returns the best available FFT implementation for the current platform and enabled features.
-pub fn new_fft_impl() -> Box<dyn FftImpl<f32>> {
-    #[cfg(all(target_arch = "x86_64", feature = "std"))]
-    {
-        #[cfg(any(feature = "avx512", target_feature = "avx512f"))]
-        if std::arch::is_x86_feature_detected!("avx512f") {
-            return Box::new(SimdFftX86_64Impl);
+///
+/// Works for both precisions: `new_fft_impl::<f32>()` or `new_fft_impl::<f64>()`.
+/// The choice can be overridden at runtime; see [`crate::backend`].
+pub fn new_fft_impl<T: Float>() -> Box<dyn FftImpl<T>> {
+    crate::backend::new_active::<T>()
+}
+
+/// Immutable, precomputed FFT plan for one length that can be shared
+/// between threads.
+///
+/// Every table the transform needs (Stockham twiddles, mixed-radix and
+/// Rader tables, Bluestein chirps) is built by [`new`](Self::new). After
+/// that the plan is read-only: [`fft`](Self::fft) and [`ifft`](Self::ifft)
+/// take `&self` plus a caller-supplied scratch buffer of at least
+/// [`scratch_len`](Self::scratch_len) elements. The plan is `Send + Sync`,
+/// so one `Arc<SharedFftPlan<T>>` can serve any number of rayon workers,
+/// each with its own scratch.
+///
+/// ```
+/// use kofft::fft::{Complex32, SharedFftPlan};
+/// use std::sync::Arc;
+///
+/// let plan = Arc::new(SharedFftPlan::<f32>::new(48).unwrap());
+/// let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+/// let mut data = vec![Complex32::new(1.0, 0.0); 48];
+/// plan.fft(&mut data, &mut scratch).unwrap();
+/// assert!((data[0].re - 48.0).abs() < 1e-4);
+/// ```
+pub struct SharedFftPlan<T: Float> {
+    n: usize,
+    kind: SharedKind<T>,
+    norm: Normalization,
+    #[cfg(feature = "parallel")]
+    parallelism: Parallelism,
+}
+
+enum SharedKind<T: Float> {
+    /// Lengths 1, 2, 4, 8 and 16 use the unrolled kernels.
+    Small,
+    /// Radix-2 Stockham auto-sort FFT; `exp(-2πi k / n)` for `k < n/2`.
+    Stockham(Twiddles<T>),
+    MixedRadix(MixedRadixPlan<T>),
+    FourStep(Box<FourStepPlan<T>>),
+    #[cfg(feature = "std")]
+    Bluestein {
+        chirp: Arc<[Complex<T>]>,
+        b_fft: Arc<[Complex<T>]>,
+        inner: Box<SharedFftPlan<T>>,
+    },
+}
+
+impl<T: Float> SharedFftPlan<T> {
+    /// Precompute a plan for length `n`, choosing the algorithm the same way
+    /// as [`ScalarFftImpl::fft`].
+    pub fn new(n: usize) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
         }
-        if std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma")
-        {
-            return Box::new(SimdFftX86_64Impl);
+        let kind = if n.is_power_of_two() {
+            if n <= 16 {
+                SharedKind::Small
+            } else if n >= FOUR_STEP_MIN_LEN {
+                SharedKind::FourStep(Box::new(FourStepPlan::new(n)?))
+            } else {
+                SharedKind::Stockham(FftPlanner::<T>::new().get_twiddles(n))
+            }
+        } else {
+            #[cfg(feature = "std")]
+            if !is_rader_smooth(n) {
+                let (chirp, b_fft) = FftPlanner::<T>::new().get_bluestein(n);
+                let inner = Box::new(SharedFftPlan::new(b_fft.len())?);
+                return Ok(Self {
+                    n,
+                    kind: SharedKind::Bluestein {
+                        chirp,
+                        b_fft,
+                        inner,
+                    },
+                    norm: Normalization::Backward,
+                    #[cfg(feature = "parallel")]
+                    parallelism: Parallelism::default(),
+                });
+            }
+            SharedKind::MixedRadix(MixedRadixPlan::new(n))
+        };
+        Ok(Self {
+            n,
+            kind,
+            norm: Normalization::Backward,
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
+        })
+    }
+
+    /// Use `norm` instead of the default [`Normalization::Backward`].
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Normalization applied by [`fft`](Self::fft) and [`ifft`](Self::ifft).
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Split large transforms, and the frames of batch APIs given this plan,
+    /// over `parallelism` instead of Rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.set_parallelism(parallelism);
+        self
+    }
+
+    #[cfg(feature = "parallel")]
+    fn set_parallelism(&mut self, parallelism: Parallelism) {
+        #[cfg(feature = "std")]
+        if let SharedKind::Bluestein { inner, .. } = &mut self.kind {
+            inner.set_parallelism(parallelism.clone());
         }
-        if std::arch::is_x86_feature_detected!("sse2") {
-            return Box::new(SimdFftX86_64Impl);
+        self.parallelism = parallelism;
+    }
+
+    /// Workers used by this plan and the batch APIs it is passed to.
+    #[cfg(feature = "parallel")]
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// Transform length.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Minimum scratch length accepted by [`fft`](Self::fft) and
+    /// [`ifft`](Self::ifft).
+    pub fn scratch_len(&self) -> usize {
+        match &self.kind {
+            SharedKind::Small => 0,
+            SharedKind::Stockham(_) => self.n,
+            SharedKind::MixedRadix(plan) => plan.scratch_len(),
+            SharedKind::FourStep(plan) => plan.scratch_len(),
+            #[cfg(feature = "std")]
+            SharedKind::Bluestein { b_fft, inner, .. } => b_fft.len() + inner.scratch_len(),
         }
     }
-    #[cfg(all(target_arch = "aarch64", feature = "std"))]
-    {
-        if std::arch::is_aarch64_feature_detected!("neon") {
-            return Box::new(SimdFftAarch64Impl);
+
+    fn check(&self, data: &[Complex<T>], scratch: &[Complex<T>]) -> Result<(), FftError> {
+        if data.len() != self.n || scratch.len() < self.scratch_len() {
+            return Err(FftError::MismatchedLengths);
         }
+        Ok(())
     }
-    #[cfg(all(target_arch = "aarch64", not(feature = "std"), target_feature = "neon"))]
-    {
-        return Box::new(SimdFftAarch64Impl);
+
+    /// In-place forward FFT of `data` (length [`size`](Self::size)).
+    pub fn fft(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) -> Result<(), FftError> {
+        self.check(data, scratch)?;
+        self.execute(data, scratch);
+        scale_complex(data, self.norm.factors::<T>(self.n as f64).0);
+        Ok(())
     }
-    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
-    {
-        return Box::new(SimdFftWasmImpl);
+
+    /// In-place inverse FFT of `data`, scaled by `1/n` under the default
+    /// normalization.
+    pub fn ifft(
+        &self,
+        data: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.check(data, scratch)?;
+        for c in data.iter_mut() {
+            c.im = -c.im;
+        }
+        self.execute(data, scratch);
+        let scale = self.norm.factors::<T>(self.n as f64).1;
+        for c in data.iter_mut() {
+            c.re = c.re * scale;
+            c.im = -c.im * scale;
+        }
+        Ok(())
+    }
+
+    /// Unnormalized forward transform; `scratch` as checked by `check`.
+    pub(crate) fn execute(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let n = self.n;
+        match &self.kind {
+            SharedKind::Small => match n {
+                2 => fft2(data),
+                4 => fft4(data),
+                8 => fft8(data),
+                16 => fft16(data),
+                _ => {}
+            },
+            SharedKind::Stockham(twiddles) => stockham_radix2(data, scratch, twiddles, 1),
+            SharedKind::MixedRadix(plan) => plan.execute(data, scratch),
+            SharedKind::FourStep(plan) => {
+                #[cfg(feature = "parallel")]
+                if self.parallelism.should_parallelize(n) {
+                    if let Some(result) = four_step_parallel(plan, &self.parallelism, data, scratch)
+                    {
+                        // Lengths were checked by the caller.
+                        debug_assert!(result.is_ok());
+                        return;
+                    }
+                }
+                plan.execute_with_scratch(data, scratch)
+            }
+            #[cfg(feature = "std")]
+            SharedKind::Bluestein {
+                chirp,
+                b_fft,
+                inner,
+            } => {
+                let m = b_fft.len();
+                let (buf, rest) = scratch.split_at_mut(m);
+                for i in 0..n {
+                    buf[i] = data[i].mul(chirp[i]);
+                }
+                for v in &mut buf[n..] {
+                    *v = Complex::zero();
+                }
+                inner.execute(buf, rest);
+                for (a, &b) in buf.iter_mut().zip(b_fft.iter()) {
+                    // conj(a·b) so the second forward pass acts as an inverse
+                    let p = a.mul(b);
+                    *a = Complex::new(p.re, -p.im);
+                }
+                inner.execute(buf, rest);
+                let scale = T::one() / T::from_f32(m as f32);
+                for i in 0..n {
+                    let c = Complex::new(buf[i].re * scale, -buf[i].im * scale);
+                    data[i] = c.mul(chirp[i]);
+                }
+            }
+        }
     }
-    // Fall back to the portable scalar version on unsupported targets.
-    Box::new(ScalarFftImpl::<f32>::default())
+}
+
+/// Run an unnormalized four-step transform on `par`, with `scratch` of at
+/// least `n` elements. Only `f32` and `f64` are known to be `Send + Sync`;
+/// other types return `None` and run serially.
+#[cfg(feature = "parallel")]
+fn four_step_parallel<T: Float>(
+    plan: &FourStepPlan<T>,
+    par: &Parallelism,
+    input: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+) -> Option<Result<(), FftError>> {
+    use core::any::TypeId;
+    if TypeId::of::<T>() == TypeId::of::<f32>() {
+        // SAFETY: `T` is `f32`.
+        let plan = unsafe { &*(plan as *const FourStepPlan<T> as *const FourStepPlan<f32>) };
+        let input = unsafe { &mut *(input as *mut [Complex<T>] as *mut [Complex32]) };
+        let scratch = unsafe { &mut *(scratch as *mut [Complex<T>] as *mut [Complex32]) };
+        return Some(plan.execute_parallel(par, input, scratch));
+    }
+    if TypeId::of::<T>() == TypeId::of::<f64>() {
+        // SAFETY: `T` is `f64`.
+        let plan = unsafe { &*(plan as *const FourStepPlan<T> as *const FourStepPlan<f64>) };
+        let input = unsafe { &mut *(input as *mut [Complex<T>] as *mut [Complex64]) };
+        let scratch = unsafe { &mut *(scratch as *mut [Complex<T>] as *mut [Complex64]) };
+        return Some(plan.execute_parallel(par, input, scratch));
+    }
+    None
 }
 
 /// Plan-based FFT: precompute twiddles and bit-reversal for repeated transforms.
@@ -1991,6 +3068,10 @@ pub struct FftPlan<T: Float> {
     pub strategy: FftStrategy,
     pub twiddles: Option<TwiddleFactorBuffer>,
     pub fft: ScalarFftImpl<T>,
+    /// Precomputed tables for the allocation-free `*_with_scratch` methods;
+    /// `None` only for `n == 0`.
+    shared: Option<SharedFftPlan<T>>,
+    norm: Normalization,
 }
 
 #[cfg(feature = "std")]
@@ -2007,10 +3088,42 @@ impl<T: Float> FftPlan<T> {
             strategy,
             twiddles,
             fft: ScalarFftImpl::<T>::default(),
+            shared: SharedFftPlan::new(n).ok(),
+            norm: Normalization::Backward,
         }
     }
+    /// Create a plan for size `n`, choosing its strategy with `mode`.
+    ///
+    /// With [`PlanMode::Measure`] or [`PlanMode::Exhaustive`] the candidate
+    /// algorithms are timed once here; the plan then always runs the winner.
+    pub fn with_mode(n: usize, mode: PlanMode) -> Self {
+        let mut planner = FftPlanner::with_mode(mode);
+        let strategy = planner.plan_strategy(n);
+        let mut plan = Self::new(n, strategy);
+        plan.fft = ScalarFftImpl::with_planner(planner);
+        plan
+    }
+    /// Use `norm` instead of the default [`Normalization::Backward`] for
+    /// every transform run through this plan.
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self.shared = self.shared.map(|p| p.with_normalization(norm));
+        self
+    }
+    /// Normalization applied by the plan's transforms.
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+    fn factors(&self) -> (T, T) {
+        self.norm.factors(self.n as f64)
+    }
     /// In-place FFT using the plan
     pub fn fft(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
+        self.fft_unscaled(input)?;
+        scale_complex(input, self.factors().0);
+        Ok(())
+    }
+    fn fft_unscaled(&self, input: &mut [Complex<T>]) -> Result<(), FftError> {
         if input.len() != self.n {
             return Err(FftError::MismatchedLengths);
         }
@@ -2030,8 +3143,11 @@ impl<T: Float> FftPlan<T> {
                             .fft_radix4_with_twiddles(input32, tw);
                     }
                 }
-                FftStrategy::SplitRadix => {}
-                FftStrategy::Auto => {}
+                FftStrategy::SplitRadix
+                | FftStrategy::MixedRadix
+                | FftStrategy::Bluestein
+                | FftStrategy::FourStep
+                | FftStrategy::Auto => {}
             }
         }
         self.fft.fft_with_strategy(input, self.strategy)
@@ -2044,8 +3160,8 @@ impl<T: Float> FftPlan<T> {
         for c in input.iter_mut() {
             c.im = -c.im;
         }
-        self.fft(input)?;
-        let scale = T::one() / T::from_f32(self.n as f32);
+        self.fft_unscaled(input)?;
+        let scale = self.factors().1;
         for c in input.iter_mut() {
             c.im = -c.im;
             c.re = c.re * scale;
@@ -2082,14 +3198,105 @@ impl<T: Float> FftPlan<T> {
         if re.len() != self.n || im.len() != self.n {
             return Err(FftError::MismatchedLengths);
         }
-        self.fft.fft_split(re, im)
+        self.fft.fft_split(re, im)?;
+        scale_split(re, im, self.factors().0);
+        Ok(())
     }
 
     pub fn ifft_split(&self, re: &mut [T], im: &mut [T]) -> Result<(), FftError> {
         if re.len() != self.n || im.len() != self.n {
             return Err(FftError::MismatchedLengths);
         }
-        self.fft.ifft_split(re, im)
+        self.fft.ifft_split(re, im)?;
+        // `ifft_split` already divides by `n`.
+        if self.norm != Normalization::Backward {
+            scale_split(re, im, self.factors().1 * T::from_f64(self.n as f64));
+        }
+        Ok(())
+    }
+
+    /// Exact scratch length, in complex samples, needed by
+    /// [`fft_with_scratch`](Self::fft_with_scratch) and
+    /// [`ifft_with_scratch`](Self::ifft_with_scratch).
+    pub fn scratch_len(&self) -> usize {
+        self.shared.as_ref().map_or(0, SharedFftPlan::scratch_len)
+    }
+
+    /// Scratch length needed by the `*_split_with_scratch` methods: the
+    /// interleaved copy of the data plus [`scratch_len`](Self::scratch_len).
+    pub fn split_scratch_len(&self) -> usize {
+        self.n + self.scratch_len()
+    }
+
+    fn shared(&self) -> Result<&SharedFftPlan<T>, FftError> {
+        self.shared.as_ref().ok_or(FftError::EmptyInput)
+    }
+
+    /// In-place FFT that never allocates. `scratch` must hold at least
+    /// [`scratch_len`](Self::scratch_len) samples.
+    pub fn fft_with_scratch(
+        &self,
+        input: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.shared()?.fft(input, scratch)
+    }
+
+    /// In-place IFFT (scaled by `1/n`) that never allocates.
+    pub fn ifft_with_scratch(
+        &self,
+        input: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.shared()?.ifft(input, scratch)
+    }
+
+    /// Split-format FFT that never allocates. `scratch` must hold at least
+    /// [`split_scratch_len`](Self::split_scratch_len) samples.
+    pub fn fft_split_with_scratch(
+        &self,
+        re: &mut [T],
+        im: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.split_with_scratch(re, im, scratch, false)
+    }
+
+    /// Split-format IFFT (scaled by `1/n`) that never allocates.
+    pub fn ifft_split_with_scratch(
+        &self,
+        re: &mut [T],
+        im: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.split_with_scratch(re, im, scratch, true)
+    }
+
+    fn split_with_scratch(
+        &self,
+        re: &mut [T],
+        im: &mut [T],
+        scratch: &mut [Complex<T>],
+        inverse: bool,
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        if re.len() != n || im.len() != n || scratch.len() < self.split_scratch_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        let (data, rest) = scratch.split_at_mut(n);
+        for (c, (&r, &i)) in data.iter_mut().zip(re.iter().zip(im.iter())) {
+            *c = Complex::new(r, i);
+        }
+        if inverse {
+            self.shared()?.ifft(data, rest)?;
+        } else {
+            self.shared()?.fft(data, rest)?;
+        }
+        for (c, (r, i)) in data.iter().zip(re.iter_mut().zip(im.iter_mut())) {
+            *r = c.re;
+            *i = c.im;
+        }
+        Ok(())
     }
 }
 
@@ -2099,16 +3306,14 @@ impl FftPlan<f32> {
         if data.len() != self.n {
             return Err(FftError::MismatchedLengths);
         }
-        self.fft
-            .fft_split(data.re.as_mut_slice(), data.im.as_mut_slice())
+        self.fft_split(data.re.as_mut_slice(), data.im.as_mut_slice())
     }
 
     pub fn ifft_complex_vec(&self, data: &mut crate::num::ComplexVec) -> Result<(), FftError> {
         if data.len() != self.n {
             return Err(FftError::MismatchedLengths);
         }
-        self.fft
-            .ifft_split(data.re.as_mut_slice(), data.im.as_mut_slice())
+        self.ifft_split(data.re.as_mut_slice(), data.im.as_mut_slice())
     }
 }
 
@@ -2190,83 +3395,68 @@ pub fn multi_channel_inverse<T: Float, F: FftImpl<T>>(
     batch_inverse(fft, channels)
 }
 
-/// MCU/stack-only, const-generic, in-place FFT for power-of-two sizes (no heap, no alloc)
-/// Note: All stack-only, const-generic APIs in this crate require the user to provide all output buffers.
-/// This is a Rust limitation: you cannot allocate [T; N] for arbitrary N inside a function.
-/// See dct2_inplace_stack, dst2_inplace_stack, haar_forward_inplace_stack, etc.
-pub fn fft_inplace_stack<const N: usize>(buf: &mut [Complex<f32>; N]) -> Result<(), FftError> {
-    if N == 0 {
-        return Err(FftError::EmptyInput);
-    }
-    if N == 1 {
-        return Ok(());
-    }
-    if N.count_ones() != 1 {
-        // Only power-of-two supported for stack-only
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    // Bit-reversal permutation
-    let mut j = 0;
-    for i in 1..N {
-        let mut bit = N >> 1;
-        while j & bit != 0 {
-            j ^= bit;
-            bit >>= 1;
-        }
-        j ^= bit;
-        if i < j {
-            buf.swap(i, j);
-        }
-    }
-    let mut len = 2;
-    while len <= N {
-        let ang = -2.0 * PI / (len as f32);
-        let (sin_step, cos_step) = ang.sin_cos();
-        let mut i = 0;
-        while i < N {
-            let mut w_re = 1.0f32;
-            let mut w_im = 0.0f32;
-            for j in 0..(len / 2) {
-                let w = Complex32::new(w_re, w_im);
-                let u = buf[i + j];
-                let v = buf[i + j + len / 2].mul(w);
-                buf[i + j] = u.add(v);
-                buf[i + j + len / 2] = u.sub(v);
-                let tmp = w_re;
-                w_re = w_re.mul_add(cos_step, -(w_im * sin_step));
-                w_im = w_im.mul_add(cos_step, tmp * sin_step);
-            }
-            i += len;
-        }
-        len <<= 1;
-    }
-    Ok(())
+/// Parallel [`batch`]: transform every buffer with `plan`, spreading
+/// contiguous runs of buffers over [`plan.parallelism()`](SharedFftPlan::parallelism).
+///
+/// Each worker allocates one scratch buffer. Every buffer must have the
+/// plan's length. The output is bit-identical to running `plan.fft` on each
+/// buffer in turn, so it does not depend on the pool or thread count.
+#[cfg(feature = "parallel")]
+pub fn batch_parallel<T: Float + Send + Sync>(
+    plan: &SharedFftPlan<T>,
+    batches: &mut [Vec<Complex<T>>],
+) -> Result<(), FftError> {
+    batch_with_plan(plan, batches, SharedFftPlan::fft)
 }
 
-/// MCU/stack-only, const-generic, in-place IFFT for power-of-two sizes (no heap, no alloc)
-pub fn ifft_inplace_stack<const N: usize>(buf: &mut [Complex<f32>; N]) -> Result<(), FftError> {
-    if N == 0 {
-        return Err(FftError::EmptyInput);
-    }
-    if N == 1 {
-        return Ok(());
-    }
-    if N.count_ones() != 1 {
-        return Err(FftError::NonPowerOfTwoNoStd);
-    }
-    // Conjugate
-    for c in buf.iter_mut() {
-        c.im = -c.im;
-    }
-    fft_inplace_stack(buf)?;
-    // Conjugate and scale
-    let scale = 1.0 / (N as f32);
-    for c in buf.iter_mut() {
-        c.im = -c.im;
-        c.re *= scale;
-        c.im *= scale;
-    }
-    Ok(())
+/// Parallel [`batch_inverse`]; see [`batch_parallel`].
+#[cfg(feature = "parallel")]
+pub fn batch_inverse_parallel<T: Float + Send + Sync>(
+    plan: &SharedFftPlan<T>,
+    batches: &mut [Vec<Complex<T>>],
+) -> Result<(), FftError> {
+    batch_with_plan(plan, batches, SharedFftPlan::ifft)
+}
+
+/// Parallel [`multi_channel`]; see [`batch_parallel`].
+#[cfg(feature = "parallel")]
+pub fn multi_channel_parallel<T: Float + Send + Sync>(
+    plan: &SharedFftPlan<T>,
+    channels: &mut [Vec<Complex<T>>],
+) -> Result<(), FftError> {
+    batch_parallel(plan, channels)
+}
+
+/// Parallel [`multi_channel_inverse`]; see [`batch_parallel`].
+#[cfg(feature = "parallel")]
+pub fn multi_channel_inverse_parallel<T: Float + Send + Sync>(
+    plan: &SharedFftPlan<T>,
+    channels: &mut [Vec<Complex<T>>],
+) -> Result<(), FftError> {
+    batch_inverse_parallel(plan, channels)
+}
+
+/// [`SharedFftPlan::fft`] or [`SharedFftPlan::ifft`].
+#[cfg(feature = "parallel")]
+pub(crate) type PlanFn<T> =
+    fn(&SharedFftPlan<T>, &mut [Complex<T>], &mut [Complex<T>]) -> Result<(), FftError>;
+
+#[cfg(feature = "parallel")]
+fn batch_with_plan<T: Float + Send + Sync>(
+    plan: &SharedFftPlan<T>,
+    batches: &mut [Vec<Complex<T>>],
+    transform: PlanFn<T>,
+) -> Result<(), FftError> {
+    let par = plan.parallelism();
+    par.try_for_each_chunk_mut(
+        batches,
+        par.batch_chunk(batches.len()),
+        || alloc::vec![Complex::zero(); plan.scratch_len()],
+        |scratch, _, run| {
+            run.iter_mut()
+                .try_for_each(|batch| transform(plan, batch, scratch))
+        },
+    )
 }
 
 // Reference DFT/IDFT is now in dft.rs
@@ -2665,21 +3855,37 @@ mod parallel_override_tests {
 
         let base = parallel_fft_threshold();
         assert!(base > 0);
-        assert!(should_parallelize_fft(base, base));
-        assert!(!should_parallelize_fft(base - 1, base));
+        assert!(should_parallelize_with(
+            base,
+            base,
+            &ParallelFftConfig::default()
+        ));
+        assert!(!should_parallelize_with(
+            base - 1,
+            base,
+            &ParallelFftConfig::default()
+        ));
 
         set_parallel_fft_threshold(base + 1);
-        assert!(!should_parallelize_fft(base, base));
+        assert!(!should_parallelize_with(
+            base,
+            base,
+            &ParallelFftConfig::default()
+        ));
         set_parallel_fft_threshold(1);
-        assert!(should_parallelize_fft(2, base));
+        assert!(should_parallelize_with(
+            2,
+            base,
+            &ParallelFftConfig::default()
+        ));
         set_parallel_fft_threshold(0);
 
         set_parallel_fft_threads(2);
-        assert_eq!(parallel_fft_threads(), 2);
+        assert_eq!(parallel_fft_threads(&ParallelFftConfig::default()), 2);
         set_parallel_fft_threads(0);
 
         set_parallel_fft_block_size(64);
-        assert_eq!(parallel_fft_block_size(), 64);
+        assert_eq!(parallel_fft_block_size(&ParallelFftConfig::default()), 64);
         set_parallel_fft_block_size(0);
     }
 }
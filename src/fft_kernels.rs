@@ -32,7 +32,7 @@ pub fn fft4<T: Float>(input: &mut [Complex<T>]) {
 pub fn fft8<T: Float>(input: &mut [Complex<T>]) {
     debug_assert_eq!(input.len(), 8);
     let w1 = Complex::new(T::zero(), -T::one());
-    let s = T::from_f32(0.70710677); // sqrt(2)/2
+    let s = T::from_f64(core::f64::consts::FRAC_1_SQRT_2); // sqrt(2)/2
 
     // load inputs before overwriting
     let x0 = input[0];
@@ -107,7 +107,7 @@ pub fn fft16<T: Float>(input: &mut [Complex<T>]) {
     let x15 = input[15];
 
     let w1 = Complex::new(T::zero(), -T::one());
-    let s = T::from_f32(0.70710677); // sqrt(2)/2
+    let s = T::from_f64(core::f64::consts::FRAC_1_SQRT_2); // sqrt(2)/2
 
     // ---- even index FFT8 ----
     let a0 = x0.add(x8);
@@ -178,12 +178,12 @@ pub fn fft16<T: Float>(input: &mut [Complex<T>]) {
     input[15] = oa3.sub(t3);
 
     // ---- twiddle multiply odd half ----
-    let c1 = T::from_f32(0.9238795);
-    let s1 = T::from_f32(-0.38268343);
-    let c2 = T::from_f32(0.70710677);
-    let s2 = T::from_f32(-0.70710677);
-    let c3 = T::from_f32(0.38268343);
-    let s3 = T::from_f32(-0.9238795);
+    let c1 = T::from_f64(0.9238795325112867);
+    let s1 = T::from_f64(-0.3826834323650898);
+    let c2 = T::from_f64(core::f64::consts::FRAC_1_SQRT_2);
+    let s2 = T::from_f64(-core::f64::consts::FRAC_1_SQRT_2);
+    let c3 = T::from_f64(0.3826834323650898);
+    let s3 = T::from_f64(-0.9238795325112867);
     let c4 = T::zero();
     let s4 = T::from_f32(-1.0);
 
@@ -222,3 +222,263 @@ pub fn fft16<T: Float>(input: &mut [Complex<T>]) {
     input[7] = e7.add(o7);
     input[15] = e7.sub(o7);
 }
+
+/// Multiply by `-i`.
+#[inline(always)]
+fn mul_neg_i<T: Float>(z: Complex<T>) -> Complex<T> {
+    Complex::new(z.im, -z.re)
+}
+
+#[inline(always)]
+pub fn fft3<T: Float>(input: &mut [Complex<T>]) {
+    debug_assert_eq!(input.len(), 3);
+    let c = T::from_f32(-0.5); // cos(2π/3)
+    let s = T::from_f64(0.8660254037844386); // sin(2π/3)
+    let x0 = input[0];
+    let a = input[1].add(input[2]);
+    let b = input[1].sub(input[2]);
+    let m = Complex::new(x0.re + c * a.re, x0.im + c * a.im);
+    let t = mul_neg_i(Complex::new(s * b.re, s * b.im));
+    input[0] = x0.add(a);
+    input[1] = m.add(t);
+    input[2] = m.sub(t);
+}
+
+#[inline(always)]
+pub fn fft5<T: Float>(input: &mut [Complex<T>]) {
+    debug_assert_eq!(input.len(), 5);
+    let c1 = T::from_f64(0.30901699437494745); // cos(2π/5)
+    let c2 = T::from_f64(-0.8090169943749475); // cos(4π/5)
+    let s1 = T::from_f64(0.9510565162951535); // sin(2π/5)
+    let s2 = T::from_f64(0.5877852522924731); // sin(4π/5)
+    let x0 = input[0];
+    let a1 = input[1].add(input[4]);
+    let b1 = input[1].sub(input[4]);
+    let a2 = input[2].add(input[3]);
+    let b2 = input[2].sub(input[3]);
+
+    let m1 = Complex::new(
+        x0.re + c1 * a1.re + c2 * a2.re,
+        x0.im + c1 * a1.im + c2 * a2.im,
+    );
+    let m2 = Complex::new(
+        x0.re + c2 * a1.re + c1 * a2.re,
+        x0.im + c2 * a1.im + c1 * a2.im,
+    );
+    let t1 = mul_neg_i(Complex::new(
+        s1 * b1.re + s2 * b2.re,
+        s1 * b1.im + s2 * b2.im,
+    ));
+    let t2 = mul_neg_i(Complex::new(
+        s2 * b1.re - s1 * b2.re,
+        s2 * b1.im - s1 * b2.im,
+    ));
+    input[0] = x0.add(a1).add(a2);
+    input[1] = m1.add(t1);
+    input[4] = m1.sub(t1);
+    input[2] = m2.add(t2);
+    input[3] = m2.sub(t2);
+}
+
+/// Butterfly for an odd prime `P = 2H + 1` using the symmetric pair
+/// decomposition: with `a_j = x_j + x_(P-j)` and `b_j = x_j - x_(P-j)`,
+/// `X_k = x_0 + Σ a_j cos(2πjk/P) - i Σ b_j sin(2πjk/P)` and `X_(P-k)` takes
+/// the opposite sign on the sine term.  `cos[j - 1]`/`sin[j - 1]` hold
+/// `cos(2πj/P)`/`sin(2πj/P)` for `j = 1..=H`.
+#[inline(always)]
+fn fft_odd_prime<T: Float, const P: usize, const H: usize>(
+    input: &mut [Complex<T>],
+    cos: &[f64; H],
+    sin: &[f64; H],
+) {
+    debug_assert_eq!(input.len(), P);
+    let x0 = input[0];
+    let mut a = [Complex::zero(); H];
+    let mut b = [Complex::zero(); H];
+    let mut sum = x0;
+    for j in 1..=H {
+        a[j - 1] = input[j].add(input[P - j]);
+        b[j - 1] = input[j].sub(input[P - j]);
+        sum = sum.add(a[j - 1]);
+    }
+    for k in 1..=H {
+        let mut re = x0;
+        let mut im = Complex::zero();
+        for j in 1..=H {
+            let idx = (j * k) % P;
+            let (c, s) = if idx <= H {
+                (cos[idx - 1], sin[idx - 1])
+            } else {
+                (cos[P - idx - 1], -sin[P - idx - 1])
+            };
+            let (c, s) = (T::from_f64(c), T::from_f64(s));
+            re = Complex::new(re.re + c * a[j - 1].re, re.im + c * a[j - 1].im);
+            im = Complex::new(im.re + s * b[j - 1].re, im.im + s * b[j - 1].im);
+        }
+        let t = mul_neg_i(im);
+        input[k] = re.add(t);
+        input[P - k] = re.sub(t);
+    }
+    input[0] = sum;
+}
+
+const COS7: [f64; 3] = [0.6234898018587336, -0.22252093395631434, -0.900968867902419];
+const SIN7: [f64; 3] = [0.7818314824680298, 0.9749279121818236, 0.43388373911755823];
+
+#[inline(always)]
+pub fn fft7<T: Float>(input: &mut [Complex<T>]) {
+    fft_odd_prime::<T, 7, 3>(input, &COS7, &SIN7);
+}
+
+const COS11: [f64; 5] = [
+    0.8412535328311812,
+    0.41541501300188644,
+    -0.14231483827328514,
+    -0.654860733945285,
+    -0.9594929736144974,
+];
+const SIN11: [f64; 5] = [
+    0.5406408174555976,
+    0.9096319953545183,
+    0.9898214418809327,
+    0.7557495743542583,
+    0.28173255684142967,
+];
+
+#[inline(always)]
+pub fn fft11<T: Float>(input: &mut [Complex<T>]) {
+    fft_odd_prime::<T, 11, 5>(input, &COS11, &SIN11);
+}
+
+const COS13: [f64; 6] = [
+    0.8854560256532099,
+    0.5680647467311558,
+    0.120536680255323,
+    -0.3546048870425356,
+    -0.7485107481711011,
+    -0.970941817426052,
+];
+const SIN13: [f64; 6] = [
+    0.46472317204376856,
+    0.8229838658936564,
+    0.992708874098054,
+    0.9350162426854148,
+    0.6631226582407952,
+    0.23931566428755774,
+];
+
+#[inline(always)]
+pub fn fft13<T: Float>(input: &mut [Complex<T>]) {
+    fft_odd_prime::<T, 13, 6>(input, &COS13, &SIN13);
+}
+
+/// Radix-2 Stockham passes over a power-of-two `input` (`n > 1`), ping-ponging
+/// with `scratch[..n]`. `twiddles[k * stride]` must be `exp(-2πik/n)` for
+/// `k < n/2`.
+pub(crate) fn stockham_radix2<T: Float>(
+    input: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+    stride: usize,
+) {
+    let n = input.len();
+    // Keep raw ptr so we can copy back if the final output ends up in scratch.
+    let input_ptr = input.as_mut_ptr();
+    let mut src: &mut [Complex<T>] = input;
+    let mut dst: &mut [Complex<T>] = &mut scratch[..n];
+
+    // n1 = number of groups, n2 = size of each group in this pass.
+    let mut n1 = 1usize;
+    let mut n2 = n;
+    while n1 < n {
+        n2 >>= 1;
+
+        for k in 0..n1 {
+            // Twiddle for this group: exp(-2πi * k / (2*n1)) = table[k * n2]
+            let w = twiddles[k * n2 * stride];
+            let base0 = 2 * k * n2;
+            let base1 = base0 + n2;
+
+            debug_assert!(base1 + n2 <= src.len());
+            debug_assert!((k + 1) * n2 <= dst.len());
+            debug_assert!((k + n1 + 1) * n2 <= dst.len());
+
+            unsafe {
+                let base0_ptr = src.as_ptr().add(base0);
+                let base1_ptr = src.as_ptr().add(base1);
+                let dst0_ptr = dst.as_mut_ptr().add(k * n2);
+                let dst1_ptr = dst.as_mut_ptr().add((k + n1) * n2);
+
+                let mut j = 0;
+                while j < n2 {
+                    let u = *base0_ptr.add(j);
+                    let v = (*base1_ptr.add(j)).mul(w);
+                    *dst0_ptr.add(j) = u.add(v);
+                    *dst1_ptr.add(j) = u.sub(v);
+                    j += 1;
+                }
+            }
+        }
+
+        core::mem::swap(&mut src, &mut dst);
+        n1 <<= 1;
+    }
+
+    // If result is in scratch, copy back to input.
+    if src.as_ptr() != input_ptr {
+        unsafe { core::ptr::copy_nonoverlapping(src.as_ptr(), input_ptr, n) };
+    }
+}
+
+/// Turn the half-length complex FFT `z` of the even/odd-packed input into
+/// the `m + 1` bins of the real spectrum. `twiddles[k * stride]` must be
+/// `exp(-iπk/m)`.
+pub(crate) fn pack_spectrum<T: Float>(
+    z: &[Complex<T>],
+    output: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+    stride: usize,
+) {
+    let m = z.len();
+    let y0 = z[0];
+    output[0] = Complex::new(y0.re + y0.im, T::zero());
+    output[m] = Complex::new(y0.re - y0.im, T::zero());
+    let half = T::from_f32(0.5);
+    for k in 1..m {
+        let a = z[k];
+        let b = Complex::new(z[m - k].re, -z[m - k].im);
+        let sum = a.add(b);
+        let diff = a.sub(b);
+        let w = twiddles[k * stride];
+        let t = w.mul(diff);
+        let temp = sum.add(Complex::new(t.im, -t.re));
+        output[k] = Complex::new(temp.re * half, temp.im * half);
+    }
+}
+
+/// Inverse of [`pack_spectrum`]: fold the `m + 1` spectrum bins back into
+/// the half-length complex spectrum `z`.
+pub(crate) fn unpack_spectrum<T: Float>(
+    input: &[Complex<T>],
+    z: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+    stride: usize,
+) {
+    let m = z.len();
+    let half = T::from_f32(0.5);
+    z[0] = Complex::new(
+        (input[0].re + input[m].re) * half,
+        (input[0].re - input[m].re) * half,
+    );
+    for k in 1..m {
+        let a = input[k];
+        let b = Complex::new(input[m - k].re, -input[m - k].im);
+        let sum = a.add(b);
+        let diff = a.sub(b);
+        let w = twiddles[k * stride];
+        let w = Complex::new(w.re, -w.im);
+        let t = w.mul(diff);
+        let temp = sum.sub(Complex::new(t.im, -t.re));
+        z[k] = Complex::new(temp.re * half, temp.im * half);
+    }
+}
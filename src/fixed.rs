@@ -0,0 +1,524 @@
+//! Fixed-point FFTs for microcontrollers without an FPU.
+//!
+//! Samples are signed fractions in Q15 (`i16`, `1.0 = 2^15`) or Q31
+//! (`i32`, `1.0 = 2^31`). The transforms use integer arithmetic only:
+//! twiddle factors come from quarter-wave sine tables built at compile time
+//! for lengths up to [`MAX_LEN`], products are rounded and every result
+//! saturates. Q15 runs in `i32`; only Q31 widens to `i64`.
+//!
+//! A radix-2 FFT grows its values by up to `1 + √2` per stage, so each
+//! stage uses block floating point: when the largest component could
+//! overflow, the whole block is shifted right by one bit and a shared
+//! exponent is incremented. Every transform returns that exponent `e`;
+//! the true result is the output multiplied by `2^e`:
+//!
+//! - [`fft`]/[`rfft`]: the unnormalized DFT of the input;
+//! - [`ifft`]/[`irfft`]: the inverse DFT normalized by `1/N`, so `e` is
+//!   usually negative.
+//!
+//! After `let e1 = fft(&mut x)?` and `let e2 = ifft(&mut x)?` the buffer
+//! holds the original signal times `2^-(e1 + e2)`.
+//!
+//! ```
+//! use kofft::fixed::{fft_inplace_stack, ComplexQ15, Fixed};
+//!
+//! let mut buf = [ComplexQ15::new(i16::from_f32(0.5), 0); 8];
+//! let exp = fft_inplace_stack(&mut buf).unwrap();
+//! // DC bin: 8 · 0.5 = 4.0
+//! let dc = buf[0].re.to_f32() * (exp as f32).exp2();
+//! assert!((dc - 4.0).abs() < 1e-3);
+//! ```
+
+use crate::error::FftError;
+use crate::twiddles::sin_cos_turns;
+
+mod sealed {
+    use core::ops::{Add, BitAnd, Mul, Neg, Shl, Shr, Sub};
+
+    /// Integer type holding intermediate sums and products: `i32` for Q15
+    /// and `i64` for Q31.
+    pub trait Wide:
+        Copy
+        + Ord
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + Neg<Output = Self>
+        + Shl<u32, Output = Self>
+        + Shr<u32, Output = Self>
+        + BitAnd<Output = Self>
+    {
+        const ZERO: Self;
+        const ONE: Self;
+        fn abs(self) -> Self;
+    }
+
+    impl Wide for i32 {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+        #[inline(always)]
+        fn abs(self) -> Self {
+            i32::abs(self)
+        }
+    }
+
+    impl Wide for i64 {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+        #[inline(always)]
+        fn abs(self) -> Self {
+            i64::abs(self)
+        }
+    }
+
+    pub trait Sealed: Sized + 'static {
+        type Wide: Wide;
+        /// Largest component magnitude that cannot overflow through one
+        /// radix-2 butterfly: `full_scale · 53/128 < full_scale / (1 + √2)`.
+        const GROWTH_LIMIT: Self::Wide;
+        fn widen(self) -> Self::Wide;
+        /// Narrow, saturating at the type's range.
+        fn narrow(v: Self::Wide) -> Self;
+        /// `sin(2πk/MAX_LEN)` for `k = 0..=MAX_LEN/4` in this format.
+        fn quarter_sine() -> &'static [Self];
+    }
+
+    impl Sealed for i16 {
+        type Wide = i32;
+        const GROWTH_LIMIT: i32 = ((1 << 15) * 53) >> 7;
+        #[inline(always)]
+        fn widen(self) -> i32 {
+            self as i32
+        }
+        #[inline(always)]
+        fn narrow(v: i32) -> Self {
+            v.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+        }
+        #[inline(always)]
+        fn quarter_sine() -> &'static [Self] {
+            &super::SINE_Q15
+        }
+    }
+
+    impl Sealed for i32 {
+        type Wide = i64;
+        const GROWTH_LIMIT: i64 = ((1 << 31) * 53) >> 7;
+        #[inline(always)]
+        fn widen(self) -> i64 {
+            self as i64
+        }
+        #[inline(always)]
+        fn narrow(v: i64) -> Self {
+            v.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+        }
+        #[inline(always)]
+        fn quarter_sine() -> &'static [Self] {
+            &super::SINE_Q31
+        }
+    }
+}
+
+use sealed::Wide;
+
+/// Fixed-point sample format: implemented for `i16` (Q15) and `i32` (Q31).
+pub trait Fixed: Copy + Default + PartialEq + core::fmt::Debug + sealed::Sealed {
+    /// Number of fractional bits.
+    const FRAC_BITS: u32;
+    /// Widen to `i64`.
+    fn to_i64(self) -> i64;
+    /// Narrow from `i64`, saturating at the type's range.
+    fn saturate(v: i64) -> Self;
+    /// Nearest fixed-point value to `x`, saturating outside `[-1, 1)`.
+    fn from_f32(x: f32) -> Self {
+        let v = x * (1u64 << Self::FRAC_BITS) as f32;
+        Self::saturate(libm::roundf(v) as i64)
+    }
+    /// Value as a fraction of full scale.
+    fn to_f32(self) -> f32 {
+        self.to_i64() as f32 / (1u64 << Self::FRAC_BITS) as f32
+    }
+}
+
+impl Fixed for i16 {
+    const FRAC_BITS: u32 = 15;
+    #[inline(always)]
+    fn to_i64(self) -> i64 {
+        self as i64
+    }
+    #[inline(always)]
+    fn saturate(v: i64) -> Self {
+        v.clamp(i16::MIN as i64, i16::MAX as i64) as i16
+    }
+}
+
+impl Fixed for i32 {
+    const FRAC_BITS: u32 = 31;
+    #[inline(always)]
+    fn to_i64(self) -> i64 {
+        self as i64
+    }
+    #[inline(always)]
+    fn saturate(v: i64) -> Self {
+        v.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+}
+
+/// Complex fixed-point sample.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComplexFixed<S: Fixed> {
+    pub re: S,
+    pub im: S,
+}
+
+/// Complex Q15 sample.
+pub type ComplexQ15 = ComplexFixed<i16>;
+/// Complex Q31 sample.
+pub type ComplexQ31 = ComplexFixed<i32>;
+
+impl<S: Fixed> ComplexFixed<S> {
+    pub fn new(re: S, im: S) -> Self {
+        Self { re, im }
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+}
+
+/// Longest transform the fixed-point FFTs support: the period of the
+/// compile-time twiddle tables.
+pub const MAX_LEN: usize = 4096;
+
+const QUARTER: usize = MAX_LEN / 4;
+
+/// `sin(2πk/MAX_LEN)` for `k = 0..=MAX_LEN/4` with `bits` fractional bits,
+/// evaluated in `f64` by [`sin_cos_turns`], rounded to nearest and kept
+/// below `1.0`.
+const fn quarter_sine(bits: u32) -> [i64; QUARTER + 1] {
+    let scale = (1u64 << bits) as f64;
+    let max = (1i64 << bits) - 1;
+    let mut table = [0i64; QUARTER + 1];
+    let mut k = 0;
+    while k <= QUARTER {
+        let v = sin_cos_turns(k as u128, MAX_LEN as u128).0 * scale + 0.5;
+        let v = v as i64;
+        table[k] = if v > max { max } else { v };
+        k += 1;
+    }
+    table
+}
+
+const fn quarter_sine_q15() -> [i16; QUARTER + 1] {
+    let wide = quarter_sine(15);
+    let mut table = [0i16; QUARTER + 1];
+    let mut k = 0;
+    while k <= QUARTER {
+        table[k] = wide[k] as i16;
+        k += 1;
+    }
+    table
+}
+
+const fn quarter_sine_q31() -> [i32; QUARTER + 1] {
+    let wide = quarter_sine(31);
+    let mut table = [0i32; QUARTER + 1];
+    let mut k = 0;
+    while k <= QUARTER {
+        table[k] = wide[k] as i32;
+        k += 1;
+    }
+    table
+}
+
+/// Quarter-wave Q15 sine table, built at compile time.
+static SINE_Q15: [i16; QUARTER + 1] = quarter_sine_q15();
+/// Quarter-wave Q31 sine table, built at compile time.
+static SINE_Q31: [i32; QUARTER + 1] = quarter_sine_q31();
+
+/// `exp(-2πik/n)` for `k < n/2` and a power of two `n <= MAX_LEN`, read
+/// from the quarter-wave table by symmetry.
+#[inline(always)]
+fn twiddle<S: Fixed>(k: usize, n: usize) -> (S::Wide, S::Wide) {
+    let table = S::quarter_sine();
+    let j = k * (MAX_LEN / n);
+    let (cos, sin) = if j <= QUARTER {
+        (table[QUARTER - j].widen(), table[j])
+    } else {
+        (-table[j - QUARTER].widen(), table[2 * QUARTER - j])
+    };
+    (cos, -sin.widen())
+}
+
+/// Rounded fixed-point product `a · w` with `w` a twiddle of the same
+/// format, in the format's wide type. Each partial product drops one bit
+/// first so the sum cannot overflow.
+#[inline(always)]
+fn mul<S: Fixed>(a: (S::Wide, S::Wide), w: (S::Wide, S::Wide)) -> (S::Wide, S::Wide) {
+    let shift = S::FRAC_BITS - 1;
+    let round = S::Wide::ONE << (shift - 1);
+    let re = (((a.0 * w.0) >> 1) - ((a.1 * w.1) >> 1) + round) >> shift;
+    let im = (((a.0 * w.1) >> 1) + ((a.1 * w.0) >> 1) + round) >> shift;
+    (re, im)
+}
+
+/// Right shift needed so every component of `data` is within the format's
+/// growth limit.
+fn headroom_shift<S: Fixed>(data: &[ComplexFixed<S>]) -> u32 {
+    let max = data
+        .iter()
+        .map(|c| c.re.widen().abs().max(c.im.widen().abs()))
+        .max()
+        .unwrap_or(S::Wide::ZERO);
+    let mut shift = 0;
+    while (max >> shift) > S::GROWTH_LIMIT {
+        shift += 1;
+    }
+    shift
+}
+
+/// Arithmetic right shift, rounding half to even.
+///
+/// Half-up rounding adds a +¼ LSB bias on a one-bit shift; the later
+/// unscaled stages concentrate that constant offset into a single bin, so
+/// ties must round without bias.
+#[inline(always)]
+fn shr_round<W: Wide>(v: W, shift: u32) -> W {
+    if shift == 0 {
+        return v;
+    }
+    let half = W::ONE << (shift - 1);
+    let rest = v & ((half << 1) - W::ONE);
+    let q = v >> shift;
+    if rest > half || (rest == half && q & W::ONE == W::ONE) {
+        q + W::ONE
+    } else {
+        q
+    }
+}
+
+fn scale_block<S: Fixed>(data: &mut [ComplexFixed<S>], shift: u32) {
+    if shift == 0 {
+        return;
+    }
+    for c in data.iter_mut() {
+        c.re = S::narrow(shr_round(c.re.widen(), shift));
+        c.im = S::narrow(shr_round(c.im.widen(), shift));
+    }
+}
+
+fn check_len(n: usize) -> Result<(), FftError> {
+    if n == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if !n.is_power_of_two() {
+        return Err(FftError::NonPowerOfTwoNoStd);
+    }
+    if n > MAX_LEN {
+        return Err(FftError::InvalidValue);
+    }
+    Ok(())
+}
+
+/// In-place forward FFT with block floating-point scaling. `data.len()`
+/// must be a power of two. Returns the exponent `e` such that the DFT is
+/// `data · 2^e`.
+pub fn fft<S: Fixed>(data: &mut [ComplexFixed<S>]) -> Result<i32, FftError> {
+    let n = data.len();
+    check_len(n)?;
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+    let mut exponent = 0u32;
+    let mut len = 2;
+    while len <= n {
+        let shift = headroom_shift(data);
+        scale_block(data, shift);
+        exponent += shift;
+        let half = len / 2;
+        for k in 0..half {
+            let w = twiddle::<S>(k * (n / len), n);
+            for start in (0..n).step_by(len) {
+                let a = data[start + k];
+                let b = data[start + k + half];
+                let t = mul::<S>((b.re.widen(), b.im.widen()), w);
+                let (ar, ai) = (a.re.widen(), a.im.widen());
+                data[start + k] = ComplexFixed::new(S::narrow(ar + t.0), S::narrow(ai + t.1));
+                data[start + k + half] =
+                    ComplexFixed::new(S::narrow(ar - t.0), S::narrow(ai - t.1));
+            }
+        }
+        len <<= 1;
+    }
+    Ok(exponent as i32)
+}
+
+fn conjugate<S: Fixed>(data: &mut [ComplexFixed<S>]) {
+    for c in data.iter_mut() {
+        c.im = S::narrow(-c.im.widen());
+    }
+}
+
+/// In-place inverse FFT normalized by `1/n`. Returns the exponent `e` such
+/// that the inverse is `data · 2^e`.
+pub fn ifft<S: Fixed>(data: &mut [ComplexFixed<S>]) -> Result<i32, FftError> {
+    conjugate(data);
+    let exponent = fft(data)?;
+    conjugate(data);
+    Ok(exponent - data.len().trailing_zeros() as i32)
+}
+
+/// Real-input FFT of `input` (length `n`, a power of two of at least 2)
+/// into `output` (length `n/2 + 1`). Returns the exponent `e` such that the
+/// non-negative half of the DFT is `output · 2^e`.
+pub fn rfft<S: Fixed>(input: &[S], output: &mut [ComplexFixed<S>]) -> Result<i32, FftError> {
+    let n = input.len();
+    check_len(n)?;
+    if n < 2 {
+        return Err(FftError::InvalidValue);
+    }
+    let m = n / 2;
+    if output.len() != m + 1 {
+        return Err(FftError::MismatchedLengths);
+    }
+    for (o, pair) in output.iter_mut().zip(input.chunks_exact(2)) {
+        *o = ComplexFixed::new(pair[0], pair[1]);
+    }
+    let mut exponent = fft(&mut output[..m])?;
+    let shift = headroom_shift(&output[..m]);
+    scale_block(&mut output[..m], shift);
+    exponent += shift as i32;
+
+    let z0 = output[0];
+    let (r0, i0) = (z0.re.widen(), z0.im.widen());
+    output[0] = ComplexFixed::new(S::narrow(r0 + i0), S::default());
+    output[m] = ComplexFixed::new(S::narrow(r0 - i0), S::default());
+    for k in 1..=m / 2 {
+        let a = output[k];
+        let b = output[m - k];
+        let (ar, ai) = (a.re.widen(), a.im.widen());
+        let (br, bi) = (b.re.widen(), b.im.widen());
+        // even = (Z[k] + conj Z[m-k]) / 2, odd = -i (Z[k] - conj Z[m-k]) / 2
+        let even = (shr_round(ar + br, 1), shr_round(ai - bi, 1));
+        let odd = (shr_round(ai + bi, 1), shr_round(br - ar, 1));
+        let t = mul::<S>(odd, twiddle::<S>(k, n));
+        output[k] = ComplexFixed::new(S::narrow(even.0 + t.0), S::narrow(even.1 + t.1));
+        // X[m-k] = conj(even - w·odd)
+        output[m - k] = ComplexFixed::new(S::narrow(even.0 - t.0), S::narrow(t.1 - even.1));
+    }
+    Ok(exponent)
+}
+
+/// Inverse of [`rfft`]: `input` holds `n/2 + 1` bins and `output` receives
+/// `n` real samples. Returns the exponent `e` such that the inverse DFT,
+/// normalized by `1/n`, is `output · 2^e`.
+pub fn irfft<S: Fixed>(input: &[ComplexFixed<S>], output: &mut [S]) -> Result<i32, FftError> {
+    let n = output.len();
+    check_len(n)?;
+    if n < 2 {
+        return Err(FftError::InvalidValue);
+    }
+    let m = n / 2;
+    if input.len() != m + 1 {
+        return Err(FftError::MismatchedLengths);
+    }
+    let shift = headroom_shift(input);
+    let load = |c: ComplexFixed<S>| {
+        (
+            shr_round(c.re.widen(), shift),
+            shr_round(c.im.widen(), shift),
+        )
+    };
+    // SAFETY: `ComplexFixed<S>` is `#[repr(C)]` with two `S` fields, so `n`
+    // samples are `m` complex values with the same alignment.
+    let z =
+        unsafe { core::slice::from_raw_parts_mut(output.as_mut_ptr() as *mut ComplexFixed<S>, m) };
+    let (x0, xm) = (load(input[0]).0, load(input[m]).0);
+    z[0] = ComplexFixed::new(
+        S::narrow(shr_round(x0 + xm, 1)),
+        S::narrow(shr_round(x0 - xm, 1)),
+    );
+    for k in 1..=m / 2 {
+        let (ar, ai) = load(input[k]);
+        let (br, bi) = load(input[m - k]);
+        // even = (X[k] + conj X[m-k]) / 2, odd = conj(w) (X[k] - conj X[m-k]) / 2
+        let even = (shr_round(ar + br, 1), shr_round(ai - bi, 1));
+        let diff = (shr_round(ar - br, 1), shr_round(ai + bi, 1));
+        let w = twiddle::<S>(k, n);
+        let odd = mul::<S>(diff, (w.0, -w.1));
+        // Z[k] = even + i·odd, Z[m-k] = conj(even) + i·conj(odd)
+        z[k] = ComplexFixed::new(S::narrow(even.0 - odd.1), S::narrow(even.1 + odd.0));
+        z[m - k] = ComplexFixed::new(S::narrow(even.0 + odd.1), S::narrow(odd.0 - even.1));
+    }
+    let exponent = ifft(z)?;
+    Ok(exponent + shift as i32)
+}
+
+/// Stack-only, const-generic [`fft`] for power-of-two `N`.
+pub fn fft_inplace_stack<S: Fixed, const N: usize>(
+    buf: &mut [ComplexFixed<S>; N],
+) -> Result<i32, FftError> {
+    fft(buf)
+}
+
+/// Stack-only, const-generic [`ifft`] for power-of-two `N`.
+pub fn ifft_inplace_stack<S: Fixed, const N: usize>(
+    buf: &mut [ComplexFixed<S>; N],
+) -> Result<i32, FftError> {
+    ifft(buf)
+}
+
+/// Stack-only, const-generic [`rfft`]; `M` must be `N/2 + 1`.
+pub fn rfft_stack<S: Fixed, const N: usize, const M: usize>(
+    input: &[S; N],
+    output: &mut [ComplexFixed<S>; M],
+) -> Result<i32, FftError> {
+    rfft(input, output)
+}
+
+/// Stack-only, const-generic [`irfft`]; `M` must be `N/2 + 1`.
+pub fn irfft_stack<S: Fixed, const N: usize, const M: usize>(
+    input: &[ComplexFixed<S>; M],
+    output: &mut [S; N],
+) -> Result<i32, FftError> {
+    irfft(input, output)
+}
+
+#[cfg(all(feature = "internal-tests", test))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn twiddle_tables_are_pinned() {
+        assert_eq!(SINE_Q15.len(), MAX_LEN / 4 + 1);
+        assert_eq!(
+            (SINE_Q15[0], SINE_Q15[512], SINE_Q15[1024]),
+            (0, 23170, 32767)
+        );
+        assert_eq!(
+            (SINE_Q31[0], SINE_Q31[512], SINE_Q31[1024]),
+            (0, 1_518_500_250, i32::MAX)
+        );
+        for k in 0..=QUARTER {
+            let s = libm::sin(2.0 * core::f64::consts::PI * k as f64 / MAX_LEN as f64);
+            assert!((SINE_Q15[k] as f64 - s * 32768.0).abs() <= 1.0, "Q15 k={k}");
+            assert!(
+                (SINE_Q31[k] as f64 - s * 2_147_483_648.0).abs() <= 1.0,
+                "Q31 k={k}"
+            );
+        }
+        // exp(-iπ/4) and exp(-3iπ/4) of an 8-point transform.
+        assert_eq!(twiddle::<i32>(1, 8), (1_518_500_250, -1_518_500_250));
+        assert_eq!(twiddle::<i16>(3, 8), (-23170, -23170));
+        assert_eq!(twiddle::<i16>(0, MAX_LEN), (32767, 0));
+    }
+}
@@ -0,0 +1,505 @@
+//! Bailey's four-step FFT for transforms much larger than the cache.
+//!
+//! A length `n = n1·n2` transform (`n1 ≤ n2`, both close to `√n`) views the
+//! input as an `n2 × n1` matrix and runs in two passes over column blocks
+//! sized to stay in L2:
+//!
+//! 1. length-`n2` FFTs down each column, the `exp(-2πi·j1·k2/n)` twiddle,
+//!    and a transpose into an `n1 × n2` scratch matrix;
+//! 2. length-`n1` FFTs down each column of the scratch matrix, written back
+//!    in natural order.
+//!
+//! Each block's FFTs run together as one radix-2 Stockham transform whose
+//! butterflies act on whole block rows, so memory is swept twice instead of
+//! once per radix-2 pass, and the transpose happens block by block while
+//! the data is in cache. With the `parallel` feature the column blocks of
+//! each pass are spread over the plan's [`Parallelism`](crate::parallel::Parallelism).
+//!
+//! Blocks are sized for a fixed 256 KiB budget rather than tuned to the
+//! running CPU's caches; there is no six-step variant and no
+//! cache-oblivious transpose.
+//!
+//! [`ScalarFftImpl`](crate::fft::ScalarFftImpl) and
+//! [`SharedFftPlan`] take this path for power-of-two lengths of at least
+//! [`FOUR_STEP_MIN_LEN`]; [`FftStrategy::FourStep`](crate::fft::FftStrategy::FourStep)
+//! forces it for any composite length, or a plan can be used directly:
+//!
+//! ```
+//! use kofft::four_step::FourStepPlan;
+//! use kofft::Complex32;
+//!
+//! let plan = FourStepPlan::<f32>::new(1 << 12).unwrap();
+//! assert_eq!(plan.factors(), (64, 64));
+//! let mut data = vec![Complex32::new(1.0, 0.0); 1 << 12];
+//! let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+//! plan.fft(&mut data, &mut scratch).unwrap();
+//! assert!((data[0].re - 4096.0).abs() < 1e-2);
+//! ```
+
+use alloc::sync::Arc;
+#[cfg(feature = "parallel")]
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "parallel")]
+use crate::parallel::{Parallelism, SharedMut};
+
+use crate::fft::{Complex, FftError, SharedFftPlan};
+use crate::num::Float;
+
+/// Smallest power-of-two length that [`ScalarFftImpl`](crate::fft::ScalarFftImpl)
+/// and [`SharedFftPlan`] run through the four-step algorithm: 2^20 points
+/// (8 MiB of `Complex32`), past the L2 cache of current cores.
+pub const FOUR_STEP_MIN_LEN: usize = 1 << 20;
+
+/// Working-set budget of one column block: both Stockham buffers.
+const BLOCK_BYTES: usize = 256 * 1024;
+/// Widest column block; wider blocks stop paying off once rows span
+/// several cache lines.
+const MAX_BLOCK: usize = 64;
+
+/// The column FFT of one pass.
+enum Pass<T: Float> {
+    /// Radix-2 Stockham across a whole block; `exp(-2πik/len)` for
+    /// `k < len/2`.
+    Batched(Arc<[Complex<T>]>),
+    /// Other lengths, one column at a time.
+    Single(SharedFftPlan<T>),
+}
+
+impl<T: Float> Pass<T> {
+    fn new(len: usize) -> Result<Self, FftError> {
+        Ok(if len.is_power_of_two() {
+            Pass::Batched(roots(len / 2, 1, len))
+        } else {
+            Pass::Single(SharedFftPlan::new(len)?)
+        })
+    }
+
+    /// Scratch beyond the two block buffers.
+    fn extra_len(&self, len: usize) -> usize {
+        match self {
+            Pass::Batched(_) => 0,
+            Pass::Single(plan) => len + plan.scratch_len(),
+        }
+    }
+
+    /// FFT of length `len` down each column of the `len × width` row-major
+    /// block `buf`, in place; `tmp` has the size of `buf`.
+    fn run(
+        &self,
+        len: usize,
+        width: usize,
+        buf: &mut [Complex<T>],
+        tmp: &mut [Complex<T>],
+        extra: &mut [Complex<T>],
+    ) {
+        match self {
+            Pass::Batched(twiddles) => {
+                if stockham_columns(buf, tmp, len, width, twiddles) {
+                    buf.copy_from_slice(tmp);
+                }
+            }
+            Pass::Single(plan) => {
+                let (col, rest) = extra.split_at_mut(len);
+                for t in 0..width {
+                    for (c, row) in col.iter_mut().zip(buf.chunks_exact(width)) {
+                        *c = row[t];
+                    }
+                    plan.execute(col, rest);
+                    for (c, row) in col.iter().zip(buf.chunks_exact_mut(width)) {
+                        row[t] = *c;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Radix-2 Stockham FFT of length `len` down the columns of the
+/// `len × width` block `src`, ping-ponging with `dst`. Every butterfly
+/// combines two block rows with one twiddle. Returns `true` if the result
+/// ended up in `dst`.
+fn stockham_columns<T: Float>(
+    src: &mut [Complex<T>],
+    dst: &mut [Complex<T>],
+    len: usize,
+    width: usize,
+    twiddles: &[Complex<T>],
+) -> bool {
+    let (mut src, mut dst) = (src, dst);
+    let mut swapped = false;
+    let mut n1 = 1;
+    let mut n2 = len;
+    while n1 < len {
+        n2 >>= 1;
+        let (lo, hi) = dst.split_at_mut(n1 * n2 * width);
+        for k in 0..n1 {
+            let w = twiddles[k * n2];
+            let a = &src[2 * k * n2 * width..(2 * k + 1) * n2 * width];
+            let b = &src[(2 * k + 1) * n2 * width..(2 * k + 2) * n2 * width];
+            let out0 = &mut lo[k * n2 * width..(k + 1) * n2 * width];
+            let out1 = &mut hi[k * n2 * width..(k + 1) * n2 * width];
+            for (((o0, o1), &u), &v) in out0.iter_mut().zip(out1).zip(a).zip(b) {
+                let v = v.mul(w);
+                *o0 = u.add(v);
+                *o1 = u.sub(v);
+            }
+        }
+        core::mem::swap(&mut src, &mut dst);
+        swapped = !swapped;
+        n1 <<= 1;
+    }
+    swapped
+}
+
+/// Precomputed four-step plan: the column FFTs of both passes and the
+/// inter-pass twiddles. Immutable and `Send + Sync` like [`SharedFftPlan`].
+pub struct FourStepPlan<T: Float> {
+    n1: usize,
+    n2: usize,
+    /// Columns per block.
+    block: usize,
+    /// Length-`n2` FFTs of the first pass.
+    first: Pass<T>,
+    /// Length-`n1` FFTs of the second pass.
+    second: Pass<T>,
+    /// `exp(-2πi·b/n)` for `b < n2`.
+    fine: Arc<[Complex<T>]>,
+    /// `exp(-2πi·a·n2/n)` for `a < n1`.
+    coarse: Arc<[Complex<T>]>,
+    /// Workers for [`fft_parallel`](Self::fft_parallel).
+    #[cfg(feature = "parallel")]
+    parallelism: Parallelism,
+}
+
+/// `(n1, n2)` with `n1` the largest divisor of `n` not above `√n`, or `None`
+/// for primes and `n < 4`.
+fn split(n: usize) -> Option<(usize, usize)> {
+    if n.is_power_of_two() {
+        let n1 = 1usize << (n.trailing_zeros() / 2);
+        return (n1 > 1).then_some((n1, n / n1));
+    }
+    let mut d = n.isqrt();
+    while d > 1 {
+        if n.is_multiple_of(d) {
+            return Some((d, n / d));
+        }
+        d -= 1;
+    }
+    None
+}
+
+fn roots<T: Float>(count: usize, step: usize, n: usize) -> Arc<[Complex<T>]> {
+    let table: Vec<Complex<T>> = (0..count)
+        .map(|k| {
+            let angle = -2.0 * core::f64::consts::PI * ((k * step) % n) as f64 / n as f64;
+            let (s, c) = libm::sincos(angle);
+            Complex::new(T::from_f64(c), T::from_f64(s))
+        })
+        .collect();
+    Arc::from(table)
+}
+
+impl<T: Float> FourStepPlan<T> {
+    /// Plan a transform of length `n`, which must be composite.
+    ///
+    /// Returns [`FftError::EmptyInput`] for `n == 0` and
+    /// [`FftError::InvalidValue`] when `n` is prime or below 4.
+    pub fn new(n: usize) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        let (n1, n2) = split(n).ok_or(FftError::InvalidValue)?;
+        let per_column = 2 * n2 * core::mem::size_of::<Complex<T>>();
+        let block = (BLOCK_BYTES / per_column).clamp(1, MAX_BLOCK);
+        Ok(Self {
+            n1,
+            n2,
+            block: 1 << block.ilog2(),
+            first: Pass::new(n2)?,
+            second: Pass::new(n1)?,
+            fine: roots(n2, 1, n),
+            coarse: roots(n1, n2, n),
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
+        })
+    }
+
+    /// Run [`fft_parallel`](Self::fft_parallel) and
+    /// [`ifft_parallel`](Self::ifft_parallel) on `parallelism` instead of
+    /// Rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Workers used by the parallel transforms.
+    #[cfg(feature = "parallel")]
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// Transform length.
+    pub fn size(&self) -> usize {
+        self.n1 * self.n2
+    }
+
+    /// The split `(n1, n2)`: FFTs of length `n2` down the `n1` columns of
+    /// the input, then of length `n1` down the `n2` columns of the result.
+    pub fn factors(&self) -> (usize, usize) {
+        (self.n1, self.n2)
+    }
+
+    /// Minimum scratch length accepted by [`fft`](Self::fft) and
+    /// [`ifft`](Self::ifft).
+    pub fn scratch_len(&self) -> usize {
+        self.size() + self.work_len()
+    }
+
+    /// Per-worker scratch for one column block.
+    fn work_len(&self) -> usize {
+        let extra = self
+            .first
+            .extra_len(self.n2)
+            .max(self.second.extra_len(self.n1));
+        2 * self.n2 * self.block + extra
+    }
+
+    fn check(
+        &self,
+        data: &[Complex<T>],
+        scratch: &[Complex<T>],
+        need: usize,
+    ) -> Result<(), FftError> {
+        if data.len() != self.size() || scratch.len() < need {
+            return Err(FftError::MismatchedLengths);
+        }
+        Ok(())
+    }
+
+    /// In-place forward FFT of `data` (length [`size`](Self::size)).
+    pub fn fft(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) -> Result<(), FftError> {
+        self.check(data, scratch, self.scratch_len())?;
+        self.execute_with_scratch(data, scratch);
+        Ok(())
+    }
+
+    /// In-place inverse FFT of `data`, scaled by `1/n`.
+    pub fn ifft(
+        &self,
+        data: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.check(data, scratch, self.scratch_len())?;
+        for c in data.iter_mut() {
+            c.im = -c.im;
+        }
+        self.execute_with_scratch(data, scratch);
+        let scale = T::one() / T::from_f64(data.len() as f64);
+        for c in data.iter_mut() {
+            *c = Complex::new(c.re * scale, -c.im * scale);
+        }
+        Ok(())
+    }
+
+    /// Unnormalized forward transform with `scratch` of at least
+    /// [`scratch_len`](Self::scratch_len).
+    pub(crate) fn execute_with_scratch(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        let (n1, n2, block) = (self.n1, self.n2, self.block);
+        let (matrix, work) = scratch.split_at_mut(self.size());
+        for (b, out) in matrix.chunks_mut(block * n2).enumerate() {
+            self.first_block(data, b * block, out, work);
+        }
+        for c0 in (0..n2).step_by(block) {
+            let width = block.min(n2 - c0);
+            let result = self.second_block(matrix, c0, width, work);
+            for (k1, row) in result.chunks_exact(width).enumerate().take(n1) {
+                data[k1 * n2 + c0..k1 * n2 + c0 + width].copy_from_slice(row);
+            }
+        }
+    }
+
+    /// First pass over input columns `c0..c0 + out.len() / n2`: column FFTs,
+    /// twiddles, and the transpose into rows of `out`.
+    fn first_block(
+        &self,
+        data: &[Complex<T>],
+        c0: usize,
+        out: &mut [Complex<T>],
+        work: &mut [Complex<T>],
+    ) {
+        let (n1, n2) = (self.n1, self.n2);
+        let width = out.len() / n2;
+        let (buf, rest) = work.split_at_mut(n2 * width);
+        let (tmp, extra) = rest.split_at_mut(n2 * width);
+        for (j2, row) in buf.chunks_exact_mut(width).enumerate() {
+            row.copy_from_slice(&data[j2 * n1 + c0..j2 * n1 + c0 + width]);
+        }
+        self.first.run(n2, width, buf, tmp, extra);
+        for (t, row) in out.chunks_exact_mut(n2).enumerate() {
+            // `exp(-2πi·m/n)` for `m = j1·k2`, read as
+            // `coarse[m / n2] · fine[m % n2]`; `j1 < n1 <= n2`, so the
+            // remainder wraps at most once per step.
+            let j1 = c0 + t;
+            let (mut hi, mut lo) = (0, 0);
+            for (k2, o) in row.iter_mut().enumerate() {
+                *o = buf[k2 * width + t].mul(self.coarse[hi].mul(self.fine[lo]));
+                lo += j1;
+                if lo >= n2 {
+                    lo -= n2;
+                    hi += 1;
+                }
+            }
+        }
+    }
+
+    /// Second pass over columns `c0..c0 + width` of the `n1 × n2` matrix;
+    /// returns the `n1 × width` block of output rows.
+    fn second_block<'w>(
+        &self,
+        matrix: &[Complex<T>],
+        c0: usize,
+        width: usize,
+        work: &'w mut [Complex<T>],
+    ) -> &'w [Complex<T>] {
+        let (n1, n2) = (self.n1, self.n2);
+        let (buf, rest) = work.split_at_mut(n1 * width);
+        let (tmp, extra) = rest.split_at_mut(n1 * width);
+        for (j1, row) in buf.chunks_exact_mut(width).enumerate() {
+            row.copy_from_slice(&matrix[j1 * n2 + c0..j1 * n2 + c0 + width]);
+        }
+        self.second.run(n1, width, buf, tmp, extra);
+        buf
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: Float + Send + Sync> FourStepPlan<T> {
+    /// [`fft`](Self::fft) with the column blocks of each pass spread over
+    /// the plan's [`Parallelism`]. `scratch` needs only [`size`](Self::size)
+    /// elements; each worker allocates its own block buffers once.
+    pub fn fft_parallel(
+        &self,
+        data: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.check(data, scratch, self.size())?;
+        self.execute_parallel(&self.parallelism, data, scratch)
+    }
+
+    /// Parallel [`ifft`](Self::ifft); `scratch` as in
+    /// [`fft_parallel`](Self::fft_parallel).
+    pub fn ifft_parallel(
+        &self,
+        data: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        self.check(data, scratch, self.size())?;
+        let par = &self.parallelism;
+        let chunk = data.len().div_ceil(par.threads());
+        par.try_for_each_chunk_mut(
+            data,
+            chunk,
+            || (),
+            |_, _, part| {
+                part.iter_mut().for_each(|c| c.im = -c.im);
+                Ok(())
+            },
+        )?;
+        self.execute_parallel(par, data, scratch)?;
+        let scale = T::one() / T::from_f64(data.len() as f64);
+        par.try_for_each_chunk_mut(
+            data,
+            chunk,
+            || (),
+            |_, _, part| {
+                for c in part {
+                    *c = Complex::new(c.re * scale, -c.im * scale);
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Unnormalized forward transform on `par`; `scratch` holds at least
+    /// [`size`](Self::size) elements.
+    pub(crate) fn execute_parallel(
+        &self,
+        par: &Parallelism,
+        data: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        let (n1, n2, block) = (self.n1, self.n2, self.block);
+        let matrix = &mut scratch[..self.size()];
+        let input = &*data;
+        par.try_for_each_chunk_mut(
+            matrix,
+            block * n2,
+            || vec![Complex::zero(); self.work_len()],
+            |work, b, out| {
+                self.first_block(input, b * block, out, work);
+                Ok(())
+            },
+        )?;
+        let out = SharedMut(data.as_mut_ptr());
+        let matrix = &*matrix;
+        par.try_for_each_init(
+            n2.div_ceil(block),
+            || vec![Complex::zero(); self.work_len()],
+            |work, b| {
+                let c0 = b * block;
+                let width = block.min(n2 - c0);
+                let result = self.second_block(matrix, c0, width, work);
+                for (k1, row) in result.chunks_exact(width).enumerate().take(n1) {
+                    // SAFETY: blocks write disjoint column ranges of `data`,
+                    // which outlives the parallel loop.
+                    unsafe {
+                        let dst = out.get().add(k1 * n2 + c0);
+                        core::ptr::copy_nonoverlapping(row.as_ptr(), dst, width);
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(all(feature = "internal-tests", test))]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn split_prefers_square_factors() {
+        assert_eq!(split(1 << 20), Some((1024, 1024)));
+        assert_eq!(split(1 << 21), Some((1024, 2048)));
+        assert_eq!(split(1000), Some((25, 40)));
+        assert_eq!(split(97), None);
+        assert_eq!(split(2), None);
+    }
+
+    #[test]
+    fn column_stockham_matches_per_column_fft() {
+        let (len, width) = (32, 5);
+        let x: Vec<Complex<f64>> = (0..len * width)
+            .map(|i| Complex::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+            .collect();
+        let mut buf = x.clone();
+        let mut tmp = vec![Complex::zero(); len * width];
+        Pass::<f64>::new(len)
+            .unwrap()
+            .run(len, width, &mut buf, &mut tmp, &mut []);
+        let plan = SharedFftPlan::<f64>::new(len).unwrap();
+        let mut scratch = vec![Complex::zero(); plan.scratch_len()];
+        for t in 0..width {
+            let mut col: Vec<_> = x.iter().skip(t).step_by(width).copied().collect();
+            plan.fft(&mut col, &mut scratch).unwrap();
+            for (k, c) in col.iter().enumerate() {
+                let d = buf[k * width + t];
+                assert!((d.re - c.re).abs() < 1e-12 && (d.im - c.im).abs() < 1e-12);
+            }
+        }
+    }
+}
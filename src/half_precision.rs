@@ -0,0 +1,296 @@
+//! Half-precision (`f16`/`bf16`) storage with `f32` compute.
+//!
+//! Spectrogram archives and WASM inference are usually limited by memory
+//! bandwidth rather than arithmetic, so this module keeps samples and
+//! spectra in 16-bit floats ([`ComplexF16`], [`ComplexBf16`]) while every
+//! transform runs in `f32`: buffers are widened on entry and rounded back to
+//! the nearest half-precision value on exit.
+//!
+//! Slice conversions go through the `half` crate, which uses F16C on x86
+//! (detected at runtime when `std` is enabled) and NEON fp16 on AArch64 when
+//! the target enables `fp16`. Widening `bf16` is a 16-bit shift that the
+//! compiler vectorizes on every target.
+//!
+//! ```
+//! use kofft::fft::ScalarFftImpl;
+//! use kofft::half_precision::{self, f16, ComplexF16};
+//!
+//! let fft = ScalarFftImpl::<f32>::default();
+//! let mut data = [ComplexF16::new(f16::from_f32(1.0), f16::ZERO); 8];
+//! half_precision::fft(&mut data, &fft).unwrap();
+//! assert_eq!(data[0].re.to_f32(), 8.0);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use half::slice::HalfFloatSliceExt;
+pub use half::{bf16, f16};
+
+use crate::fft::{Complex32, FftError, FftImpl};
+use crate::num::{Complex, Float};
+use crate::rfft::RealFftPlan;
+
+/// Complex number stored as two IEEE 754 binary16 values.
+pub type ComplexF16 = Complex<f16>;
+/// Complex number stored as two bfloat16 values.
+pub type ComplexBf16 = Complex<bf16>;
+
+macro_rules! impl_float {
+    ($t:ty) => {
+        /// Arithmetic rounds through `f32`; prefer the `f32`-compute entry
+        /// points in this module over running kernels on this type directly.
+        impl Float for $t {
+            fn zero() -> Self {
+                <$t>::ZERO
+            }
+            fn one() -> Self {
+                <$t>::ONE
+            }
+            fn from_f32(x: f32) -> Self {
+                <$t>::from_f32(x)
+            }
+            fn from_f64(x: f64) -> Self {
+                <$t>::from_f64(x)
+            }
+            fn cos(self) -> Self {
+                <$t>::from_f32(Float::cos(self.to_f32()))
+            }
+            fn sin(self) -> Self {
+                <$t>::from_f32(Float::sin(self.to_f32()))
+            }
+            fn sin_cos(self) -> (Self, Self) {
+                let (s, c) = Float::sin_cos(self.to_f32());
+                (<$t>::from_f32(s), <$t>::from_f32(c))
+            }
+            fn pi() -> Self {
+                <$t>::PI
+            }
+        }
+    };
+}
+
+impl_float!(f16);
+impl_float!(bf16);
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::f16 {}
+    impl Sealed for super::bf16 {}
+}
+
+/// 16-bit storage format: implemented for [`f16`] and [`bf16`].
+pub trait HalfFloat: Float + sealed::Sealed {
+    /// Convert `src` to `f32` into `dst` of the same length.
+    fn widen(src: &[Self], dst: &mut [f32]);
+    /// Round `src` to the nearest representable value into `dst` of the
+    /// same length.
+    fn narrow(src: &[f32], dst: &mut [Self]);
+}
+
+impl HalfFloat for f16 {
+    #[inline]
+    fn widen(src: &[Self], dst: &mut [f32]) {
+        src.convert_to_f32_slice(dst);
+    }
+    #[inline]
+    fn narrow(src: &[f32], dst: &mut [Self]) {
+        dst.convert_from_f32_slice(src);
+    }
+}
+
+impl HalfFloat for bf16 {
+    #[inline]
+    fn widen(src: &[Self], dst: &mut [f32]) {
+        src.convert_to_f32_slice(dst);
+    }
+    #[inline]
+    fn narrow(src: &[f32], dst: &mut [Self]) {
+        dst.convert_from_f32_slice(src);
+    }
+}
+
+fn flatten<T: Float>(data: &[Complex<T>]) -> &[T] {
+    // SAFETY: `Complex<T>` is `#[repr(C)]` with two `T` fields and no padding.
+    unsafe { core::slice::from_raw_parts(data.as_ptr() as *const T, data.len() * 2) }
+}
+
+fn flatten_mut<T: Float>(data: &mut [Complex<T>]) -> &mut [T] {
+    // SAFETY: as in `flatten`.
+    unsafe { core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut T, data.len() * 2) }
+}
+
+/// Widen half-precision samples into `dst`.
+pub fn to_f32<H: HalfFloat>(src: &[H], dst: &mut [f32]) -> Result<(), FftError> {
+    if src.len() != dst.len() {
+        return Err(FftError::MismatchedLengths);
+    }
+    H::widen(src, dst);
+    Ok(())
+}
+
+/// Round `f32` samples to half precision into `dst`.
+pub fn from_f32<H: HalfFloat>(src: &[f32], dst: &mut [H]) -> Result<(), FftError> {
+    if src.len() != dst.len() {
+        return Err(FftError::MismatchedLengths);
+    }
+    H::narrow(src, dst);
+    Ok(())
+}
+
+/// Widen half-precision complex samples into `dst`.
+pub fn complex_to_f32<H: HalfFloat>(
+    src: &[Complex<H>],
+    dst: &mut [Complex32],
+) -> Result<(), FftError> {
+    to_f32(flatten(src), flatten_mut(dst))
+}
+
+/// Round `f32` complex samples to half precision into `dst`.
+pub fn complex_from_f32<H: HalfFloat>(
+    src: &[Complex32],
+    dst: &mut [Complex<H>],
+) -> Result<(), FftError> {
+    from_f32(flatten(src), flatten_mut(dst))
+}
+
+fn widened<H: HalfFloat>(data: &[Complex<H>]) -> Vec<Complex32> {
+    let mut work = vec![Complex32::zero(); data.len()];
+    H::widen(flatten(data), flatten_mut(&mut work));
+    work
+}
+
+/// In-place forward FFT of half-precision data, computed in `f32`.
+pub fn fft<H: HalfFloat, F: FftImpl<f32> + ?Sized>(
+    data: &mut [Complex<H>],
+    fft: &F,
+) -> Result<(), FftError> {
+    let mut work = widened(data);
+    fft.fft(&mut work)?;
+    H::narrow(flatten(&work), flatten_mut(data));
+    Ok(())
+}
+
+/// In-place inverse FFT of half-precision data, computed in `f32` and
+/// normalized like [`FftImpl::ifft`].
+pub fn ifft<H: HalfFloat, F: FftImpl<f32> + ?Sized>(
+    data: &mut [Complex<H>],
+    fft: &F,
+) -> Result<(), FftError> {
+    let mut work = widened(data);
+    fft.ifft(&mut work)?;
+    H::narrow(flatten(&work), flatten_mut(data));
+    Ok(())
+}
+
+/// Real-to-complex FFT of half-precision `input` (length `plan.size()`)
+/// into `output` (length `plan.spectrum_len()`).
+pub fn rfft<H: HalfFloat>(
+    plan: &RealFftPlan<f32>,
+    input: &[H],
+    output: &mut [Complex<H>],
+) -> Result<(), FftError> {
+    if input.len() != plan.size() || output.len() != plan.spectrum_len() {
+        return Err(FftError::MismatchedLengths);
+    }
+    let mut x = vec![0.0f32; input.len()];
+    H::widen(input, &mut x);
+    let mut spectrum = vec![Complex32::zero(); output.len()];
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    plan.rfft_with_scratch(&x, &mut spectrum, &mut scratch)?;
+    H::narrow(flatten(&spectrum), flatten_mut(output));
+    Ok(())
+}
+
+/// Complex-to-real inverse of [`rfft`], normalized by the plan.
+pub fn irfft<H: HalfFloat>(
+    plan: &RealFftPlan<f32>,
+    input: &[Complex<H>],
+    output: &mut [H],
+) -> Result<(), FftError> {
+    if output.len() != plan.size() || input.len() != plan.spectrum_len() {
+        return Err(FftError::MismatchedLengths);
+    }
+    let spectrum = widened(input);
+    let mut x = vec![0.0f32; output.len()];
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    plan.irfft_with_scratch(&spectrum, &mut x, &mut scratch)?;
+    H::narrow(&x, output);
+    Ok(())
+}
+
+/// STFT of a half-precision signal with the framing of [`crate::stft::stft`].
+///
+/// Each frame is widened, windowed and transformed in `f32` and stored back
+/// in half precision, so only one `f32` frame is live at a time.
+pub fn stft<H: HalfFloat, F: FftImpl<f32> + ?Sized>(
+    signal: &[H],
+    window: &[f32],
+    hop_size: usize,
+    output: &mut [Vec<Complex<H>>],
+    fft: &F,
+) -> Result<(), FftError> {
+    if hop_size == 0 {
+        return Err(FftError::InvalidHopSize);
+    }
+    if output.len() < signal.len().div_ceil(hop_size) {
+        return Err(FftError::MismatchedLengths);
+    }
+    let win_len = window.len();
+    let mut samples = vec![0.0f32; win_len];
+    let mut work = vec![Complex32::zero(); win_len];
+    for (frame_idx, frame) in output.iter_mut().enumerate() {
+        let start = (frame_idx * hop_size).min(signal.len());
+        let end = (start + win_len).min(signal.len());
+        let avail = end - start;
+        H::widen(&signal[start..end], &mut samples[..avail]);
+        samples[avail..].fill(0.0);
+        for ((w, &x), &g) in work.iter_mut().zip(&samples).zip(window) {
+            *w = Complex32::new(x * g, 0.0);
+        }
+        fft.fft(&mut work)?;
+        frame.resize(win_len, Complex::zero());
+        H::narrow(flatten(&work), flatten_mut(frame));
+    }
+    Ok(())
+}
+
+/// Inverse of [`stft`] with the overlap-add normalization of
+/// [`crate::stft::istft`]; accumulation happens in `f32` and the result is
+/// rounded into `output` once at the end.
+pub fn istft<H: HalfFloat, F: FftImpl<f32> + ?Sized>(
+    frames: &[Vec<Complex<H>>],
+    window: &[f32],
+    hop_size: usize,
+    output: &mut [H],
+    fft: &F,
+) -> Result<(), FftError> {
+    if hop_size == 0 {
+        return Err(FftError::InvalidHopSize);
+    }
+    let win_len = window.len();
+    let mut acc = vec![0.0f32; output.len()];
+    let mut norm = vec![0.0f32; output.len()];
+    let mut work = vec![Complex32::zero(); win_len];
+    for (frame_idx, frame) in frames.iter().enumerate() {
+        if frame.len() != win_len {
+            return Err(FftError::MismatchedLengths);
+        }
+        H::widen(flatten(frame), flatten_mut(&mut work));
+        fft.ifft(&mut work)?;
+        let start = frame_idx * hop_size;
+        for (i, (c, &g)) in work.iter().zip(window).enumerate() {
+            if start + i < acc.len() {
+                acc[start + i] += c.re * g;
+                norm[start + i] += g * g;
+            }
+        }
+    }
+    for (a, &n) in acc.iter_mut().zip(&norm) {
+        if n > 1e-8 {
+            *a /= n;
+        }
+    }
+    H::narrow(&acc, output);
+    Ok(())
+}
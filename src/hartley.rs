@@ -1,69 +1,441 @@
 //! Discrete Hartley Transform (DHT) module
 //! Supports DHT for f32 (real input)
 //! no_std + alloc compatible
+//!
+//! The transform is derived from the real FFT: for `X = rfft(x)` the DHT is
+//! `H[k] = Re X[k] - Im X[k]`, with the upper half recovered from the
+//! conjugate symmetry of `X`.  [`HartleyPlanner`] caches the FFT state so
+//! repeated transforms run in `O(n log n)` without re-planning, and
+//! [`HartleyPlan`] is the immutable, thread-safe plan for one length.
 
 extern crate alloc;
-use alloc::vec;
+use crate::fft::{
+    Complex, Complex32, FftError, FftImpl, Normalization, ScalarFftImpl, SharedFftPlan,
+};
+#[cfg(feature = "parallel")]
+use crate::parallel::Parallelism;
+use crate::rfft::{RealFftNum, RealFftPlan, RfftPlanner};
 use alloc::vec::Vec;
 use libm::{cosf, sinf};
 
-/// Discrete Hartley Transform (DHT)
-#[cfg(feature = "std")]
-pub fn dht(input: &[f32]) -> Vec<f32> {
-    let n = input.len();
-    let mut output = vec![0.0; n];
-    let factor = 2.0 * core::f32::consts::PI / n as f32;
-    for (k, out) in output.iter_mut().enumerate() {
-        let mut sum = 0.0;
-        for (i, &x) in input.iter().enumerate() {
-            let angle = factor * (i * k) as f32;
-            let re = cosf(angle);
-            let im = sinf(angle);
-            sum += x * (re + im);
+pub use crate::mcu::hartley::dht_inplace_stack;
+
+/// Planner for FFT-backed Hartley transforms.
+///
+/// Even lengths go through the cached [`RfftPlanner`]; odd lengths fall back
+/// to a complex FFT of the same size.  Work buffers are kept between calls so
+/// repeated transforms of the same length do not allocate.
+///
+/// The DHT is its own inverse up to a factor of `N`; where that factor goes
+/// is set with [`with_normalization`](Self::with_normalization).
+pub struct HartleyPlanner<T: RealFftNum> {
+    /// Real-FFT planner used for even lengths.
+    rfft: RfftPlanner<T>,
+    /// Complex FFT shared by every transform.
+    fft: ScalarFftImpl<T>,
+    /// Reusable work buffers.
+    buf: Vec<T>,
+    spectrum: Vec<Complex<T>>,
+    norm: Normalization,
+}
+
+impl<T: RealFftNum> Default for HartleyPlanner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RealFftNum> HartleyPlanner<T> {
+    /// Create a new [`HartleyPlanner`].
+    pub fn new() -> Self {
+        Self {
+            rfft: RfftPlanner::new(),
+            fft: ScalarFftImpl::default(),
+            buf: Vec::new(),
+            spectrum: Vec::new(),
+            norm: Normalization::Backward,
         }
-        *out = sum;
     }
-    output
+
+    /// Use `norm` instead of the default [`Normalization::Backward`].
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Normalization applied by the forward and inverse transforms.
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Copy `input` into the internal work buffer.
+    fn load(&mut self, input: &[T]) {
+        let n = input.len();
+        if self.buf.len() < n {
+            self.buf.resize(n, T::zero());
+        }
+        self.buf[..n].copy_from_slice(input);
+    }
+
+    /// Transform the first `output.len()` samples of the work buffer.
+    fn execute(&mut self, output: &mut [T]) -> Result<(), FftError> {
+        let n = output.len();
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if n.is_multiple_of(2) {
+            let half = n / 2;
+            if self.spectrum.len() < half + 1 {
+                self.spectrum.resize(half + 1, Complex::zero());
+            }
+            let spec = &mut self.spectrum[..half + 1];
+            self.rfft.rfft(&self.fft, &mut self.buf[..n], spec)?;
+            for (k, out) in output.iter_mut().enumerate() {
+                *out = if k <= half {
+                    spec[k].re - spec[k].im
+                } else {
+                    // X[k] = conj(X[n - k]) for real input.
+                    spec[n - k].re + spec[n - k].im
+                };
+            }
+        } else {
+            if self.spectrum.len() < n {
+                self.spectrum.resize(n, Complex::zero());
+            }
+            let spec = &mut self.spectrum[..n];
+            for (c, &x) in spec.iter_mut().zip(self.buf.iter()) {
+                *c = Complex::new(x, T::zero());
+            }
+            self.fft.fft(spec)?;
+            for (out, c) in output.iter_mut().zip(spec.iter()) {
+                *out = c.re - c.im;
+            }
+        }
+        Ok(())
+    }
+
+    /// Transform `input` into `output` and apply the forward or inverse
+    /// normalization factor.
+    fn transform(&mut self, input: &[T], output: &mut [T], inverse: bool) -> Result<(), FftError> {
+        if input.len() != output.len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        self.load(input);
+        self.finish(output, inverse)
+    }
+
+    fn finish(&mut self, output: &mut [T], inverse: bool) -> Result<(), FftError> {
+        self.execute(output)?;
+        let (forward, backward) = self.norm.factors::<T>(output.len() as f64);
+        scale(output, if inverse { backward } else { forward });
+        Ok(())
+    }
+
+    /// Compute the DHT of `input` into `output`.
+    pub fn dht(&mut self, input: &[T], output: &mut [T]) -> Result<(), FftError> {
+        self.transform(input, output, false)
+    }
+
+    /// Compute the DHT of `data` in place.
+    pub fn dht_inplace(&mut self, data: &mut [T]) -> Result<(), FftError> {
+        self.load(data);
+        self.finish(data, false)
+    }
+
+    /// Inverse DHT, normalized by `1/N` by default so that
+    /// `idht(dht(x)) == x`.
+    pub fn idht(&mut self, input: &[T], output: &mut [T]) -> Result<(), FftError> {
+        self.transform(input, output, true)
+    }
+
+    /// Inverse DHT of `data` in place, normalized by `1/N` by default.
+    pub fn idht_inplace(&mut self, data: &mut [T]) -> Result<(), FftError> {
+        self.load(data);
+        self.finish(data, true)
+    }
+
+    /// Batch DHT, transforming each buffer in place.
+    pub fn batch(&mut self, batches: &mut [Vec<T>]) -> Result<(), FftError> {
+        for batch in batches.iter_mut() {
+            self.dht_inplace(batch)?;
+        }
+        Ok(())
+    }
+
+    /// Batch inverse DHT, transforming each buffer in place.
+    pub fn batch_inverse(&mut self, batches: &mut [Vec<T>]) -> Result<(), FftError> {
+        for batch in batches.iter_mut() {
+            self.idht_inplace(batch)?;
+        }
+        Ok(())
+    }
+
+    /// Multi-channel DHT
+    pub fn multi_channel(&mut self, channels: &mut [Vec<T>]) -> Result<(), FftError> {
+        self.batch(channels)
+    }
+
+    /// Multi-channel inverse DHT
+    pub fn multi_channel_inverse(&mut self, channels: &mut [Vec<T>]) -> Result<(), FftError> {
+        self.batch_inverse(channels)
+    }
 }
 
-#[cfg(not(feature = "std"))]
-pub fn dht(input: &[f32]) -> Vec<f32> {
+/// Multiply `data` by `s`, skipping the pass when it is `1`.
+fn scale<T: RealFftNum>(data: &mut [T], s: T) {
+    if s == T::one() {
+        return;
+    }
+    for v in data.iter_mut() {
+        *v = *v * s;
+    }
+}
+
+/// Immutable, precomputed DHT of one length.
+///
+/// Even lengths run through a [`RealFftPlan`] and odd lengths through a
+/// complex [`SharedFftPlan`] of the same size.
+/// [`execute_with_scratch`](Self::execute_with_scratch) does not touch the
+/// allocator and [`inverse_with_scratch`](Self::inverse_with_scratch) undoes
+/// it under the plan's normalization. The plan is `Send + Sync`.
+pub struct HartleyPlan {
+    n: usize,
+    kernel: HartleyKernel,
+    norm: Normalization,
+    #[cfg(feature = "parallel")]
+    parallelism: Parallelism,
+}
+
+enum HartleyKernel {
+    Real(RealFftPlan<f32>),
+    Complex(SharedFftPlan<f32>),
+}
+
+impl HartleyPlan {
+    /// Precompute a DHT of length `n`.
+    pub fn new(n: usize) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        let kernel = if n.is_multiple_of(2) {
+            HartleyKernel::Real(RealFftPlan::new(n)?)
+        } else {
+            HartleyKernel::Complex(SharedFftPlan::new(n)?)
+        };
+        Ok(Self {
+            n,
+            kernel,
+            norm: Normalization::Backward,
+            #[cfg(feature = "parallel")]
+            parallelism: Parallelism::default(),
+        })
+    }
+
+    /// Use `norm` instead of the default [`Normalization::Backward`].
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Normalization applied by the forward and inverse transforms.
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Spread the buffers of batch APIs given this plan over `parallelism`
+    /// instead of Rayon's global pool.
+    #[cfg(feature = "parallel")]
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Workers used by the batch APIs this plan is passed to.
+    #[cfg(feature = "parallel")]
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+
+    /// Transform length.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Exact scratch length, in complex samples, for
+    /// [`execute_with_scratch`](Self::execute_with_scratch) and
+    /// [`inverse_with_scratch`](Self::inverse_with_scratch).
+    pub fn scratch_len(&self) -> usize {
+        match &self.kernel {
+            // n/2 + 1 spectrum bins
+            HartleyKernel::Real(rfft) => self.n / 2 + 1 + rfft.scratch_len(),
+            HartleyKernel::Complex(fft) => self.n + fft.scratch_len(),
+        }
+    }
+
+    /// Transform `input` into `output` (both of length [`size`](Self::size))
+    /// using `scratch` of at least [`scratch_len`](Self::scratch_len).
+    pub fn execute_with_scratch(
+        &self,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        self.run(false, input, output, scratch)
+    }
+
+    /// Inverse DHT, normalized by `1/N` by default so that it undoes
+    /// [`execute_with_scratch`](Self::execute_with_scratch).
+    pub fn inverse_with_scratch(
+        &self,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        self.run(true, input, output, scratch)
+    }
+
+    fn run(
+        &self,
+        inverse: bool,
+        input: &[f32],
+        output: &mut [f32],
+        scratch: &mut [Complex32],
+    ) -> Result<(), FftError> {
+        let n = self.n;
+        if input.len() != n || output.len() != n || scratch.len() < self.scratch_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        match &self.kernel {
+            HartleyKernel::Real(rfft) => {
+                let half = n / 2;
+                let (spec, rest) = scratch.split_at_mut(half + 1);
+                rfft.rfft_with_scratch(input, spec, rest)?;
+                for (k, out) in output.iter_mut().enumerate() {
+                    *out = if k <= half {
+                        spec[k].re - spec[k].im
+                    } else {
+                        // X[k] = conj(X[n - k]) for real input.
+                        spec[n - k].re + spec[n - k].im
+                    };
+                }
+            }
+            HartleyKernel::Complex(fft) => {
+                let (spec, rest) = scratch.split_at_mut(n);
+                for (c, &x) in spec.iter_mut().zip(input) {
+                    *c = Complex::new(x, 0.0);
+                }
+                fft.fft(spec, rest)?;
+                for (out, c) in output.iter_mut().zip(spec.iter()) {
+                    *out = c.re - c.im;
+                }
+            }
+        }
+        let (forward, backward) = self.norm.factors::<f32>(n as f64);
+        scale(output, if inverse { backward } else { forward });
+        Ok(())
+    }
+}
+
+/// Direct `O(n²)` summation, used when no FFT is available for the length.
+fn dht_direct(input: &[f32], output: &mut [f32]) {
     let n = input.len();
-    let mut output = vec![0.0; n];
     let factor = 2.0 * core::f32::consts::PI / n as f32;
     for (k, out) in output.iter_mut().enumerate() {
         let mut sum = 0.0;
         for (i, &x) in input.iter().enumerate() {
-            let angle = factor * (i * k) as f32;
+            let angle = factor * ((i * k) % n) as f32;
             let re = cosf(angle);
             let im = sinf(angle);
             sum += x * (re + im);
         }
         *out = sum;
     }
-    output
+}
+
+/// Transform with `planner`, falling back to direct summation only when the
+/// FFT backend cannot serve the length.
+fn dht_with(planner: &mut HartleyPlanner<f32>, data: &mut [f32]) -> Result<(), FftError> {
+    if data.is_empty() {
+        return Ok(());
+    }
+    match planner.dht_inplace(data) {
+        Err(FftError::NonPowerOfTwoNoStd) => {
+            let input = data.to_vec();
+            dht_direct(&input, data);
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Discrete Hartley Transform (DHT)
+pub fn dht(input: &[f32]) -> Result<Vec<f32>, FftError> {
+    let mut output = input.to_vec();
+    dht_with(&mut HartleyPlanner::new(), &mut output)?;
+    Ok(output)
+}
+
+/// Inverse DHT, normalized by `1/N`.
+pub fn idht(input: &[f32]) -> Result<Vec<f32>, FftError> {
+    let mut output = dht(input)?;
+    let s = Normalization::Backward
+        .factors::<f32>(output.len() as f64)
+        .1;
+    scale(&mut output, s);
+    Ok(output)
 }
 
 /// Batch DHT
-pub fn batch(batches: &mut [Vec<f32>]) {
+pub fn batch(batches: &mut [Vec<f32>]) -> Result<(), FftError> {
+    let mut planner = HartleyPlanner::new();
     for batch in batches.iter_mut() {
-        let out = dht(batch);
-        batch.copy_from_slice(&out);
+        dht_with(&mut planner, batch)?;
     }
+    Ok(())
 }
 /// Multi-channel DHT
-pub fn multi_channel(channels: &mut [Vec<f32>]) {
+pub fn multi_channel(channels: &mut [Vec<f32>]) -> Result<(), FftError> {
     batch(channels)
 }
 
+/// Parallel batch DHT: transform every buffer in place with `plan`,
+/// spreading contiguous runs of buffers over
+/// [`plan.parallelism()`](HartleyPlan::parallelism).
+///
+/// Each worker allocates its scratch once and every buffer must have the
+/// plan's length. The output is bit-identical to running
+/// [`HartleyPlan::execute_with_scratch`] on each buffer in turn, whatever
+/// the pool.
+#[cfg(feature = "parallel")]
+pub fn batch_parallel(plan: &HartleyPlan, batches: &mut [Vec<f32>]) -> Result<(), FftError> {
+    let par = plan.parallelism();
+    par.try_for_each_chunk_mut(
+        batches,
+        par.batch_chunk(batches.len()),
+        || {
+            (
+                alloc::vec![0.0; plan.size()],
+                alloc::vec![Complex32::zero(); plan.scratch_len()],
+            )
+        },
+        |(out, scratch), _, run| {
+            for batch in run {
+                plan.execute_with_scratch(batch, out, scratch)?;
+                batch.copy_from_slice(out);
+            }
+            Ok(())
+        },
+    )
+}
+
 #[cfg(all(feature = "internal-tests", test))]
 mod tests {
     use super::*;
     #[test]
     fn test_dht_roundtrip() {
         let x = [1.0, 2.0, 3.0, 4.0];
-        let y = dht(&x);
-        let z = dht(&y);
+        let y = dht(&x).unwrap();
+        let z = dht(&y).unwrap();
         for (a, b) in x.iter().zip(z.iter()) {
             assert!((a - b / 4.0).abs() < 1e-5, "{} vs {}", a, b);
         }
@@ -73,10 +445,11 @@ mod tests {
 #[cfg(all(feature = "internal-tests", test))]
 mod batch_tests {
     use super::*;
+    use alloc::vec;
     #[test]
     fn test_dht_batch() {
         let mut batches = vec![vec![1.0, 2.0, 3.0, 4.0], vec![5.0, 6.0, 7.0, 8.0]];
-        batch(&mut batches);
+        batch(&mut batches).unwrap();
         assert_eq!(batches.len(), 2);
     }
 }
@@ -90,19 +463,19 @@ mod coverage_tests {
     #[test]
     fn test_dht_empty() {
         let x: [f32; 0] = [];
-        let y = dht(&x);
+        let y = dht(&x).unwrap();
         assert_eq!(y.len(), 0);
     }
     #[test]
     fn test_dht_single_element() {
         let x = [1.0];
-        let y = dht(&x);
+        let y = dht(&x).unwrap();
         assert_eq!(y.len(), 1);
     }
     #[test]
     fn test_dht_all_zeros() {
         let x = [0.0; 8];
-        let y = dht(&x);
+        let y = dht(&x).unwrap();
         for v in y {
             assert_eq!(v, 0.0);
         }
@@ -110,29 +483,128 @@ mod coverage_tests {
     #[test]
     fn test_dht_all_ones() {
         let x = [1.0; 8];
-        let y = dht(&x);
+        let y = dht(&x).unwrap();
         // DHT of all-ones is not guaranteed to be all nonzero; check at least one is nonzero
         assert!(y.iter().any(|&v| v.abs() > 0.0));
     }
     #[test]
     fn test_dht_roundtrip() {
         let x = [1.0, 2.0, 3.0, 4.0];
-        let y = dht(&x);
-        let z = dht(&y);
+        let y = dht(&x).unwrap();
+        let z = dht(&y).unwrap();
         for (a, b) in x.iter().zip(z.iter()) {
             assert!((a - b / 4.0).abs() < 1e-4);
         }
     }
+    #[test]
+    fn test_plan_roundtrip_even_and_odd() {
+        for n in [1, 6, 9, 16] {
+            let plan = HartleyPlan::new(n).unwrap();
+            let mut scratch = alloc::vec![Complex32::zero(); plan.scratch_len()];
+            let x: Vec<f32> = (0..n).map(|i| i as f32 - 2.5).collect();
+            let mut y = alloc::vec![0.0; n];
+            let mut z = alloc::vec![0.0; n];
+            plan.execute_with_scratch(&x, &mut y, &mut scratch).unwrap();
+            for (a, b) in y.iter().zip(dht(&x).unwrap()) {
+                assert!((a - b).abs() < 1e-4, "n = {n}: {a} vs {b}");
+            }
+            plan.inverse_with_scratch(&y, &mut z, &mut scratch).unwrap();
+            for (a, b) in x.iter().zip(z.iter()) {
+                assert!((a - b).abs() < 1e-4, "n = {n}: {a} vs {b}");
+            }
+        }
+    }
     proptest! {
         #[test]
         fn prop_dht_roundtrip(len in 2usize..16, ref signal in proptest::collection::vec(-1000.0f32..1000.0, 16)) {
             if len < 4 { return Ok(()); } // skip degenerate cases
             let x: Vec<f32> = signal.iter().take(len).cloned().collect();
-            let y = dht(&x);
-            let z = dht(&y);
+            let y = dht(&x).unwrap();
+            let z = dht(&y).unwrap();
             for (a, b) in x.iter().zip(z.iter()) {
                 prop_assert!((a - b / (len as f32)).abs() < 1e-1);
             }
         }
     }
 }
+
+#[cfg(all(feature = "internal-tests", test))]
+mod planner_tests {
+    use super::*;
+    use alloc::vec;
+
+    fn naive(input: &[f32]) -> Vec<f32> {
+        let mut out = vec![0.0; input.len()];
+        dht_direct(input, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_planner_matches_naive_even_and_odd() {
+        let mut planner = HartleyPlanner::new();
+        for &n in &[1usize, 2, 3, 4, 7, 8, 15, 16, 100, 256] {
+            let x: Vec<f32> = (0..n).map(|i| ((i * 5 + 1) % 9) as f32 - 4.0).collect();
+            let mut out = vec![0.0; n];
+            planner.dht(&x, &mut out).unwrap();
+            let expected = naive(&x);
+            for (a, b) in out.iter().zip(expected.iter()) {
+                assert!((a - b).abs() < 1e-3 * n as f32, "n={} {} vs {}", n, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_planner_inverse_roundtrip_f64() {
+        let mut planner = HartleyPlanner::<f64>::new();
+        let x: Vec<f64> = (0..30).map(|i| (i as f64 * 0.37).sin()).collect();
+        let mut data = x.clone();
+        planner.dht_inplace(&mut data).unwrap();
+        planner.idht_inplace(&mut data).unwrap();
+        for (a, b) in x.iter().zip(data.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_planner_batch_inverse() {
+        let mut planner = HartleyPlanner::new();
+        let orig = vec![
+            vec![1.0f32, 2.0, 3.0, 4.0, 5.0],
+            vec![5.0, 6.0, 7.0, 8.0, 9.0],
+        ];
+        let mut channels = orig.clone();
+        planner.multi_channel(&mut channels).unwrap();
+        planner.multi_channel_inverse(&mut channels).unwrap();
+        for (a, b) in orig.iter().flatten().zip(channels.iter().flatten()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_planner_errors() {
+        let mut planner = HartleyPlanner::<f32>::new();
+        assert_eq!(
+            planner.dht(&[1.0, 2.0], &mut [0.0; 3]).unwrap_err(),
+            FftError::MismatchedLengths
+        );
+        assert_eq!(
+            planner.dht_inplace(&mut []).unwrap_err(),
+            FftError::EmptyInput
+        );
+    }
+
+    #[test]
+    fn test_dht_inplace_stack() {
+        let x = [1.0f32, -2.0, 3.0, 0.5, 4.0, 0.0, -1.0, 2.0];
+        let mut buf = x;
+        dht_inplace_stack(&mut buf).unwrap();
+        for (a, b) in buf.iter().zip(naive(&x).iter()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+        let mut odd = [1.0f32; 6];
+        assert_eq!(
+            dht_inplace_stack(&mut odd).unwrap_err(),
+            FftError::NonPowerOfTwoNoStd
+        );
+    }
+}
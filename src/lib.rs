@@ -16,11 +16,15 @@
 //! ## Cargo Features
 //!
 //! - `std` (default): Enable standard library features
+//! - `alloc` (implied by `std`): Heap-backed planners and transforms; without it only
+//!   `fixed`, `stack`, `static_planner` and the stack-only `*_inplace_stack`/`*_stack`
+//!   helpers of `fft`, `rfft`, `dct`, `dst`, `hartley`, `wavelet` and `window` are available
 //! - `parallel`: Enable parallel processing with Rayon
 //! - `x86_64`: Enable x86_64 AVX2/FMA optimizations
 //! - `sse`: Enable SSE optimizations for x86_64 without AVX2
 //! - `aarch64`: Enable AArch64 SIMD optimizations
 //! - `wasm`: Enable WebAssembly SIMD optimizations
+//! - `f16`: `f16`/`bf16` storage types with `f32` compute (`half_precision` module)
 //!
 //! SIMD backends are also activated automatically when compiling with the
 //! appropriate `target-feature` flags (e.g., `-C target-feature=+avx2`).
@@ -64,12 +68,28 @@
 //! at your option.
 
 #![no_std]
+#[cfg(feature = "alloc")]
 extern crate alloc;
-#[cfg(feature = "std")]
+// Rayon needs `std`, so `parallel` builds can use it too.
+#[cfg(any(feature = "std", feature = "parallel"))]
 extern crate std;
 
+#[cfg(all(target_arch = "x86_64", feature = "avx512", feature = "alloc"))]
+mod avx512;
+/// Runtime FFT backend registry: list, force and inspect backends
+#[cfg(feature = "alloc")]
+pub mod backend;
+mod error;
+#[cfg(feature = "alloc")]
 pub mod fft;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::fft;
 mod fft_kernels;
+/// Stack-only `f32` helpers of the transform modules: all of `fft`, `rfft`,
+/// `dct`, `dst`, `hartley`, `wavelet` and `window` that builds without `alloc`.
+mod mcu;
+#[cfg(feature = "alloc")]
+mod mixed_radix;
 /// Real-input FFT helpers built on top of complex FFT routines
 /// for converting between real and complex domains.
 pub mod num;
@@ -77,75 +97,175 @@ pub mod num;
 ///
 /// Provides both scalar and SIMD-optimized FFT implementations.
 /// Supports complex and real input signals.
+#[cfg(feature = "alloc")]
 pub mod rfft;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::rfft;
+#[cfg(all(
+    feature = "alloc",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "wasm32"
+    )
+))]
+mod simd_f64;
+
+/// Four-step FFT
+///
+/// Bailey's cache-blocked algorithm for transforms far larger than the cache, optionally on Rayon.
+#[cfg(feature = "alloc")]
+pub mod four_step;
+
+/// Parallel execution
+///
+/// `Parallelism` handles choosing Rayon's global pool, a caller-owned pool or a custom `Executor` per plan.
+#[cfg(feature = "parallel")]
+pub mod parallel;
 
 /// N-dimensional FFT operations
 ///
 /// Multi-dimensional FFT implementations for image and volume processing.
+#[cfg(feature = "alloc")]
 pub mod ndfft;
 
+/// Fixed-point FFT
+///
+/// Q15/Q31 complex and real FFTs with block floating-point scaling for MCUs without an FPU.
+pub mod fixed;
+
+/// Generic const-size stack transforms
+///
+/// `T: Float` FFT, real FFT, DCT/DST and windows for any `N`, with compile-time twiddles.
+pub mod stack;
+
+/// Compile-time twiddle tables
+///
+/// `TwiddleTable<T, N>` and the `twiddles!` macro: const-evaluated factors for any `N`, in read-only data.
+pub mod twiddles;
+
+/// Heapless FFT planner
+///
+/// Const-capacity twiddle and scratch storage for complex FFT, RFFT and STFT frames without `alloc`.
+pub mod static_planner;
+
+/// Half-precision storage
+///
+/// `f16`/`bf16` samples and spectra with FFT, RFFT and STFT computed in `f32`.
+#[cfg(feature = "f16")]
+pub mod half_precision;
+
 /// Window functions for signal processing
 ///
-/// Common window functions including Hann, Hamming, Blackman, and Kaiser windows.
+/// Common window functions including Hann, Hamming, Blackman, and Kaiser windows,
+/// plus `WindowSpec` values and their spectral metrics.
+#[cfg(feature = "alloc")]
 pub mod window;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::window;
 
 /// Discrete Cosine Transform (DCT)
 ///
 /// DCT-I, DCT-II, DCT-III, and DCT-IV implementations for audio and image compression.
+#[cfg(feature = "alloc")]
 pub mod dct;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::dct;
 
 /// Discrete Sine Transform (DST)
 ///
 /// DST-I, DST-II, DST-III, and DST-IV implementations.
+#[cfg(feature = "alloc")]
 pub mod dst;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::dst;
 
 /// Discrete Hartley Transform (DHT)
 ///
 /// Real-valued alternative to FFT with similar properties.
+#[cfg(feature = "alloc")]
 pub mod hartley;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::hartley;
 
 /// Wavelet transforms
 ///
 /// Haar wavelet transform implementation for signal analysis.
+#[cfg(feature = "alloc")]
 pub mod wavelet;
+#[cfg(not(feature = "alloc"))]
+pub use mcu::wavelet;
 
 /// Goertzel algorithm
 ///
 /// Efficient single-frequency detection algorithm.
+#[cfg(feature = "alloc")]
 pub mod goertzel;
 
 /// Chirp Z-Transform (CZT)
 ///
 /// Arbitrary frequency resolution DFT implementation.
+#[cfg(feature = "alloc")]
 pub mod czt;
 
 /// Hilbert transform
 ///
 /// Analytic signal computation and phase analysis.
+#[cfg(feature = "alloc")]
 pub mod hilbert;
 
 /// Short-Time Fourier Transform (STFT)
 ///
 /// Streaming and batch STFT/ISTFT utilities.
+#[cfg(feature = "alloc")]
 pub mod stft;
 
 /// Cepstrum analysis
 ///
 /// Real cepstrum computation for signal analysis.
+#[cfg(feature = "alloc")]
 pub mod cepstrum;
 
+/// Convolution and correlation
+///
+/// Direct and FFT-based convolve/correlate plus streaming overlap-add and
+/// overlap-save FIR filters.
+#[cfg(feature = "alloc")]
+pub mod conv;
+
+/// Spectral density estimation
+///
+/// Periodogram, Welch, cross spectral density and coherence following
+/// `scipy.signal` conventions.
+#[cfg(feature = "alloc")]
+pub mod psd;
+
+/// Multitaper spectral estimation
+///
+/// DPSS (Slepian) tapers with their concentration ratios and a multitaper
+/// PSD with Thomson's adaptive weighting.
+#[cfg(feature = "alloc")]
+pub mod multitaper;
+
 /// Additional window functions
 ///
 /// Extended collection of window functions for specialized applications.
+#[cfg(feature = "alloc")]
 pub mod window_more;
 
-pub use fft::FftPlanner;
+pub use error::FftError;
+#[cfg(feature = "alloc")]
+pub use fft::{FftPlanner, Normalization, PlanMode};
 pub use num::{Complex, Complex32, Complex64, Float};
 
 #[cfg(feature = "std")]
 /// Visualisation helpers (requires `std`)
 pub mod visual;
 
+#[cfg(feature = "std")]
+/// Saving and restoring measured planner decisions (requires `std`)
+pub mod wisdom;
+
 #[cfg(feature = "std")]
 /// Media utilities such as song identification (requires `std`)
 pub mod media;
@@ -0,0 +1,384 @@
+//! Stack-only `f32` transforms for MCUs.
+//!
+//! The const-generic `*_inplace_stack` and `*_stack` helpers of [`fft`],
+//! [`rfft`], [`dct`], [`dst`], [`hartley`], [`wavelet`] and [`window`] live
+//! here so that they build without `alloc`. Each public module re-exports
+//! its helpers; without `alloc` the public module *is* the one below.
+//! Trigonometry goes through `libm` so nothing depends on `std`.
+
+/// Stack-only complex FFT.
+pub mod fft {
+    use core::f32::consts::PI;
+    use libm::sincosf;
+
+    pub use crate::error::FftError;
+    #[cfg(not(feature = "alloc"))]
+    pub use crate::num::Complex64;
+    pub use crate::num::{Complex, Complex32};
+
+    /// MCU/stack-only, const-generic, in-place FFT for power-of-two sizes (no heap, no alloc)
+    /// Note: All stack-only, const-generic APIs in this crate require the user to provide all output buffers.
+    /// This is a Rust limitation: you cannot allocate [T; N] for arbitrary N inside a function.
+    /// See dct2_inplace_stack, dst2_inplace_stack, haar_forward_inplace_stack, etc.
+    /// For `f64` or non-power-of-two `N`, use [`crate::stack::fft`].
+    pub fn fft_inplace_stack<const N: usize>(buf: &mut [Complex<f32>; N]) -> Result<(), FftError> {
+        if N == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if N == 1 {
+            return Ok(());
+        }
+        if N.count_ones() != 1 {
+            // Only power-of-two supported for stack-only
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        // Bit-reversal permutation
+        let mut j = 0;
+        for i in 1..N {
+            let mut bit = N >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j ^= bit;
+            if i < j {
+                buf.swap(i, j);
+            }
+        }
+        let mut len = 2;
+        while len <= N {
+            let ang = -2.0 * PI / (len as f32);
+            let (sin_step, cos_step) = sincosf(ang);
+            let mut i = 0;
+            while i < N {
+                let mut w_re = 1.0f32;
+                let mut w_im = 0.0f32;
+                for j in 0..(len / 2) {
+                    let w = Complex32::new(w_re, w_im);
+                    let u = buf[i + j];
+                    let v = buf[i + j + len / 2].mul(w);
+                    buf[i + j] = u.add(v);
+                    buf[i + j + len / 2] = u.sub(v);
+                    let tmp = w_re;
+                    w_re = w_re * cos_step - w_im * sin_step;
+                    w_im = w_im * cos_step + tmp * sin_step;
+                }
+                i += len;
+            }
+            len <<= 1;
+        }
+        Ok(())
+    }
+
+    /// MCU/stack-only, const-generic, in-place IFFT for power-of-two sizes (no heap, no alloc)
+    pub fn ifft_inplace_stack<const N: usize>(buf: &mut [Complex<f32>; N]) -> Result<(), FftError> {
+        if N == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if N == 1 {
+            return Ok(());
+        }
+        if N.count_ones() != 1 {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        // Conjugate
+        for c in buf.iter_mut() {
+            c.im = -c.im;
+        }
+        fft_inplace_stack(buf)?;
+        // Conjugate and scale
+        let scale = 1.0 / (N as f32);
+        for c in buf.iter_mut() {
+            c.im = -c.im;
+            c.re *= scale;
+            c.im *= scale;
+        }
+        Ok(())
+    }
+}
+
+/// Stack-only real FFT.
+pub mod rfft {
+    use super::fft::{fft_inplace_stack, ifft_inplace_stack, Complex32, FftError};
+
+    /// Perform a real-input FFT using only stack allocation.
+    ///
+    /// The output buffer must have length `N/2 + 1`.
+    pub fn rfft_stack<const N: usize, const M: usize>(
+        input: &[f32; N],
+        output: &mut [Complex32; M],
+    ) -> Result<(), FftError> {
+        if N == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if M != N / 2 + 1 {
+            return Err(FftError::MismatchedLengths);
+        }
+        let mut buf = [Complex32::new(0.0, 0.0); N];
+        for (b, &x) in buf.iter_mut().zip(input.iter()) {
+            *b = Complex32::new(x, 0.0);
+        }
+        fft_inplace_stack(&mut buf)?;
+        output[..(N / 2 + 1)].copy_from_slice(&buf[..(N / 2 + 1)]);
+        Ok(())
+    }
+
+    /// Perform an inverse real-input FFT using only stack allocation.
+    ///
+    /// The input buffer must have length `N/2 + 1`.
+    pub fn irfft_stack<const N: usize, const M: usize>(
+        input: &[Complex32; M],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if M != N / 2 + 1 {
+            return Err(FftError::MismatchedLengths);
+        }
+        let mut buf = [Complex32::new(0.0, 0.0); N];
+        buf[..(N / 2 + 1)].copy_from_slice(&input[..(N / 2 + 1)]);
+        for k in 1..N / 2 {
+            buf[N - k] = Complex32::new(input[k].re, -input[k].im);
+        }
+        ifft_inplace_stack(&mut buf)?;
+        for (o, &b) in output.iter_mut().zip(buf.iter()) {
+            *o = b.re;
+        }
+        Ok(())
+    }
+}
+
+/// Stack-only DCT.
+pub mod dct {
+    use crate::error::FftError;
+    use core::f32::consts::PI;
+    use libm::cosf;
+
+    /// MCU/stack-only, const-generic, in-place DCT-I for power-of-two sizes (no heap, no alloc).
+    ///
+    /// `N` must be a positive even power of two. Returns an error otherwise.
+    pub fn dct1_inplace_stack<const N: usize>(
+        input: &[f32; N],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N < 2 || !N.is_multiple_of(2) || !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        let factor = PI / (N as f32 - 1.0);
+        for (k, out) in output.iter_mut().enumerate() {
+            let mut sum = input[0]
+                + if k.is_multiple_of(2) {
+                    input[N - 1]
+                } else {
+                    -input[N - 1]
+                };
+            for (i, &x) in input.iter().take(N - 1).enumerate().skip(1) {
+                sum += 2.0 * x * cosf(factor * i as f32 * k as f32);
+            }
+            *out = sum;
+        }
+        Ok(())
+    }
+
+    /// MCU/stack-only, const-generic, in-place DCT-II for power-of-two sizes (no heap, no alloc).
+    ///
+    /// `N` must be a positive even power of two. Returns an error otherwise.
+    pub fn dct2_inplace_stack<const N: usize>(
+        input: &[f32; N],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        let factor = PI / N as f32;
+        for (k, out) in output.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            for (i, &x) in input.iter().enumerate() {
+                sum += x * cosf(factor * (i as f32 + 0.5) * k as f32);
+            }
+            *out = sum;
+        }
+        Ok(())
+    }
+}
+
+/// Stack-only DST.
+pub mod dst {
+    use crate::error::FftError;
+    use core::f32::consts::PI;
+    use libm::sinf;
+
+    /// MCU/stack-only, const-generic, in-place DST-II for power-of-two sizes (no heap, no alloc)
+    ///
+    /// `N` must be a positive even power of two. Returns an error otherwise.
+    pub fn dst2_inplace_stack<const N: usize>(
+        input: &[f32; N],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        let factor = PI / N as f32;
+        for (k, out) in output.iter_mut().enumerate().take(N) {
+            let mut sum = 0.0;
+            for (i, &x) in input.iter().enumerate().take(N) {
+                sum += x * sinf(factor * (i as f32 + 0.5) * (k as f32 + 1.0));
+            }
+            *out = sum;
+        }
+        Ok(())
+    }
+
+    /// MCU/stack-only, FFT-based, in-place DST-II using only stack memory.
+    ///
+    /// `N` must be a positive power of two. Returns an error otherwise.
+    pub fn dst2_inplace_stack_fft<const N: usize>(
+        input: &[f32; N],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        let factor = PI / N as f32;
+        for (k, out) in output.iter_mut().enumerate() {
+            let mut sum = 0.0;
+            for (i, &x) in input.iter().enumerate() {
+                sum += x * sinf(factor * (i as f32 + 0.5) * (k as f32 + 1.0));
+            }
+            *out = sum;
+        }
+        Ok(())
+    }
+
+    /// MCU/stack-only, FFT-based, in-place DST-III using only stack memory.
+    ///
+    /// `N` must be a positive even power of two. Returns an error otherwise.
+    pub fn dst3_inplace_stack_fft<const N: usize>(
+        input: &[f32; N],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        // Direct computation using stack memory to ensure parity with heap-based version.
+        let factor = PI / N as f32;
+        for (k, out) in output.iter_mut().enumerate() {
+            let half = input[N - 1] / 2.0;
+            let mut sum = if k % 2 == 0 { half } else { -half };
+            for (n, &x) in input.iter().take(N - 1).enumerate() {
+                sum += x * sinf(factor * (k as f32 + 0.5) * (n + 1) as f32);
+            }
+            *out = sum;
+        }
+        Ok(())
+    }
+
+    /// MCU/stack-only, const-generic, in-place DST-IV for power-of-two sizes (no heap, no alloc)
+    ///
+    /// `N` must be a positive even power of two. Returns an error otherwise.
+    pub fn dst4_inplace_stack<const N: usize>(
+        input: &[f32; N],
+        output: &mut [f32; N],
+    ) -> Result<(), FftError> {
+        if N == 0 || !N.is_multiple_of(2) || !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        let factor = PI / N as f32;
+        for (k, out) in output.iter_mut().enumerate().take(N) {
+            let mut sum = 0.0;
+            for (i, &x) in input.iter().enumerate().take(N) {
+                sum += x * sinf(factor * (i as f32 + 0.5) * (k as f32 + 0.5));
+            }
+            *out = sum;
+        }
+        Ok(())
+    }
+}
+
+/// Stack-only discrete Hartley transform.
+pub mod hartley {
+    use super::fft::{fft_inplace_stack, Complex32, FftError};
+
+    /// MCU/stack-only, const-generic, in-place DHT for power-of-two sizes (no heap, no alloc).
+    ///
+    /// Uses an `N`-element complex buffer on the stack. `N` must be a power of
+    /// two; returns an error otherwise.
+    pub fn dht_inplace_stack<const N: usize>(buf: &mut [f32; N]) -> Result<(), FftError> {
+        let mut tmp = [Complex32::new(0.0, 0.0); N];
+        for (c, &x) in tmp.iter_mut().zip(buf.iter()) {
+            *c = Complex32::new(x, 0.0);
+        }
+        fft_inplace_stack(&mut tmp)?;
+        for (out, c) in buf.iter_mut().zip(tmp.iter()) {
+            *out = c.re - c.im;
+        }
+        Ok(())
+    }
+}
+
+/// Stack-only Haar wavelet.
+pub mod wavelet {
+    /// MCU/stack-only, const-generic, in-place Haar wavelet forward (N must be even, no heap)
+    /// Output buffers avg and diff must be of length N/2.
+    pub fn haar_forward_inplace_stack<const N: usize>(
+        input: &[f32; N],
+        avg: &mut [f32],
+        diff: &mut [f32],
+    ) {
+        let n = N / 2;
+        assert!(
+            avg.len() == n && diff.len() == n,
+            "Output buffers must be of length N/2"
+        );
+        for i in 0..n {
+            avg[i] = (input[2 * i] + input[2 * i + 1]) / 2.0;
+            diff[i] = (input[2 * i] - input[2 * i + 1]) / 2.0;
+        }
+    }
+
+    /// MCU/stack-only, const-generic, in-place Haar wavelet inverse (N must be even, no heap)
+    /// Output buffer out must be of length 2*N.
+    pub fn haar_inverse_inplace_stack<const N: usize>(avg: &[f32], diff: &[f32], out: &mut [f32]) {
+        let n = avg.len();
+        assert!(
+            diff.len() == n && out.len() == 2 * n,
+            "Output buffer must be of length 2*N"
+        );
+        for i in 0..n {
+            out[2 * i] = avg[i] + diff[i];
+            out[2 * i + 1] = avg[i] - diff[i];
+        }
+    }
+}
+
+/// Stack-only window functions.
+pub mod window {
+    use core::f32::consts::PI;
+    use libm::cosf;
+
+    /// MCU/stack-only, const-generic, in-place Hann window (no heap)
+    pub fn hann_inplace_stack<const N: usize>(out: &mut [f32; N]) {
+        for (i, x) in out.iter_mut().enumerate() {
+            *x = 0.5 - 0.5 * cosf(2.0 * PI * i as f32 / N as f32);
+        }
+    }
+
+    /// MCU/stack-only, const-generic, in-place Hamming window (no heap)
+    pub fn hamming_inplace_stack<const N: usize>(out: &mut [f32; N]) {
+        for (i, x) in out.iter_mut().enumerate() {
+            *x = 0.54 - 0.46 * cosf(2.0 * PI * i as f32 / N as f32);
+        }
+    }
+
+    /// MCU/stack-only, const-generic, in-place Blackman window (no heap)
+    pub fn blackman_inplace_stack<const N: usize>(out: &mut [f32; N]) {
+        let a0 = 0.42;
+        let a1 = 0.5;
+        let a2 = 0.08;
+        for (i, out_val) in out.iter_mut().enumerate() {
+            let x = i as f32 / N as f32;
+            *out_val = a0 - a1 * cosf(2.0 * PI * x) + a2 * cosf(4.0 * PI * x);
+        }
+    }
+}
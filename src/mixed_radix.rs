@@ -0,0 +1,395 @@
+//! Mixed-radix FFT for arbitrary lengths.
+//!
+//! Lengths are factored into radix-4, radix-2 and the small odd primes 3, 5,
+//! 7, 11 and 13, each of which has a hand-written butterfly in
+//! `fft_kernels`.  Any remaining prime factor is handled with
+//! [Rader's algorithm](https://en.wikipedia.org/wiki/Rader%27s_FFT_algorithm),
+//! which turns a length-`p` DFT into a cyclic convolution of length `p - 1`.
+//! When `p - 1` is itself smooth the convolution runs at that exact length;
+//! otherwise it is zero padded to a power of two.
+//!
+//! A [`MixedRadixPlan`] is immutable once built; all temporary storage is
+//! supplied by the caller so a cached plan never allocates while executing.
+
+use crate::fft_kernels::{fft11, fft13, fft2, fft3, fft4, fft5, fft7};
+use crate::num::{Complex, Float};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Largest radix with a dedicated butterfly.
+const MAX_RADIX: usize = 13;
+
+/// Smallest prime factor of `n > 1`.
+fn smallest_factor(n: usize) -> usize {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+    let mut f = 3;
+    while f * f <= n {
+        if n.is_multiple_of(f) {
+            return f;
+        }
+        f += 2;
+    }
+    n
+}
+
+/// Prime factors of `n` in ascending order, with multiplicity.
+pub(crate) fn prime_factors(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    while n > 1 {
+        let f = smallest_factor(n);
+        factors.push(f);
+        n /= f;
+    }
+    factors
+}
+
+/// `true` if every prime factor of `n` has a dedicated butterfly.
+pub(crate) fn is_smooth(mut n: usize) -> bool {
+    for p in [2, 3, 5, 7, 11, 13] {
+        while n.is_multiple_of(p) {
+            n /= p;
+        }
+    }
+    n == 1
+}
+
+/// `true` if every prime factor of `n` can be handled without zero padding,
+/// either by a dedicated butterfly or by Rader's algorithm on a smooth
+/// `p - 1`.  Checked once per length at plan time, when the planner picks
+/// between mixed radix and Bluestein.
+#[cfg(feature = "std")]
+pub(crate) fn is_rader_smooth(mut n: usize) -> bool {
+    while n > 1 {
+        let p = smallest_factor(n);
+        if p > MAX_RADIX && !is_smooth(p - 1) {
+            return false;
+        }
+        n /= p;
+    }
+    true
+}
+
+/// Split `n` into the radices used by the recursion, outermost first.
+fn radices(n: usize) -> Vec<usize> {
+    let factors = prime_factors(n);
+    let twos = factors.iter().filter(|&&f| f == 2).count();
+    let mut out = vec![4; twos / 2];
+    if twos % 2 == 1 {
+        out.push(2);
+    }
+    out.extend(factors.into_iter().filter(|&f| f != 2));
+    out
+}
+
+/// `exp(-2πi k / n)` for `k = 0..len`, evaluated in `f64`.
+fn twiddle_table<T: Float>(n: usize, len: usize) -> Vec<Complex<T>> {
+    (0..len)
+        .map(|k| {
+            let angle = -2.0 * core::f64::consts::PI * (k as f64) / (n as f64);
+            let (s, c) = libm::sincos(angle);
+            Complex::new(T::from_f64(c), T::from_f64(s))
+        })
+        .collect()
+}
+
+fn mod_pow(base: usize, mut exp: usize, modulus: usize) -> usize {
+    let m = modulus as u128;
+    let mut result = 1u128;
+    let mut b = base as u128 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * b % m;
+        }
+        b = b * b % m;
+        exp >>= 1;
+    }
+    result as usize
+}
+
+/// Smallest primitive root modulo the prime `p`.
+fn primitive_root(p: usize) -> usize {
+    let mut divisors = prime_factors(p - 1);
+    divisors.dedup();
+    (2..p)
+        .find(|&g| divisors.iter().all(|&q| mod_pow(g, (p - 1) / q, p) != 1))
+        .expect("every prime has a primitive root")
+}
+
+/// Rader's algorithm for a single prime length `p`.
+struct RaderPlan<T: Float> {
+    p: usize,
+    /// `g^q mod p` for `q = 0..p-1`.
+    gather: Vec<usize>,
+    /// `g^-q mod p` for `q = 0..p-1`.
+    scatter: Vec<usize>,
+    /// FFT of the twiddle sequence `ω^(g^-q)`, pre-scaled by `1/len`.
+    kernel: Vec<Complex<T>>,
+    /// Convolution FFT; its length is `p - 1` or a padded power of two.
+    inner: Box<MixedRadixPlan<T>>,
+}
+
+impl<T: Float> RaderPlan<T> {
+    fn new(p: usize) -> Self {
+        let g = primitive_root(p);
+        let g_inv = mod_pow(g, p - 2, p);
+        let mut gather = Vec::with_capacity(p - 1);
+        let mut scatter = Vec::with_capacity(p - 1);
+        let (mut fwd, mut inv) = (1usize, 1usize);
+        for _ in 0..p - 1 {
+            gather.push(fwd);
+            scatter.push(inv);
+            fwd = fwd * g % p;
+            inv = inv * g_inv % p;
+        }
+        let len = if is_smooth(p - 1) {
+            p - 1
+        } else {
+            (2 * p - 3).next_power_of_two()
+        };
+        let inner = Box::new(MixedRadixPlan::new(len));
+        let roots = twiddle_table::<T>(p, p);
+        let mut kernel = vec![Complex::zero(); len];
+        for (q, &idx) in scatter.iter().enumerate() {
+            kernel[q] = roots[idx];
+        }
+        // Wrap the tail so the linear convolution of the padded buffers is
+        // cyclic over the first `p - 1` outputs.
+        if len > p - 1 {
+            for q in 1..p - 1 {
+                kernel[len - q] = kernel[p - 1 - q];
+            }
+        }
+        let mut scratch = vec![Complex::zero(); inner.scratch_len()];
+        inner.execute(&mut kernel, &mut scratch);
+        let scale = T::one() / T::from_f64(len as f64);
+        for k in &mut kernel {
+            *k = Complex::new(k.re * scale, k.im * scale);
+        }
+        Self {
+            p,
+            gather,
+            scatter,
+            kernel,
+            inner,
+        }
+    }
+
+    fn scratch_len(&self) -> usize {
+        self.kernel.len() + self.inner.scratch_len()
+    }
+
+    /// Transform `x` (length `p`) in place.
+    fn execute(&self, x: &mut [Complex<T>], work: &mut [Complex<T>]) {
+        let (buf, inner_scratch) = work.split_at_mut(self.kernel.len());
+        let x0 = x[0];
+        let mut sum = x0;
+        for (b, &idx) in buf.iter_mut().zip(&self.gather) {
+            *b = x[idx];
+            sum = sum.add(*b);
+        }
+        for b in &mut buf[self.p - 1..] {
+            *b = Complex::zero();
+        }
+        self.inner.execute(buf, inner_scratch);
+        // Inverse FFT through conjugation: ifft(y) = conj(fft(conj(y))).
+        for (b, &k) in buf.iter_mut().zip(&self.kernel) {
+            let v = b.mul(k);
+            *b = Complex::new(v.re, -v.im);
+        }
+        self.inner.execute(buf, inner_scratch);
+        x[0] = sum;
+        for (b, &idx) in buf.iter().zip(&self.scatter) {
+            x[idx] = Complex::new(x0.re + b.re, x0.im - b.im);
+        }
+    }
+}
+
+/// Precomputed decimation-in-time FFT for an arbitrary length.
+pub(crate) struct MixedRadixPlan<T: Float> {
+    n: usize,
+    radices: Vec<usize>,
+    /// `exp(-2πi k / n)` for `k = 0..n`.
+    twiddles: Vec<Complex<T>>,
+    rader: Vec<RaderPlan<T>>,
+    scratch_len: usize,
+}
+
+impl<T: Float> MixedRadixPlan<T> {
+    pub(crate) fn new(n: usize) -> Self {
+        assert!(n > 0, "mixed-radix plan requires a non-zero length");
+        let radices = radices(n);
+        let mut rader: Vec<RaderPlan<T>> = Vec::new();
+        for &p in &radices {
+            if p > MAX_RADIX && !rader.iter().any(|r| r.p == p) {
+                rader.push(RaderPlan::new(p));
+            }
+        }
+        let work = rader
+            .iter()
+            .map(|r| r.p + r.scratch_len())
+            .max()
+            .unwrap_or(0);
+        Self {
+            n,
+            radices,
+            twiddles: twiddle_table(n, n),
+            rader,
+            scratch_len: n + work,
+        }
+    }
+
+    /// Number of scratch elements required by [`execute`](Self::execute).
+    pub(crate) fn scratch_len(&self) -> usize {
+        self.scratch_len
+    }
+
+    /// Forward FFT of `data` in place. `scratch` must hold at least
+    /// [`scratch_len`](Self::scratch_len) elements.
+    pub(crate) fn execute(&self, data: &mut [Complex<T>], scratch: &mut [Complex<T>]) {
+        debug_assert_eq!(data.len(), self.n);
+        if self.n == 1 {
+            return;
+        }
+        let (copy, work) = scratch[..self.scratch_len].split_at_mut(self.n);
+        copy.copy_from_slice(data);
+        self.recurse(copy, 1, data, 0, work);
+    }
+
+    fn recurse(
+        &self,
+        src: &[Complex<T>],
+        stride: usize,
+        dst: &mut [Complex<T>],
+        level: usize,
+        work: &mut [Complex<T>],
+    ) {
+        let len = dst.len();
+        let p = self.radices[level];
+        let m = len / p;
+        if m == 1 {
+            for (q, d) in dst.iter_mut().enumerate() {
+                *d = src[q * stride];
+            }
+            self.butterfly(dst, work);
+            return;
+        }
+        for q in 0..p {
+            self.recurse(
+                &src[q * stride..],
+                stride * p,
+                &mut dst[q * m..(q + 1) * m],
+                level + 1,
+                work,
+            );
+        }
+        let step = self.n / len;
+        if p <= MAX_RADIX {
+            let mut x = [Complex::zero(); MAX_RADIX];
+            for k in 0..m {
+                self.combine(dst, &mut x[..p], k, m, step, work);
+            }
+        } else {
+            let (x, rest) = work.split_at_mut(p);
+            for k in 0..m {
+                self.combine(dst, x, k, m, step, rest);
+            }
+        }
+    }
+
+    /// Twiddle, transform and write back column `k` of a `p × m` stage.
+    #[inline(always)]
+    fn combine(
+        &self,
+        dst: &mut [Complex<T>],
+        x: &mut [Complex<T>],
+        k: usize,
+        m: usize,
+        step: usize,
+        work: &mut [Complex<T>],
+    ) {
+        x[0] = dst[k];
+        for q in 1..x.len() {
+            x[q] = dst[q * m + k].mul(self.twiddles[q * k * step]);
+        }
+        self.butterfly(x, work);
+        for (q, &v) in x.iter().enumerate() {
+            dst[q * m + k] = v;
+        }
+    }
+
+    #[inline(always)]
+    fn butterfly(&self, x: &mut [Complex<T>], work: &mut [Complex<T>]) {
+        match x.len() {
+            2 => fft2(x),
+            3 => fft3(x),
+            4 => fft4(x),
+            5 => fft5(x),
+            7 => fft7(x),
+            11 => fft11(x),
+            13 => fft13(x),
+            p => self
+                .rader
+                .iter()
+                .find(|r| r.p == p)
+                .expect("Rader plan for every large prime radix")
+                .execute(x, work),
+        }
+    }
+}
+
+#[cfg(all(feature = "internal-tests", test))]
+mod tests {
+    use super::*;
+    use crate::num::Complex64;
+
+    fn naive(input: &[Complex64]) -> Vec<Complex64> {
+        let n = input.len();
+        (0..n)
+            .map(|k| {
+                input
+                    .iter()
+                    .enumerate()
+                    .fold(Complex64::zero(), |acc, (j, &x)| {
+                        let angle = -2.0 * core::f64::consts::PI * ((j * k) % n) as f64 / n as f64;
+                        acc.add(x.mul(Complex64::expi(angle)))
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_naive_dft() {
+        // Covers every butterfly, smooth Rader (17, 23, 31) including a
+        // repeated large prime (23 * 23) and padded Rader (47, 59).
+        for &n in &[
+            1, 2, 3, 5, 6, 7, 11, 12, 13, 17, 23, 31, 45, 47, 59, 77, 143, 180, 529, 1000,
+        ] {
+            let input: Vec<Complex64> = (0..n)
+                .map(|i| Complex64::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+                .collect();
+            let plan = MixedRadixPlan::<f64>::new(n);
+            let mut data = input.clone();
+            let mut scratch = vec![Complex64::zero(); plan.scratch_len()];
+            plan.execute(&mut data, &mut scratch);
+            for (a, b) in data.iter().zip(naive(&input)) {
+                assert!((a.re - b.re).abs() < 1e-9 * n as f64, "n={n}");
+                assert!((a.im - b.im).abs() < 1e-9 * n as f64, "n={n}");
+            }
+        }
+    }
+
+    #[test]
+    fn factorization_helpers() {
+        assert_eq!(prime_factors(360), vec![2, 2, 2, 3, 3, 5]);
+        assert_eq!(radices(360), vec![4, 2, 3, 3, 5]);
+        assert!(is_smooth(44100));
+        assert!(!is_smooth(17));
+        assert!(is_rader_smooth(17 * 31));
+        assert!(is_rader_smooth(23));
+        assert!(!is_rader_smooth(47));
+        assert_eq!(primitive_root(23), 5);
+    }
+}
@@ -0,0 +1,451 @@
+//! Multitaper spectral estimation with DPSS (Slepian) tapers.
+//!
+//! [`dpss`] computes the discrete prolate spheroidal sequences of a length
+//! and time-bandwidth product `NW`: the `K` orthonormal tapers whose
+//! energy is most concentrated in the band `[-W, W]`, `W = NW/len`. The
+//! tapers are the leading eigenvectors of the symmetric tridiagonal matrix
+//! that commutes with the sinc kernel, found by bisection and inverse
+//! iteration, and carry `scipy.signal.windows.dpss`'s sign convention.
+//!
+//! [`Multitaper`] averages the periodograms of a signal under each taper.
+//! Its default [`Weighting::Adaptive`] is Thomson's iterative weighting,
+//! which discounts a taper at the frequencies where its leakage from the
+//! rest of the spectrum would dominate. The estimator owns one
+//! [`RealFftPlan`] and its buffers, reused across tapers and calls, and
+//! takes the same [`PsdConfig`] as [`psd`](crate::psd).
+//!
+//! ```
+//! use kofft::multitaper::{self, Multitaper};
+//! use kofft::psd::PsdConfig;
+//!
+//! // 5 s of a 12 Hz rhythm at 256 Hz
+//! let x: Vec<f64> = (0..1280)
+//!     .map(|i| (2.0 * core::f64::consts::PI * 12.0 * i as f64 / 256.0).sin())
+//!     .collect();
+//! let config = PsdConfig { fs: 256.0, ..Default::default() };
+//! let mut mt = Multitaper::new(x.len(), 4.0, 7, &config).unwrap();
+//! let mut pxx = vec![0.0; mt.output_len()];
+//! mt.estimate(&x, &mut pxx).unwrap();
+//! assert_eq!(mt.frequencies()[60], 12.0);
+//! assert!(pxx[60] > 1e6 * pxx[200]);
+//!
+//! // 2·NW - 1 = 4 tapers; the fourth already leaks 5% of its energy
+//! let (tapers, ratios) = multitaper::dpss(512, 2.5, 4).unwrap();
+//! assert_eq!(tapers.len(), 4);
+//! assert!(ratios[..3].iter().all(|&r| r > 0.99) && ratios[3] < 0.96);
+//! ```
+
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::conv::{self, Mode};
+use crate::fft::{Complex, FftError, Float};
+use crate::psd::{self, PsdConfig, Scaling, Sides};
+use crate::rfft::RealFftPlan;
+use core::f64::consts::PI;
+use libm::{cos, fabs, sin, sqrt};
+
+/// Upper bound on the adaptive weighting's iterations per frequency.
+const ADAPTIVE_MAX_ITER: usize = 100;
+/// Relative change at which the adaptive weighting has converged.
+const ADAPTIVE_TOL: f64 = 1e-10;
+
+/// `count` DPSS tapers of `len` samples for time-bandwidth product `nw`,
+/// with their concentration ratios: the fraction of each taper's energy
+/// inside `[-nw/len, nw/len]` cycles per sample.
+///
+/// Tapers have unit energy and come in decreasing concentration; roughly
+/// the first `2·nw - 1` are well concentrated. Returns
+/// [`FftError::EmptyInput`] if `len` or `count` is zero and
+/// [`FftError::InvalidValue`] if `count > len` or `nw` is not in
+/// `(0, len/2)`.
+pub fn dpss(len: usize, nw: f64, count: usize) -> Result<(Vec<Vec<f64>>, Vec<f64>), FftError> {
+    if len == 0 || count == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if count > len || !(nw > 0.0 && nw < len as f64 / 2.0) {
+        return Err(FftError::InvalidValue);
+    }
+    let w = nw / len as f64;
+    // the tridiagonal matrix commuting with the sinc kernel; its largest
+    // eigenvalues belong to the most concentrated sequences
+    let c = cos(2.0 * PI * w);
+    let diag: Vec<f64> = (0..len)
+        .map(|i| {
+            let t = (len as f64 - 1.0 - 2.0 * i as f64) / 2.0;
+            t * t * c
+        })
+        .collect();
+    let off: Vec<f64> = (1..len)
+        .map(|i| i as f64 * (len - i) as f64 / 2.0)
+        .collect();
+
+    let mut tapers: Vec<Vec<f64>> = Vec::with_capacity(count);
+    for k in 0..count {
+        let lambda = eigenvalue(&diag, &off, len - 1 - k);
+        let mut v = eigenvector(&diag, &off, lambda, k);
+        // eigenvalues are simple, but clean up what rounding leaves
+        for prev in &tapers {
+            let dot: f64 = prev.iter().zip(&v).map(|(a, b)| a * b).sum();
+            for (x, p) in v.iter_mut().zip(prev) {
+                *x -= dot * p;
+            }
+        }
+        normalize(&mut v);
+        // scipy's signs: symmetric tapers sum positive, antisymmetric ones
+        // start positive
+        let flip = if k % 2 == 0 {
+            v.iter().sum::<f64>() < 0.0
+        } else {
+            let thresh = (1.0 / len as f64).max(1e-7);
+            let lead = v.iter().find(|&&x| x * x > thresh);
+            lead.or(v.iter().find(|&&x| x != 0.0))
+                .is_some_and(|&x| x < 0.0)
+        };
+        if flip {
+            v.iter_mut().for_each(|x| *x = -*x);
+        }
+        tapers.push(v);
+    }
+
+    // λ = Σ_m Σ_n v[m]·v[n]·sin(2πW(m-n))/(π(m-n)), from the autocorrelation
+    let mut ratios = Vec::with_capacity(count);
+    for v in &tapers {
+        let r = conv::correlate(v, v, Mode::Full)?;
+        let lambda = 2.0 * w * r[len - 1]
+            + (1..len)
+                .map(|lag| {
+                    2.0 * r[len - 1 + lag] * sin(2.0 * PI * w * lag as f64) / (PI * lag as f64)
+                })
+                .sum::<f64>();
+        ratios.push(lambda);
+    }
+    Ok((tapers, ratios))
+}
+
+/// How [`Multitaper`] combines the tapers' eigenspectra.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Weighting {
+    /// Plain mean.
+    Unity,
+    /// Mean weighted by concentration ratio.
+    Eigenvalue,
+    /// Thomson's adaptive weights, iterated per frequency.
+    #[default]
+    Adaptive,
+}
+
+/// Multitaper power spectral density estimator for signals of a fixed
+/// length.
+///
+/// `config` supplies the sampling rate, FFT length, detrending and sides;
+/// the overlap is ignored since the whole signal is one segment. Only
+/// [`Scaling::Density`] is defined for a taper set. `nfft` must be even and
+/// defaults to the signal length, rounded up to even.
+pub struct Multitaper<T: Float> {
+    len: usize,
+    config: PsdConfig,
+    weighting: Weighting,
+    tapers: Vec<Vec<T>>,
+    ratios: Vec<T>,
+    plan: RealFftPlan<T>,
+    segment: Vec<T>,
+    spectrum: Vec<Complex<T>>,
+    scratch: Vec<Complex<T>>,
+    /// `|FFT(x·taper)|²` per taper, over the non-negative frequencies.
+    eigenspectra: Vec<Vec<T>>,
+}
+
+impl<T: Float> Multitaper<T> {
+    /// Estimator for `len`-sample signals using `count` DPSS tapers of
+    /// time-bandwidth product `nw`; see [`dpss`].
+    ///
+    /// Returns [`FftError::InvalidValue`] for an odd or too short `nfft`, a
+    /// non-positive `fs` or [`Scaling::Spectrum`], besides [`dpss`]'s
+    /// errors.
+    pub fn new(len: usize, nw: f64, count: usize, config: &PsdConfig) -> Result<Self, FftError> {
+        let (tapers, ratios) = dpss(len, nw, count)?;
+        let nfft = config.nfft.unwrap_or(len + len % 2);
+        if nfft < len
+            || config.scaling != Scaling::Density
+            || !(config.fs > 0.0 && config.fs.is_finite())
+        {
+            return Err(FftError::InvalidValue);
+        }
+        let plan = RealFftPlan::new(nfft)?;
+        let bins = plan.spectrum_len();
+        Ok(Self {
+            len,
+            config: *config,
+            weighting: Weighting::default(),
+            tapers: tapers
+                .iter()
+                .map(|v| v.iter().map(|&x| T::from_f64(x)).collect())
+                .collect(),
+            ratios: ratios.iter().map(|&r| T::from_f64(r)).collect(),
+            segment: vec![T::zero(); nfft],
+            spectrum: vec![Complex::zero(); bins],
+            scratch: vec![Complex::zero(); plan.scratch_len()],
+            eigenspectra: vec![vec![T::zero(); bins]; count],
+            plan,
+        })
+    }
+
+    /// Combine the eigenspectra with `weighting` instead of the default
+    /// [`Weighting::Adaptive`].
+    pub fn with_weighting(mut self, weighting: Weighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    /// Signal length.
+    pub fn signal_len(&self) -> usize {
+        self.len
+    }
+
+    /// FFT length.
+    pub fn nfft(&self) -> usize {
+        self.plan.size()
+    }
+
+    /// Length of the estimate: `nfft/2 + 1` one-sided, `nfft` two-sided.
+    pub fn output_len(&self) -> usize {
+        match self.config.sides {
+            Sides::OneSided => self.plan.spectrum_len(),
+            Sides::TwoSided => self.plan.size(),
+        }
+    }
+
+    /// The tapers, in decreasing concentration.
+    pub fn tapers(&self) -> &[Vec<T>] {
+        &self.tapers
+    }
+
+    /// The tapers' concentration ratios.
+    pub fn ratios(&self) -> &[T] {
+        &self.ratios
+    }
+
+    /// Frequency of each output bin, laid out like [`psd::welch`]'s.
+    pub fn frequencies(&self) -> Vec<T> {
+        psd::frequencies(self.plan.size(), &self.config)
+    }
+
+    /// Power spectral density of `x` (`len` samples) into `out`
+    /// ([`output_len`](Self::output_len) bins). Does not allocate.
+    pub fn estimate(&mut self, x: &[T], out: &mut [T]) -> Result<(), FftError> {
+        if x.len() != self.len || out.len() != self.output_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        let (offset, slope) = psd::trend(x, self.config.detrend);
+        let trend = |i: usize| offset + slope * T::from_f64(i as f64);
+        let variance = x.iter().enumerate().fold(T::zero(), |acc, (i, &v)| {
+            acc + (v - trend(i)) * (v - trend(i))
+        }) / T::from_f64(self.len as f64);
+
+        for (taper, eigen) in self.tapers.iter().zip(&mut self.eigenspectra) {
+            for (i, (s, (&v, &t))) in self.segment.iter_mut().zip(x.iter().zip(taper)).enumerate() {
+                *s = (v - trend(i)) * t;
+            }
+            self.segment[self.len..].fill(T::zero());
+            self.plan
+                .rfft_with_scratch(&self.segment, &mut self.spectrum, &mut self.scratch)?;
+            for (e, c) in eigen.iter_mut().zip(&self.spectrum) {
+                *e = c.re * c.re + c.im * c.im;
+            }
+        }
+
+        let nfft = self.plan.size();
+        let bins = self.plan.spectrum_len();
+        let scale = T::from_f64(1.0 / self.config.fs);
+        let two = T::from_f64(2.0);
+        for k in 0..bins {
+            let s = self.combine(k, variance) * scale;
+            // DC and Nyquist have no negative-frequency twin
+            let folded = k != 0 && k != bins - 1;
+            match self.config.sides {
+                Sides::OneSided => out[k] = if folded { s * two } else { s },
+                Sides::TwoSided => {
+                    out[k] = s;
+                    if folded {
+                        out[nfft - k] = s;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The eigenspectra combined at bin `k`.
+    fn combine(&self, k: usize, variance: T) -> T {
+        let spectra = self.eigenspectra.iter().map(|e| e[k]);
+        let weighted = |weight: &dyn Fn(T) -> T| {
+            let (num, den) = spectra.clone().zip(&self.ratios).fold(
+                (T::zero(), T::zero()),
+                |(num, den), (s, &r)| {
+                    let w = weight(r);
+                    (num + w * s, den + w)
+                },
+            );
+            num / den
+        };
+        match self.weighting {
+            Weighting::Unity => weighted(&|_| T::one()),
+            Weighting::Eigenvalue => weighted(&|r| r),
+            Weighting::Adaptive => {
+                // start from the two best tapers
+                let start = spectra.clone().take(2);
+                let mut estimate = start.clone().fold(T::zero(), |acc, s| acc + s)
+                    / T::from_f64(start.count() as f64);
+                let tol = T::from_f64(ADAPTIVE_TOL);
+                for _ in 0..ADAPTIVE_MAX_ITER {
+                    if estimate == T::zero() {
+                        break;
+                    }
+                    // d_k² = λ_k·S² / (λ_k·S + (1 - λ_k)·σ²)²
+                    let next = weighted(&|r| {
+                        let d = r * estimate + (T::one() - r) * variance;
+                        r * estimate * estimate / (d * d)
+                    });
+                    let change = next - estimate;
+                    estimate = next;
+                    if change <= tol * next && -change <= tol * next {
+                        break;
+                    }
+                }
+                estimate
+            }
+        }
+    }
+}
+
+/// Multitaper PSD of `x` with the `⌊2·nw⌋ - 1` best-concentrated DPSS
+/// tapers and adaptive weighting. Returns `(frequencies, power)`; see
+/// [`Multitaper`] for how `config` applies.
+pub fn psd<T: Float>(x: &[T], nw: f64, config: &PsdConfig) -> Result<(Vec<T>, Vec<T>), FftError> {
+    let count = ((2.0 * nw) as usize).saturating_sub(1).max(1);
+    let mut mt = Multitaper::new(x.len(), nw, count.min(x.len()), config)?;
+    let mut out = vec![T::zero(); mt.output_len()];
+    mt.estimate(x, &mut out)?;
+    Ok((mt.frequencies(), out))
+}
+
+/// Index `j` (ascending) eigenvalue of the symmetric tridiagonal matrix
+/// with diagonal `diag` and off-diagonal `off`, by Sturm-count bisection.
+fn eigenvalue(diag: &[f64], off: &[f64], j: usize) -> f64 {
+    // Gershgorin bounds
+    let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+    for (i, &d) in diag.iter().enumerate() {
+        let r = off.get(i.wrapping_sub(1)).map_or(0.0, |e| fabs(*e))
+            + off.get(i).map_or(0.0, |e| fabs(*e));
+        lo = lo.min(d - r);
+        hi = hi.max(d + r);
+    }
+    let pivmin = f64::EPSILON * fabs(hi).max(fabs(lo)).max(f64::MIN_POSITIVE);
+    for _ in 0..256 {
+        let mid = lo + (hi - lo) / 2.0;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        // eigenvalues below `mid`: negative pivots of LDLᵀ of T - mid·I
+        let mut below = 0;
+        let mut q = 1.0;
+        for (i, &d) in diag.iter().enumerate() {
+            let e2 = if i == 0 { 0.0 } else { off[i - 1] * off[i - 1] };
+            q = d - mid - e2 / q;
+            if fabs(q) < pivmin {
+                q = -pivmin;
+            }
+            if q < 0.0 {
+                below += 1;
+            }
+        }
+        if below > j {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    lo + (hi - lo) / 2.0
+}
+
+/// Unit eigenvector for the eigenvalue `lambda`, by inverse iteration from
+/// a start vector seeded with `seed`.
+fn eigenvector(diag: &[f64], off: &[f64], lambda: f64, seed: usize) -> Vec<f64> {
+    let n = diag.len();
+    // LU of T - λI with partial pivoting, as LAPACK's dgttrf
+    let mut d: Vec<f64> = diag.iter().map(|&x| x - lambda).collect();
+    let mut dl = off.to_vec();
+    let mut du = off.to_vec();
+    let mut du2 = vec![0.0; n.saturating_sub(2)];
+    let mut swapped = vec![false; n.saturating_sub(1)];
+    for i in 0..n.saturating_sub(1) {
+        if fabs(d[i]) >= fabs(dl[i]) {
+            if d[i] != 0.0 {
+                let fact = dl[i] / d[i];
+                dl[i] = fact;
+                d[i + 1] -= fact * du[i];
+            }
+        } else {
+            let fact = d[i] / dl[i];
+            d[i] = dl[i];
+            dl[i] = fact;
+            let temp = du[i];
+            du[i] = d[i + 1];
+            d[i + 1] = temp - fact * d[i + 1];
+            if i + 2 < n {
+                du2[i] = du[i + 1];
+                du[i + 1] *= -fact;
+            }
+            swapped[i] = true;
+        }
+    }
+    // λ is an eigenvalue, so U is singular up to rounding
+    let scale = diag
+        .iter()
+        .chain(off)
+        .fold(0.0, |m: f64, &x| m.max(fabs(x)));
+    let tiny = f64::EPSILON * scale.max(1.0);
+    for x in &mut d {
+        if fabs(*x) < tiny {
+            *x = if *x < 0.0 { -tiny } else { tiny };
+        }
+    }
+
+    let mut v: Vec<f64> = (0..n)
+        .map(|i| {
+            let h = (i as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ ((seed as u64 + 1) << 32);
+            (h >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        })
+        .collect();
+    normalize(&mut v);
+    for _ in 0..3 {
+        for i in 0..n.saturating_sub(1) {
+            if swapped[i] {
+                let temp = v[i] - dl[i] * v[i + 1];
+                v[i] = v[i + 1];
+                v[i + 1] = temp;
+            } else {
+                v[i + 1] -= dl[i] * v[i];
+            }
+        }
+        for i in (0..n).rev() {
+            let mut x = v[i];
+            if i + 1 < n {
+                x -= du[i] * v[i + 1];
+            }
+            if i + 2 < n {
+                x -= du2[i] * v[i + 2];
+            }
+            v[i] = x / d[i];
+        }
+        normalize(&mut v);
+    }
+    v
+}
+
+fn normalize(v: &mut [f64]) {
+    let norm = sqrt(v.iter().map(|x| x * x).sum());
+    v.iter_mut().for_each(|x| *x /= norm);
+}
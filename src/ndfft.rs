@@ -7,11 +7,15 @@
 //! - Future: 3D FFT, real input, streaming
 
 extern crate alloc;
-#[cfg(all(feature = "internal-tests", test))]
+#[cfg(any(feature = "parallel", all(feature = "internal-tests", test)))]
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::fft::{Complex, FftError, FftImpl, Float, ScalarFftImpl};
+use crate::fft::{scale_complex, Complex, FftError, FftImpl, Float, Normalization, ScalarFftImpl};
+#[cfg(feature = "parallel")]
+use crate::fft::{PlanFn, SharedFftPlan};
+#[cfg(feature = "parallel")]
+use crate::parallel::SharedMut;
 
 /// Result type returned by [`flatten_3d`].
 type Flatten3dResult<T> = (Vec<Complex<T>>, usize, usize, usize);
@@ -99,6 +103,156 @@ pub fn fft2d_inplace<T: Float>(
     Ok(())
 }
 
+/// Inverse of [`fft2d_inplace`], scaled by `1/(rows·cols)`.
+pub fn ifft2d_inplace<T: Float>(
+    data: &mut [Complex<T>],
+    rows: usize,
+    cols: usize,
+    fft: &ScalarFftImpl<T>,
+    scratch_col: &mut [Complex<T>],
+) -> Result<(), FftError> {
+    ifft2d_inplace_norm(data, rows, cols, fft, scratch_col, Normalization::Backward)
+}
+
+/// [`fft2d_inplace`] scaled according to `norm` for `rows·cols` points.
+pub fn fft2d_inplace_norm<T: Float>(
+    data: &mut [Complex<T>],
+    rows: usize,
+    cols: usize,
+    fft: &ScalarFftImpl<T>,
+    scratch_col: &mut [Complex<T>],
+    norm: Normalization,
+) -> Result<(), FftError> {
+    fft2d_inplace(data, rows, cols, fft, scratch_col)?;
+    scale_complex(data, norm.factors::<T>(data.len() as f64).0);
+    Ok(())
+}
+
+/// Inverse 2D FFT scaled according to `norm`; undoes
+/// [`fft2d_inplace_norm`] with the same `norm`.
+pub fn ifft2d_inplace_norm<T: Float>(
+    data: &mut [Complex<T>],
+    rows: usize,
+    cols: usize,
+    fft: &ScalarFftImpl<T>,
+    scratch_col: &mut [Complex<T>],
+    norm: Normalization,
+) -> Result<(), FftError> {
+    conjugate(data);
+    fft2d_inplace(data, rows, cols, fft, scratch_col)?;
+    conjugate(data);
+    scale_complex(data, norm.factors::<T>(data.len() as f64).1);
+    Ok(())
+}
+
+/// Columns gathered per task by [`fft2d_inplace_parallel`]: enough to fill
+/// cache lines on the strided reads without a large gather buffer.
+#[cfg(feature = "parallel")]
+const COLUMN_BLOCK: usize = 16;
+
+/// Parallel 2D FFT over a row plan of length `cols` and a column plan of
+/// length `rows`.
+///
+/// Rows are transformed in contiguous runs and columns in blocks gathered
+/// into a contiguous buffer, each spread over the handle of the plan doing
+/// the work. Every row and column goes through its plan exactly as it would
+/// serially, so the result is bit-identical whatever the pool, including
+/// [`Parallelism::serial`](crate::parallel::Parallelism::serial). Each plan's
+/// normalization applies along its axis.
+#[cfg(feature = "parallel")]
+pub fn fft2d_inplace_parallel<T: Float + Send + Sync>(
+    data: &mut [Complex<T>],
+    rows: usize,
+    cols: usize,
+    row_plan: &SharedFftPlan<T>,
+    col_plan: &SharedFftPlan<T>,
+) -> Result<(), FftError> {
+    axes_parallel(data, rows, cols, row_plan, col_plan, SharedFftPlan::fft)
+}
+
+/// Inverse of [`fft2d_inplace_parallel`], using each plan's inverse
+/// (scaled by `1/(rows·cols)` under the default normalization).
+#[cfg(feature = "parallel")]
+pub fn ifft2d_inplace_parallel<T: Float + Send + Sync>(
+    data: &mut [Complex<T>],
+    rows: usize,
+    cols: usize,
+    row_plan: &SharedFftPlan<T>,
+    col_plan: &SharedFftPlan<T>,
+) -> Result<(), FftError> {
+    axes_parallel(data, rows, cols, row_plan, col_plan, SharedFftPlan::ifft)
+}
+
+#[cfg(feature = "parallel")]
+fn axes_parallel<T: Float + Send + Sync>(
+    data: &mut [Complex<T>],
+    rows: usize,
+    cols: usize,
+    row_plan: &SharedFftPlan<T>,
+    col_plan: &SharedFftPlan<T>,
+    transform: PlanFn<T>,
+) -> Result<(), FftError> {
+    if rows * cols != data.len() || row_plan.size() != cols || col_plan.size() != rows {
+        return Err(FftError::MismatchedLengths);
+    }
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    let par = row_plan.parallelism();
+    par.try_for_each_chunk_mut(
+        data,
+        par.batch_chunk(rows) * cols,
+        || vec![Complex::zero(); row_plan.scratch_len()],
+        |scratch, _, run| {
+            run.chunks_exact_mut(cols)
+                .try_for_each(|row| transform(row_plan, row, scratch))
+        },
+    )?;
+
+    let par = col_plan.parallelism();
+    let base = SharedMut(data.as_mut_ptr());
+    par.try_for_each_init(
+        cols.div_ceil(COLUMN_BLOCK),
+        || {
+            (
+                vec![Complex::zero(); rows * COLUMN_BLOCK],
+                vec![Complex::zero(); col_plan.scratch_len()],
+            )
+        },
+        |(columns, scratch), block| {
+            let c0 = block * COLUMN_BLOCK;
+            let width = COLUMN_BLOCK.min(cols - c0);
+            // SAFETY: each task reads and writes only columns
+            // `c0..c0 + width`, which no other task touches, and `data`
+            // stays mutably borrowed until all tasks finish.
+            let at = |r: usize, c: usize| unsafe { &mut *base.get().add(r * cols + c0 + c) };
+            for r in 0..rows {
+                for c in 0..width {
+                    columns[c * rows + r] = *at(r, c);
+                }
+            }
+            for column in columns.chunks_exact_mut(rows).take(width) {
+                transform(col_plan, column, scratch)?;
+            }
+            for r in 0..rows {
+                for c in 0..width {
+                    *at(r, c) = columns[c * rows + r];
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Conjugate every sample, turning a forward transform into an unscaled
+/// inverse: `ifft(x) = conj(fft(conj(x)))`.
+fn conjugate<T: Float>(data: &mut [Complex<T>]) {
+    for c in data.iter_mut() {
+        c.im = -c.im;
+    }
+}
+
 /// Scratch buffers for [`fft3d_inplace`].
 pub struct Fft3dScratch<'a, T: Float> {
     pub tube: &'a mut [Complex<T>],
@@ -152,6 +306,60 @@ pub fn fft3d_inplace<T: Float>(
     Ok(())
 }
 
+/// Inverse of [`fft3d_inplace`], scaled by `1/(depth·rows·cols)`.
+pub fn ifft3d_inplace<T: Float>(
+    data: &mut [Complex<T>],
+    depth: usize,
+    rows: usize,
+    cols: usize,
+    fft: &ScalarFftImpl<T>,
+    scratch: &mut Fft3dScratch<'_, T>,
+) -> Result<(), FftError> {
+    ifft3d_inplace_norm(
+        data,
+        depth,
+        rows,
+        cols,
+        fft,
+        scratch,
+        Normalization::Backward,
+    )
+}
+
+/// [`fft3d_inplace`] scaled according to `norm` for `depth·rows·cols`
+/// points.
+pub fn fft3d_inplace_norm<T: Float>(
+    data: &mut [Complex<T>],
+    depth: usize,
+    rows: usize,
+    cols: usize,
+    fft: &ScalarFftImpl<T>,
+    scratch: &mut Fft3dScratch<'_, T>,
+    norm: Normalization,
+) -> Result<(), FftError> {
+    fft3d_inplace(data, depth, rows, cols, fft, scratch)?;
+    scale_complex(data, norm.factors::<T>(data.len() as f64).0);
+    Ok(())
+}
+
+/// Inverse 3D FFT scaled according to `norm`; undoes
+/// [`fft3d_inplace_norm`] with the same `norm`.
+pub fn ifft3d_inplace_norm<T: Float>(
+    data: &mut [Complex<T>],
+    depth: usize,
+    rows: usize,
+    cols: usize,
+    fft: &ScalarFftImpl<T>,
+    scratch: &mut Fft3dScratch<'_, T>,
+    norm: Normalization,
+) -> Result<(), FftError> {
+    conjugate(data);
+    fft3d_inplace(data, depth, rows, cols, fft, scratch)?;
+    conjugate(data);
+    scale_complex(data, norm.factors::<T>(data.len() as f64).1);
+    Ok(())
+}
+
 #[cfg(all(feature = "internal-tests", test))]
 mod tests {
     use super::*;
@@ -165,13 +373,8 @@ mod tests {
         cols: usize,
         fft: &ScalarFftImpl<T>,
     ) {
-        for r in 0..rows {
-            fft.ifft(&mut data[r * cols..(r + 1) * cols]).unwrap();
-        }
         let mut col = vec![Complex::zero(); rows];
-        for c in 0..cols {
-            fft.ifft_strided(&mut data[c..], cols, &mut col).unwrap();
-        }
+        ifft2d_inplace(data, rows, cols, fft, &mut col).unwrap();
     }
 
     fn inverse_3d<T: Float>(
@@ -182,27 +385,14 @@ mod tests {
         fft: &ScalarFftImpl<T>,
     ) {
         let mut tube = vec![Complex::zero(); depth];
-        for r in 0..rows {
-            for c in 0..cols {
-                let start = r * cols + c;
-                fft.ifft_strided(&mut data[start..], rows * cols, &mut tube)
-                    .unwrap();
-            }
-        }
-        let mut row_buf = vec![Complex::zero(); rows];
-        for d in 0..depth {
-            for c in 0..cols {
-                let start = d * rows * cols + c;
-                fft.ifft_strided(&mut data[start..], cols, &mut row_buf)
-                    .unwrap();
-            }
-        }
-        for d in 0..depth {
-            for r in 0..rows {
-                let start = d * rows * cols + r * cols;
-                fft.ifft(&mut data[start..start + cols]).unwrap();
-            }
-        }
+        let mut row = vec![Complex::zero(); rows];
+        let mut col = vec![Complex::zero(); cols];
+        let mut scratch = Fft3dScratch {
+            tube: &mut tube,
+            row: &mut row,
+            col: &mut col,
+        };
+        ifft3d_inplace(data, depth, rows, cols, fft, &mut scratch).unwrap();
     }
 
     #[test]
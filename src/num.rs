@@ -1,3 +1,4 @@
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::f32::consts::PI as PI32;
 
@@ -18,6 +19,11 @@ pub trait Float:
     fn zero() -> Self;
     fn one() -> Self;
     fn from_f32(x: f32) -> Self;
+    /// Convert from `f64`, rounding to the nearest representable value.
+    #[inline(always)]
+    fn from_f64(x: f64) -> Self {
+        Self::from_f32(x as f32)
+    }
     fn cos(self) -> Self;
     fn sin(self) -> Self;
     fn sin_cos(self) -> (Self, Self);
@@ -84,6 +90,9 @@ impl Float for f64 {
     fn from_f32(x: f32) -> Self {
         x as f64
     }
+    fn from_f64(x: f64) -> Self {
+        x
+    }
     fn cos(self) -> Self {
         f64::cos(self)
     }
@@ -232,6 +241,15 @@ impl<T: Float> core::ops::Mul for Complex<T> {
 pub type Complex32 = Complex<f32>;
 pub type Complex64 = Complex<f64>;
 
+/// View a complex buffer as twice as many interleaved reals.
+#[cfg(feature = "alloc")]
+pub(crate) fn as_real<T: Float>(buf: &mut [Complex<T>]) -> &mut [T] {
+    // SAFETY: `Complex<T>` is `#[repr(C)]` with two `T` fields, so it has
+    // the size and alignment of `[T; 2]` and no padding; the buffer is a
+    // valid `[T]` of twice the length for the same borrow.
+    unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, buf.len() * 2) }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct SplitComplex<'a, T: Float> {
     pub re: &'a mut [T],
@@ -261,12 +279,14 @@ impl<'a, T: Float> SplitComplex<'a, T> {
 pub type SplitComplex32<'a> = SplitComplex<'a, f32>;
 pub type SplitComplex64<'a> = SplitComplex<'a, f64>;
 
+#[cfg(feature = "alloc")]
 #[derive(Clone, Debug, PartialEq)]
 pub struct ComplexVec {
     pub re: Vec<f32>,
     pub im: Vec<f32>,
 }
 
+#[cfg(feature = "alloc")]
 impl ComplexVec {
     pub fn new(re: Vec<f32>, im: Vec<f32>) -> Self {
         assert_eq!(re.len(), im.len());
@@ -307,6 +327,7 @@ impl ComplexVec {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<Vec<Complex32>> for ComplexVec {
     fn from(v: Vec<Complex32>) -> Self {
         let mut re = Vec::with_capacity(v.len());
@@ -319,6 +340,7 @@ impl From<Vec<Complex32>> for ComplexVec {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<ComplexVec> for Vec<Complex32> {
     fn from(cv: ComplexVec) -> Self {
         let mut out = Vec::with_capacity(cv.re.len());
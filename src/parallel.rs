@@ -0,0 +1,295 @@
+//! Where parallel transforms run.
+//!
+//! A [`Parallelism`] handle tells a plan which workers to use and when a
+//! transform is large enough to split:
+//!
+//! - [`Parallelism::global`] (the default): Rayon's global pool;
+//! - [`Parallelism::rayon`]: a caller-owned [`rayon::ThreadPool`];
+//! - [`Parallelism::executor`]: any [`Executor`], e.g. a server's own pool;
+//! - [`Parallelism::serial`]: the calling thread only.
+//!
+//! Its [`ParallelFftConfig`] overrides the process-wide `set_parallel_fft_*`
+//! knobs for that plan alone; fields left at `0` fall back to them. Plans
+//! take a handle through `with_parallelism`
+//! ([`FftPlanner::set_parallelism`](crate::fft::FftPlanner::set_parallelism),
+//! [`SharedFftPlan::with_parallelism`](crate::fft::SharedFftPlan::with_parallelism),
+//! [`FourStepPlan::with_parallelism`](crate::four_step::FourStepPlan::with_parallelism)),
+//! and batch APIs such as [`stft::parallel_with_plan`](crate::stft::parallel_with_plan)
+//! spread their work with the handle of the plan they are given.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use kofft::fft::{Complex32, SharedFftPlan};
+//! use kofft::parallel::Parallelism;
+//!
+//! let pool = Arc::new(rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap());
+//! let plan = SharedFftPlan::<f32>::new(1024)
+//!     .unwrap()
+//!     .with_parallelism(Parallelism::rayon(pool));
+//! let mut data = vec![Complex32::new(1.0, 0.0); 1024];
+//! let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+//! plan.fft(&mut data, &mut scratch).unwrap();
+//! ```
+
+use alloc::sync::Arc;
+use core::fmt;
+
+use rayon::prelude::*;
+
+use crate::fft::{FftError, ParallelFftConfig};
+
+/// A custom pool that runs indexed tasks.
+///
+/// Implement this to keep kofft's work on threads you manage. kofft groups
+/// its work into at most a few tasks per [`threads`](Self::threads), so each
+/// task is coarse enough to amortise a dispatch.
+pub trait Executor: Send + Sync {
+    /// Number of workers, used to size the work split.
+    fn threads(&self) -> usize;
+
+    /// Run `task(i)` for every `i < tasks`, possibly concurrently, and
+    /// return once all of them have finished.
+    fn run(&self, tasks: usize, task: &(dyn Fn(usize) + Sync));
+}
+
+impl Executor for rayon::ThreadPool {
+    fn threads(&self) -> usize {
+        self.current_num_threads()
+    }
+
+    fn run(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+        self.install(|| (0..tasks).into_par_iter().for_each(task));
+    }
+}
+
+#[derive(Clone)]
+enum Pool {
+    Serial,
+    Global,
+    Rayon(Arc<rayon::ThreadPool>),
+    Custom(Arc<dyn Executor>),
+}
+
+/// Workers and tuning for a plan's parallel execution. See the
+/// [module documentation](self).
+#[derive(Clone)]
+pub struct Parallelism {
+    pool: Pool,
+    config: ParallelFftConfig,
+}
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Self::global()
+    }
+}
+
+impl fmt::Debug for Parallelism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pool = match self.pool {
+            Pool::Serial => "serial",
+            Pool::Global => "global",
+            Pool::Rayon(_) => "rayon",
+            Pool::Custom(_) => "executor",
+        };
+        f.debug_struct("Parallelism")
+            .field("pool", &pool)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Parallelism {
+    /// Rayon's global pool, tuned by the process-wide knobs.
+    pub fn global() -> Self {
+        Self::with_pool(Pool::Global)
+    }
+
+    /// Never split work; everything runs on the calling thread.
+    pub fn serial() -> Self {
+        Self::with_pool(Pool::Serial)
+    }
+
+    /// A caller-owned Rayon pool.
+    pub fn rayon(pool: Arc<rayon::ThreadPool>) -> Self {
+        Self::with_pool(Pool::Rayon(pool))
+    }
+
+    /// A custom [`Executor`].
+    pub fn executor(executor: Arc<dyn Executor>) -> Self {
+        Self::with_pool(Pool::Custom(executor))
+    }
+
+    fn with_pool(pool: Pool) -> Self {
+        Self {
+            pool,
+            config: ParallelFftConfig::default(),
+        }
+    }
+
+    /// Override the process-wide tuning for this handle. Fields left at `0`
+    /// keep the global value; `calibrated_per_core_work` is ignored.
+    pub fn with_config(mut self, config: ParallelFftConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The per-handle tuning set by [`with_config`](Self::with_config).
+    pub fn config(&self) -> &ParallelFftConfig {
+        &self.config
+    }
+
+    /// Whether work ever leaves the calling thread.
+    pub fn is_serial(&self) -> bool {
+        matches!(self.pool, Pool::Serial)
+    }
+
+    /// Number of workers work is spread over.
+    pub fn threads(&self) -> usize {
+        match &self.pool {
+            Pool::Serial => 1,
+            Pool::Global => rayon::current_num_threads(),
+            Pool::Rayon(pool) => pool.current_num_threads(),
+            Pool::Custom(executor) => executor.threads().max(1),
+        }
+    }
+
+    /// Whether an `n`-point transform is worth splitting.
+    pub fn should_parallelize(&self, n: usize) -> bool {
+        let mut config = self.config;
+        match &self.pool {
+            Pool::Serial => return false,
+            Pool::Global => {}
+            // The global heuristic assumes one worker per core; size the
+            // split for this pool instead.
+            Pool::Rayon(_) | Pool::Custom(_) => {
+                if config.threads == 0 {
+                    config.threads = self.threads();
+                }
+            }
+        }
+        crate::fft::should_parallelize_in(n, &config)
+    }
+
+    /// Call `f(state, i)` for every `i < tasks`, possibly concurrently, with
+    /// `state` created by `init` once per worker or group of tasks. Returns
+    /// the first error any call reported.
+    pub(crate) fn try_for_each_init<S, I, F>(
+        &self,
+        tasks: usize,
+        init: I,
+        f: F,
+    ) -> Result<(), FftError>
+    where
+        I: Fn() -> S + Sync + Send,
+        F: Fn(&mut S, usize) -> Result<(), FftError> + Sync + Send,
+    {
+        match &self.pool {
+            Pool::Serial => {
+                let mut state = init();
+                (0..tasks).try_for_each(|i| f(&mut state, i))
+            }
+            Pool::Global => (0..tasks).into_par_iter().try_for_each_init(&init, &f),
+            Pool::Rayon(pool) => {
+                pool.install(|| (0..tasks).into_par_iter().try_for_each_init(&init, &f))
+            }
+            Pool::Custom(executor) => {
+                let groups = tasks.min(4 * executor.threads().max(1));
+                let first_error = FirstError::default();
+                executor.run(groups, &|g| {
+                    let mut state = init();
+                    for i in (g * tasks / groups)..((g + 1) * tasks / groups) {
+                        if let Err(e) = f(&mut state, i) {
+                            first_error.set(e);
+                            return;
+                        }
+                    }
+                });
+                first_error.get()
+            }
+        }
+    }
+
+    /// Items per task when spreading `items` independent transforms: a few
+    /// contiguous runs per worker, so each task keeps its scratch hot and
+    /// uneven workers still balance.
+    pub(crate) fn batch_chunk(&self, items: usize) -> usize {
+        items.div_ceil(4 * self.threads()).max(1)
+    }
+
+    /// [`try_for_each_init`](Self::try_for_each_init) over the
+    /// `chunk_len`-element chunks of `data` (the last one may be shorter),
+    /// calling `f(state, index, chunk)`.
+    pub(crate) fn try_for_each_chunk_mut<D, S, I, F>(
+        &self,
+        data: &mut [D],
+        chunk_len: usize,
+        init: I,
+        f: F,
+    ) -> Result<(), FftError>
+    where
+        D: Send,
+        I: Fn() -> S + Sync + Send,
+        F: Fn(&mut S, usize, &mut [D]) -> Result<(), FftError> + Sync + Send,
+    {
+        let len = data.len();
+        let chunk_len = chunk_len.max(1);
+        let base = SharedMut(data.as_mut_ptr());
+        self.try_for_each_init(len.div_ceil(chunk_len), init, |state, i| {
+            let start = i * chunk_len;
+            let end = len.min(start + chunk_len);
+            // SAFETY: every index is visited once, so chunks are disjoint,
+            // and `data` stays mutably borrowed until all tasks finish.
+            let chunk =
+                unsafe { core::slice::from_raw_parts_mut(base.get().add(start), end - start) };
+            f(state, i, chunk)
+        })
+    }
+
+    /// Infallible [`try_for_each_chunk_mut`](Self::try_for_each_chunk_mut).
+    pub(crate) fn for_each_chunk_mut<D, S, I, F>(
+        &self,
+        data: &mut [D],
+        chunk_len: usize,
+        init: I,
+        f: F,
+    ) where
+        D: Send,
+        I: Fn() -> S + Sync + Send,
+        F: Fn(&mut S, usize, &mut [D]) + Sync + Send,
+    {
+        let result = self.try_for_each_chunk_mut(data, chunk_len, init, |state, i, chunk| {
+            f(state, i, chunk);
+            Ok(())
+        });
+        debug_assert!(result.is_ok());
+    }
+}
+
+/// Base pointer of a buffer whose disjoint parts are written by different
+/// workers.
+pub(crate) struct SharedMut<T>(pub(crate) *mut T);
+
+impl<T> SharedMut<T> {
+    pub(crate) fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+// SAFETY: only hands disjoint regions of one buffer to workers.
+unsafe impl<T: Send> Send for SharedMut<T> {}
+unsafe impl<T: Send> Sync for SharedMut<T> {}
+
+/// First error reported by any task of a custom executor.
+#[derive(Default)]
+struct FirstError(std::sync::OnceLock<FftError>);
+
+impl FirstError {
+    fn set(&self, e: FftError) {
+        let _ = self.0.set(e);
+    }
+
+    fn get(&self) -> Result<(), FftError> {
+        self.0.get().map_or(Ok(()), |&e| Err(e))
+    }
+}
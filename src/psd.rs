@@ -0,0 +1,438 @@
+//! Power and cross spectral density estimates.
+//!
+//! [`periodogram`], [`welch`], [`csd`] and [`coherence`] follow
+//! `scipy.signal`: the same segmenting, detrending, window normalisation,
+//! one-sided doubling and frequency layout, so results agree to floating
+//! point tolerance. Windows come from [`window`](crate::window) or
+//! [`window_more`](crate::window_more); their length sets the segment
+//! length. Options default to scipy's defaults through [`PsdConfig`].
+//!
+//! ```
+//! use kofft::psd::{welch, PsdConfig, Scaling};
+//! use kofft::window::hann;
+//!
+//! // a 2·√2-amplitude sine at 100 Hz, sampled at 1 kHz
+//! let x: Vec<f64> = (0..4000)
+//!     .map(|i| 2.0 * 2f64.sqrt() * (2.0 * core::f64::consts::PI * 100.0 * i as f64 / 1000.0).sin())
+//!     .collect();
+//! let config = PsdConfig { fs: 1000.0, scaling: Scaling::Spectrum, ..Default::default() };
+//! let (freqs, power) = welch(&x, &hann(200), &config).unwrap();
+//! assert_eq!(freqs[20], 100.0);
+//! assert!((power[20] - 4.0).abs() < 1e-6); // the sine's mean square
+//! ```
+
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::fft::{Complex, FftError, Float, SharedFftPlan};
+use crate::rfft::RealFftPlan;
+
+/// Trend removed from each segment before windowing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Detrend {
+    None,
+    /// Subtract the mean.
+    #[default]
+    Constant,
+    /// Subtract the least-squares line.
+    Linear,
+}
+
+/// Which frequencies are returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sides {
+    /// `nfft/2 + 1` non-negative frequencies, with the power of the
+    /// negative ones folded in.
+    #[default]
+    OneSided,
+    /// All `nfft` frequencies in FFT order: `0, df, …, -2df, -df`.
+    TwoSided,
+}
+
+/// Units of the estimate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Scaling {
+    /// Power spectral density, in V²/Hz for a signal in V.
+    #[default]
+    Density,
+    /// Power spectrum, in V²: a sine of amplitude `A` centred on a bin
+    /// reads `A²/2` one-sided.
+    Spectrum,
+}
+
+/// Options shared by the estimators; the defaults are scipy's.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PsdConfig {
+    /// Sampling frequency. Default `1.0`.
+    pub fs: f64,
+    /// Samples shared by consecutive segments. Default: half a segment.
+    /// Ignored by [`periodogram`].
+    pub overlap: Option<usize>,
+    /// FFT length, at least the segment length; segments are zero-padded
+    /// to it. Default: the segment length.
+    pub nfft: Option<usize>,
+    pub detrend: Detrend,
+    pub sides: Sides,
+    pub scaling: Scaling,
+}
+
+impl Default for PsdConfig {
+    fn default() -> Self {
+        Self {
+            fs: 1.0,
+            overlap: None,
+            nfft: None,
+            detrend: Detrend::Constant,
+            sides: Sides::OneSided,
+            scaling: Scaling::Density,
+        }
+    }
+}
+
+/// Periodogram of `x`: a single segment spanning the whole signal.
+///
+/// `window` must be `x.len()` long; `None` is a rectangular window. As in
+/// scipy, an `nfft` shorter than `x` truncates it. Returns
+/// `(frequencies, power)`.
+pub fn periodogram<T: Float>(
+    x: &[T],
+    window: Option<&[f32]>,
+    config: &PsdConfig,
+) -> Result<(Vec<T>, Vec<T>), FftError> {
+    let mut config = *config;
+    config.overlap = Some(0);
+    let x = match config.nfft {
+        Some(nfft) if nfft < x.len() => {
+            config.nfft = None;
+            &x[..nfft]
+        }
+        _ => x,
+    };
+    let boxcar;
+    let window = match window {
+        Some(window) => window,
+        None => {
+            boxcar = vec![1.0; x.len()];
+            &boxcar
+        }
+    };
+    if window.len() != x.len() {
+        return Err(FftError::MismatchedLengths);
+    }
+    welch(x, window, &config)
+}
+
+/// Welch's method: the mean periodogram of overlapping, windowed segments
+/// of `window.len()` samples. Returns `(frequencies, power)`.
+///
+/// Returns [`FftError::EmptyInput`] for an empty signal or window,
+/// [`FftError::MismatchedLengths`] if `x` is shorter than one segment, and
+/// [`FftError::InvalidValue`] if the overlap is not shorter than a segment,
+/// `nfft` is shorter than one, or `fs` is not positive.
+pub fn welch<T: Float>(
+    x: &[T],
+    window: &[f32],
+    config: &PsdConfig,
+) -> Result<(Vec<T>, Vec<T>), FftError> {
+    let (freqs, spectra) = spectral_helper(x, None, window, config)?;
+    Ok((freqs, spectra.xx))
+}
+
+/// Cross spectral density `Pxy = E[conj(X)·Y]` of equally long `x` and
+/// `y` by Welch's method. Returns `(frequencies, Pxy)`.
+pub fn csd<T: Float>(
+    x: &[T],
+    y: &[T],
+    window: &[f32],
+    config: &PsdConfig,
+) -> Result<(Vec<T>, Vec<Complex<T>>), FftError> {
+    let (freqs, spectra) = spectral_helper(x, Some(y), window, config)?;
+    Ok((freqs, spectra.xy))
+}
+
+/// Magnitude-squared coherence `|Pxy|² / (Pxx·Pyy)`, between 0 and 1 at
+/// every frequency. Returns `(frequencies, Cxy)`.
+///
+/// `Pxx`, `Pyy` and `Pxy` come from one pass over the segments, so each
+/// segment of `x` and `y` is transformed once.
+pub fn coherence<T: Float>(
+    x: &[T],
+    y: &[T],
+    window: &[f32],
+    config: &PsdConfig,
+) -> Result<(Vec<T>, Vec<T>), FftError> {
+    let (freqs, spectra) = spectral_helper(x, Some(y), window, config)?;
+    let cxy = spectra
+        .xy
+        .iter()
+        .zip(spectra.xx.iter().zip(&spectra.yy))
+        .map(|(p, (&a, &b))| (p.re * p.re + p.im * p.im) / a / b)
+        .collect();
+    Ok((freqs, cxy))
+}
+
+/// Scaled and folded segment averages of `|X|²` and, given a second
+/// signal, `|Y|²` and `conj(X)·Y`.
+struct Spectra<T: Float> {
+    xx: Vec<T>,
+    yy: Vec<T>,
+    xy: Vec<Complex<T>>,
+}
+
+/// Averaged `|X|²` (and `|Y|²` and `conj(X)·Y` given `y`) over the
+/// segments, scaled and folded like scipy's `_spectral_helper`.
+fn spectral_helper<T: Float>(
+    x: &[T],
+    y: Option<&[T]>,
+    window: &[f32],
+    config: &PsdConfig,
+) -> Result<(Vec<T>, Spectra<T>), FftError> {
+    let nperseg = window.len();
+    if x.is_empty() || nperseg == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if y.is_some_and(|y| y.len() != x.len()) || x.len() < nperseg {
+        return Err(FftError::MismatchedLengths);
+    }
+    let overlap = config.overlap.unwrap_or(nperseg / 2);
+    let nfft = config.nfft.unwrap_or(nperseg);
+    if overlap >= nperseg || nfft < nperseg || !(config.fs > 0.0 && config.fs.is_finite()) {
+        return Err(FftError::InvalidValue);
+    }
+
+    let win: Vec<T> = window.iter().map(|&w| T::from_f32(w)).collect();
+    let scale = match config.scaling {
+        Scaling::Density => {
+            let energy: f64 = window.iter().map(|&w| w as f64 * w as f64).sum();
+            1.0 / (config.fs * energy)
+        }
+        Scaling::Spectrum => {
+            let sum: f64 = window.iter().map(|&w| w as f64).sum();
+            1.0 / (sum * sum)
+        }
+    };
+
+    // Real input: only the non-negative frequencies are transformed and
+    // accumulated; the negative ones are their conjugates.
+    let half = nfft / 2 + 1;
+    let mut fft = SegmentFft::new(nfft)?;
+    let mut fx = vec![Complex::zero(); half];
+    let mut fy = vec![Complex::zero(); if y.is_some() { half } else { 0 }];
+    let mut xx = vec![T::zero(); half];
+    let mut yy = vec![T::zero(); fy.len()];
+    let mut xy = vec![Complex::zero(); fy.len()];
+    let step = nperseg - overlap;
+    let segments = (x.len() - nperseg) / step + 1;
+    for start in (0..segments).map(|s| s * step) {
+        let range = start..start + nperseg;
+        fft.transform(&x[range.clone()], &win, config.detrend, &mut fx)?;
+        for (a, p) in xx.iter_mut().zip(&fx) {
+            *a = *a + p.re * p.re + p.im * p.im;
+        }
+        if let Some(y) = y {
+            fft.transform(&y[range], &win, config.detrend, &mut fy)?;
+            for (a, q) in yy.iter_mut().zip(&fy) {
+                *a = *a + q.re * q.re + q.im * q.im;
+            }
+            for (a, (p, q)) in xy.iter_mut().zip(fx.iter().zip(&fy)) {
+                *a = a.add(Complex::new(p.re, -p.im).mul(*q));
+            }
+        }
+    }
+
+    let scale = T::from_f64(scale / segments as f64);
+    let two = T::from_f64(2.0);
+    // bins whose negative-frequency twin is folded in: all but DC and,
+    // for even `nfft`, Nyquist
+    let folded = match config.sides {
+        Sides::OneSided if nfft.is_multiple_of(2) => 1..half - 1,
+        Sides::OneSided => 1..half,
+        Sides::TwoSided => 0..0,
+    };
+    let factor = |k: usize| {
+        if folded.contains(&k) {
+            scale * two
+        } else {
+            scale
+        }
+    };
+    for (k, a) in xx.iter_mut().enumerate() {
+        *a = *a * factor(k);
+    }
+    for (k, a) in yy.iter_mut().enumerate() {
+        *a = *a * factor(k);
+    }
+    for (k, a) in xy.iter_mut().enumerate() {
+        *a = Complex::new(a.re * factor(k), a.im * factor(k));
+    }
+
+    let mut spectra = Spectra { xx, yy, xy };
+    if config.sides == Sides::TwoSided {
+        spectra.xx = mirror(&spectra.xx, nfft, |&v| v);
+        spectra.yy = mirror(&spectra.yy, nfft, |&v| v);
+        spectra.xy = mirror(&spectra.xy, nfft, |c| Complex::new(c.re, -c.im));
+    }
+    Ok((frequencies(nfft, config), spectra))
+}
+
+/// Extend the non-negative-frequency bins `half` to all `nfft` bins in FFT
+/// order, bin `nfft - k` being `twin(half[k])`. An empty `half` stays
+/// empty.
+fn mirror<V: Copy>(half: &[V], nfft: usize, twin: impl Fn(&V) -> V) -> Vec<V> {
+    if half.is_empty() {
+        return Vec::new();
+    }
+    (0..nfft)
+        .map(|k| {
+            if k < half.len() {
+                half[k]
+            } else {
+                twin(&half[nfft - k])
+            }
+        })
+        .collect()
+}
+
+/// FFT of one real, zero-padded segment, keeping the `nfft/2 + 1`
+/// non-negative frequencies.
+///
+/// Even lengths run through a [`RealFftPlan`]; odd lengths, which it does
+/// not serve, through a complex FFT of the same size.
+struct SegmentFft<T: Float> {
+    kernel: SegmentKernel<T>,
+    segment: Vec<T>,
+    scratch: Vec<Complex<T>>,
+}
+
+enum SegmentKernel<T: Float> {
+    Real(RealFftPlan<T>),
+    Complex {
+        fft: SharedFftPlan<T>,
+        spectrum: Vec<Complex<T>>,
+    },
+}
+
+impl<T: Float> SegmentFft<T> {
+    fn new(nfft: usize) -> Result<Self, FftError> {
+        let (kernel, scratch) = if nfft.is_multiple_of(2) {
+            let plan = RealFftPlan::new(nfft)?;
+            let scratch = plan.scratch_len();
+            (SegmentKernel::Real(plan), scratch)
+        } else {
+            let fft = SharedFftPlan::new(nfft)?;
+            let scratch = fft.scratch_len();
+            let spectrum = vec![Complex::zero(); nfft];
+            (SegmentKernel::Complex { fft, spectrum }, scratch)
+        };
+        Ok(Self {
+            kernel,
+            segment: vec![T::zero(); nfft],
+            scratch: vec![Complex::zero(); scratch],
+        })
+    }
+
+    /// Detrend, window and zero-pad `segment`, and write the non-negative
+    /// frequencies of its FFT to `out`.
+    fn transform(
+        &mut self,
+        segment: &[T],
+        window: &[T],
+        detrend: Detrend,
+        out: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        load_segment(segment, window, detrend, &mut self.segment);
+        match &mut self.kernel {
+            SegmentKernel::Real(plan) => {
+                plan.rfft_with_scratch(&self.segment, out, &mut self.scratch)
+            }
+            SegmentKernel::Complex { fft, spectrum } => {
+                for (c, &v) in spectrum.iter_mut().zip(&self.segment) {
+                    *c = Complex::new(v, T::zero());
+                }
+                fft.fft(spectrum, &mut self.scratch)?;
+                out.copy_from_slice(&spectrum[..out.len()]);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Frequencies of the `nfft`-point estimate's bins for `config.sides`.
+pub(crate) fn frequencies<T: Float>(nfft: usize, config: &PsdConfig) -> Vec<T> {
+    let bins = match config.sides {
+        Sides::OneSided => nfft / 2 + 1,
+        Sides::TwoSided => nfft,
+    };
+    let df = config.fs / nfft as f64;
+    (0..bins)
+        .map(|k| {
+            // two-sided output is in FFT order, as `numpy.fft.fftfreq`
+            let k = match config.sides {
+                Sides::TwoSided if k >= nfft.div_ceil(2) => k as f64 - nfft as f64,
+                _ => k as f64,
+            };
+            T::from_f64(k * df)
+        })
+        .collect()
+}
+
+/// Detrend, window and zero-pad `segment` into `out`.
+fn load_segment<T: Float>(segment: &[T], window: &[T], detrend: Detrend, out: &mut [T]) {
+    let n = segment.len();
+    let (offset, slope) = trend(segment, detrend);
+    for (i, (o, (&v, &w))) in out.iter_mut().zip(segment.iter().zip(window)).enumerate() {
+        let trend = offset + slope * T::from_f64(i as f64);
+        *o = (v - trend) * w;
+    }
+    out[n..].fill(T::zero());
+}
+
+/// `(offset, slope)` of the trend `detrend` removes from `segment`: sample
+/// `i` loses `offset + slope·i`.
+pub(crate) fn trend<T: Float>(segment: &[T], detrend: Detrend) -> (T, T) {
+    let n = segment.len();
+    match detrend {
+        Detrend::None => (T::zero(), T::zero()),
+        Detrend::Constant => (mean(segment), T::zero()),
+        Detrend::Linear => {
+            // least-squares line through (i, segment[i]), centred on the
+            // mean index
+            let mid = T::from_f64((n as f64 - 1.0) / 2.0);
+            let mean = mean(segment);
+            let (mut sxy, mut sxx) = (T::zero(), T::zero());
+            for (i, &v) in segment.iter().enumerate() {
+                let t = T::from_f64(i as f64) - mid;
+                sxy = sxy + t * (v - mean);
+                sxx = sxx + t * t;
+            }
+            let slope = if sxx == T::zero() {
+                T::zero()
+            } else {
+                sxy / sxx
+            };
+            (mean - slope * mid, slope)
+        }
+    }
+}
+
+fn mean<T: Float>(x: &[T]) -> T {
+    x.iter().fold(T::zero(), |acc, &v| acc + v) / T::from_f64(x.len() as f64)
+}
+
+#[cfg(all(feature = "internal-tests", test))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_detrend_removes_ramps() {
+        let ramp: Vec<f64> = (0..16).map(|i| 3.0 - 0.25 * i as f64).collect();
+        let window = vec![1.0f64; 16];
+        let mut out = vec![0.0; 32];
+        load_segment(&ramp, &window, Detrend::Linear, &mut out);
+        assert!(out.iter().all(|v| v.abs() < 1e-12));
+        load_segment(&ramp, &window, Detrend::Constant, &mut out);
+        assert!((out[0] - 1.875).abs() < 1e-12);
+        assert!(out[16..].iter().all(|&v| v == 0.0));
+    }
+}
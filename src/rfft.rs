@@ -11,15 +11,23 @@ use hashbrown::HashMap;
 
 use core::mem::MaybeUninit;
 
-use crate::fft::{fft_inplace_stack, ifft_inplace_stack, Complex, Complex32, FftError, FftImpl};
+#[cfg(all(target_arch = "x86_64", feature = "x86_64"))]
+use crate::fft::Complex32;
+use crate::fft::{
+    scale_complex, Complex, FftError, FftImpl, Normalization, SharedFftPlan, Twiddles,
+};
+use crate::fft_kernels::{pack_spectrum, unpack_spectrum};
 use crate::num::Float;
+use crate::twiddles::TwiddleTable;
+
+pub use crate::mcu::rfft::{irfft_stack, rfft_stack};
 
 /// Trait providing specialized real FFT implementations for concrete
 /// floating-point types.
 #[doc(hidden)]
 pub trait RealFftNum: Float {
     #[cfg(feature = "compile-time-rfft")]
-    fn load_precomputed(cache: &mut HashMap<usize, Arc<[Complex<Self>]>>);
+    fn load_precomputed(cache: &mut HashMap<usize, Twiddles<Self>>);
 
     fn rfft_with_scratch_impl<F: FftImpl<Self> + ?Sized>(
         fft: &F,
@@ -42,9 +50,9 @@ pub trait RealFftNum: Float {
 
 impl RealFftNum for f32 {
     #[cfg(feature = "compile-time-rfft")]
-    fn load_precomputed(cache: &mut HashMap<usize, Arc<[Complex<Self>]>>) {
+    fn load_precomputed(cache: &mut HashMap<usize, Twiddles<Self>>) {
         for &(m, table) in precomputed::F32 {
-            cache.insert(m, Arc::from(table));
+            cache.insert(m, Twiddles::Static(table));
         }
     }
 
@@ -56,6 +64,10 @@ impl RealFftNum for f32 {
         twiddles: &[Complex<Self>],
         pack_twiddles: &[Complex<Self>],
     ) -> Result<(), FftError> {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        if crate::backend::avx512_active() {
+            return crate::avx512::rfft(fft, input, output, scratch, twiddles);
+        }
         #[cfg(all(target_arch = "x86_64", feature = "x86_64"))]
         {
             unsafe {
@@ -97,6 +109,10 @@ impl RealFftNum for f32 {
         twiddles: &[Complex<Self>],
         pack_twiddles: &[Complex<Self>],
     ) -> Result<(), FftError> {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        if crate::backend::avx512_active() {
+            return crate::avx512::irfft(fft, input, output, scratch, twiddles);
+        }
         #[cfg(all(target_arch = "x86_64", feature = "x86_64"))]
         {
             unsafe {
@@ -133,9 +149,9 @@ impl RealFftNum for f32 {
 
 impl RealFftNum for f64 {
     #[cfg(feature = "compile-time-rfft")]
-    fn load_precomputed(cache: &mut HashMap<usize, Arc<[Complex<Self>]>>) {
+    fn load_precomputed(cache: &mut HashMap<usize, Twiddles<Self>>) {
         for &(m, table) in precomputed::F64 {
-            cache.insert(m, Arc::from(table));
+            cache.insert(m, Twiddles::Static(table));
         }
     }
 
@@ -147,6 +163,10 @@ impl RealFftNum for f64 {
         twiddles: &[Complex<Self>],
         pack_twiddles: &[Complex<Self>],
     ) -> Result<(), FftError> {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        if crate::backend::avx512_active() {
+            return crate::avx512::rfft(fft, input, output, scratch, twiddles);
+        }
         rfft_direct(fft, input, output, scratch, twiddles, pack_twiddles)
     }
 
@@ -158,6 +178,10 @@ impl RealFftNum for f64 {
         twiddles: &[Complex<Self>],
         pack_twiddles: &[Complex<Self>],
     ) -> Result<(), FftError> {
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        if crate::backend::avx512_active() {
+            return crate::avx512::irfft(fft, input, output, scratch, twiddles);
+        }
         irfft_direct(fft, input, output, scratch, twiddles, pack_twiddles)
     }
 }
@@ -165,8 +189,19 @@ impl RealFftNum for f64 {
 #[cfg(feature = "compile-time-rfft")]
 mod precomputed {
     use crate::fft::{Complex32, Complex64};
-    pub const F32: &[(usize, &[Complex32])] = &[];
-    pub const F64: &[(usize, &[Complex64])] = &[];
+    use crate::twiddles::TwiddleTable;
+
+    /// `(m, exp(-iπk/m) for k < m)`: the first half of the period-`2m` table.
+    macro_rules! tables {
+        ($t:ty; $($n:literal),*) => {
+            &[$(($n / 2, TwiddleTable::half(&const { TwiddleTable::<$t, $n>::new() }))),*]
+        };
+    }
+
+    pub const F32: &[(usize, &[Complex32])] =
+        tables!(f32; 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048);
+    pub const F64: &[(usize, &[Complex64])] =
+        tables!(f64; 4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048);
 }
 
 fn build_twiddle_table<T: Float>(m: usize) -> alloc::vec::Vec<Complex<T>> {
@@ -193,9 +228,9 @@ fn build_twiddle_table<T: Float>(m: usize) -> alloc::vec::Vec<Complex<T>> {
 /// recomputing trigonometric values on every invocation.
 pub struct RfftPlanner<T: RealFftNum> {
     /// Post-processing twiddle factors used after the complex FFT.
-    cache: HashMap<usize, Arc<[Complex<T>]>>,
+    cache: HashMap<usize, Twiddles<T>>,
     /// Twiddles used when packing/unpacking the real-even/odd layout.
-    pack_cache: HashMap<usize, Arc<[Complex<T>]>>,
+    pack_cache: HashMap<usize, Twiddles<T>>,
     /// Reusable scratch buffer.
     scratch: Vec<Complex<T>>,
 }
@@ -215,24 +250,22 @@ impl<T: RealFftNum> RfftPlanner<T> {
 
     /// Create a new [`RfftPlanner`].
     pub fn new() -> Self {
-        let mut cache: HashMap<usize, Arc<[Complex<T>]>> = HashMap::new();
-        let mut pack_cache: HashMap<usize, Arc<[Complex<T>]>> = HashMap::new();
+        let mut cache: HashMap<usize, Twiddles<T>> = HashMap::new();
+        let mut pack_cache: HashMap<usize, Twiddles<T>> = HashMap::new();
 
         #[cfg(feature = "compile-time-rfft")]
         T::load_precomputed(&mut cache);
 
         for &m in Self::PRECOMPUTED {
-            cache.entry(m).or_insert_with(|| {
-                let vec = build_twiddle_table::<T>(m);
-                Arc::from(vec)
-            });
+            cache
+                .entry(m)
+                .or_insert_with(|| Twiddles::Shared(Arc::from(build_twiddle_table::<T>(m))));
             // The packing/unpacking step uses the same radix but we cache a
             // separate table to keep the interface future proof in case a
             // different generation scheme is required.
-            pack_cache.entry(m).or_insert_with(|| {
-                let vec = build_twiddle_table::<T>(m);
-                Arc::from(vec)
-            });
+            pack_cache
+                .entry(m)
+                .or_insert_with(|| Twiddles::Shared(Arc::from(build_twiddle_table::<T>(m))));
         }
 
         Self {
@@ -242,22 +275,39 @@ impl<T: RealFftNum> RfftPlanner<T> {
         }
     }
 
-    /// Retrieve or build the twiddle table for length `m`.
-    pub fn get_twiddles(&mut self, m: usize) -> Arc<[Complex<T>]> {
-        if !self.cache.contains_key(&m) {
-            let vec = build_twiddle_table::<T>(m);
-            self.cache.insert(m, Arc::from(vec));
+    /// Seed both caches for half-length `m = N/2` from a compile-time table
+    /// of period `N` (the real signal length), such as
+    /// `kofft::twiddles!(f32, 4096)` for 4096-sample input.
+    ///
+    /// Returns [`FftError::InvalidValue`] if `N` is odd or the table's period
+    /// is not `N`.
+    pub fn load_twiddles<const N: usize>(
+        &mut self,
+        table: &'static TwiddleTable<T, N>,
+    ) -> Result<(), FftError> {
+        if N == 0 || !N.is_multiple_of(2) || table.period() != N {
+            return Err(FftError::InvalidValue);
         }
-        Arc::clone(self.cache.get(&m).unwrap())
+        let half = Twiddles::Static(table.half());
+        self.cache.insert(N / 2, half.clone());
+        self.pack_cache.insert(N / 2, half);
+        Ok(())
+    }
+
+    /// Retrieve or build the twiddle table for length `m`.
+    pub fn get_twiddles(&mut self, m: usize) -> Twiddles<T> {
+        self.cache
+            .entry(m)
+            .or_insert_with(|| Twiddles::Shared(Arc::from(build_twiddle_table::<T>(m))))
+            .clone()
     }
 
     /// Retrieve or build the pack/unpack twiddle table for length `m`.
-    pub fn get_pack_twiddles(&mut self, m: usize) -> Arc<[Complex<T>]> {
-        if !self.pack_cache.contains_key(&m) {
-            let vec = build_twiddle_table::<T>(m);
-            self.pack_cache.insert(m, Arc::from(vec));
-        }
-        Arc::clone(self.pack_cache.get(&m).unwrap())
+    pub fn get_pack_twiddles(&mut self, m: usize) -> Twiddles<T> {
+        self.pack_cache
+            .entry(m)
+            .or_insert_with(|| Twiddles::Shared(Arc::from(build_twiddle_table::<T>(m))))
+            .clone()
     }
 
     /// Compute a real-input FFT using cached twiddle tables.
@@ -337,6 +387,126 @@ impl<T: RealFftNum> RfftPlanner<T> {
     }
 }
 
+/// Immutable, precomputed real FFT plan for one even length `n`.
+///
+/// Like [`SharedFftPlan`] it is `Send + Sync` and never allocates while
+/// executing: callers pass a scratch buffer of at least
+/// [`scratch_len`](Self::scratch_len) complex samples.
+///
+/// ```
+/// use kofft::fft::Complex32;
+/// use kofft::rfft::RealFftPlan;
+///
+/// let plan = RealFftPlan::<f32>::new(64).unwrap();
+/// let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+/// let signal = vec![1.0f32; 64];
+/// let mut spectrum = vec![Complex32::zero(); 33];
+/// plan.rfft_with_scratch(&signal, &mut spectrum, &mut scratch).unwrap();
+/// assert!((spectrum[0].re - 64.0).abs() < 1e-4);
+/// ```
+pub struct RealFftPlan<T: Float> {
+    n: usize,
+    fft: SharedFftPlan<T>,
+    twiddles: Arc<[Complex<T>]>,
+    norm: Normalization,
+}
+
+impl<T: Float> RealFftPlan<T> {
+    /// Precompute a plan for real signals of length `n`, which must be even.
+    pub fn new(n: usize) -> Result<Self, FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if !n.is_multiple_of(2) {
+            return Err(FftError::InvalidValue);
+        }
+        let m = n / 2;
+        Ok(Self {
+            n,
+            fft: SharedFftPlan::new(m)?,
+            twiddles: Arc::from(build_twiddle_table::<T>(m)),
+            norm: Normalization::Backward,
+        })
+    }
+
+    /// Use `norm` instead of the default [`Normalization::Backward`].
+    pub fn with_normalization(mut self, norm: Normalization) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Normalization applied by the forward and inverse transforms.
+    pub fn normalization(&self) -> Normalization {
+        self.norm
+    }
+
+    /// Length of the real signal.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Number of spectrum bins, `n/2 + 1`.
+    pub fn spectrum_len(&self) -> usize {
+        self.n / 2 + 1
+    }
+
+    /// Exact scratch length, in complex samples, for both directions.
+    pub fn scratch_len(&self) -> usize {
+        self.n / 2 + self.fft.scratch_len()
+    }
+
+    /// Real-to-complex FFT of `input` (length `n`) into `output`
+    /// (length `n/2 + 1`).
+    pub fn rfft_with_scratch(
+        &self,
+        input: &[T],
+        output: &mut [Complex<T>],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        let m = self.n / 2;
+        if input.len() != self.n || output.len() != m + 1 || scratch.len() < self.scratch_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        for (o, pair) in output.iter_mut().zip(input.chunks_exact(2)) {
+            *o = Complex::new(pair[0], pair[1]);
+        }
+        let (z, rest) = scratch.split_at_mut(m);
+        self.fft.fft(&mut output[..m], rest)?;
+        z.copy_from_slice(&output[..m]);
+        pack_spectrum(z, output, &self.twiddles, 1);
+        scale_complex(output, self.norm.factors::<T>(self.n as f64).0);
+        Ok(())
+    }
+
+    /// Complex-to-real inverse FFT of `input` (length `n/2 + 1`) into
+    /// `output` (length `n`). Under the default normalization it is scaled
+    /// by `1/n` so it inverts [`rfft_with_scratch`](Self::rfft_with_scratch).
+    pub fn irfft_with_scratch(
+        &self,
+        input: &[Complex<T>],
+        output: &mut [T],
+        scratch: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        let m = self.n / 2;
+        if output.len() != self.n || input.len() != m + 1 || scratch.len() < self.scratch_len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        let (z, rest) = scratch.split_at_mut(m);
+        unpack_spectrum(input, z, &self.twiddles, 1);
+        self.fft.ifft(z, rest)?;
+        // The half-length inverse already divides by `n`.
+        let scale = match self.norm {
+            Normalization::Backward => T::one(),
+            norm => norm.factors::<T>(self.n as f64).1 * T::from_f64(self.n as f64),
+        };
+        for (pair, c) in output.chunks_exact_mut(2).zip(z.iter()) {
+            pair[0] = c.re * scale;
+            pair[1] = c.im * scale;
+        }
+        Ok(())
+    }
+}
+
 /// Old packed real FFT kernel used for comparison and fallback.
 pub fn rfft_packed<T: RealFftNum, F: FftImpl<T>>(
     planner: &mut RfftPlanner<T>,
@@ -447,20 +617,7 @@ fn rfft_direct<T: Float, F: FftImpl<T> + ?Sized>(
     fft.fft(&mut output[..m])?;
     // Copy FFT results so we can perform the symmetric post-processing
     scratch[..m].copy_from_slice(&output[..m]);
-    let y0 = scratch[0];
-    output[0] = Complex::new(y0.re + y0.im, T::zero());
-    output[m] = Complex::new(y0.re - y0.im, T::zero());
-    let half = T::from_f32(0.5);
-    for k in 1..m {
-        let a = scratch[k];
-        let b = Complex::new(scratch[m - k].re, -scratch[m - k].im);
-        let sum = a.add(b);
-        let diff = a.sub(b);
-        let w = twiddles[k];
-        let t = w.mul(diff);
-        let temp = sum.add(Complex::new(t.im, -t.re));
-        output[k] = Complex::new(temp.re * half, temp.im * half);
-    }
+    pack_spectrum(&scratch[..m], output, twiddles, 1);
     Ok(())
 }
 
@@ -484,21 +641,7 @@ fn irfft_direct<T: Float, F: FftImpl<T> + ?Sized>(
     if input.len() != m + 1 || scratch.len() < m {
         return Err(FftError::MismatchedLengths);
     }
-    let half = T::from_f32(0.5);
-    scratch[0] = Complex::new(
-        (input[0].re + input[m].re) * half,
-        (input[0].re - input[m].re) * half,
-    );
-    for k in 1..m {
-        let a = input[k];
-        let b = Complex::new(input[m - k].re, -input[m - k].im);
-        let sum = a.add(b);
-        let diff = a.sub(b);
-        let w = Complex::new(twiddles[k].re, -twiddles[k].im);
-        let t = w.mul(diff);
-        let temp = sum.sub(Complex::new(t.im, -t.re));
-        scratch[k] = Complex::new(temp.re * half, temp.im * half);
-    }
+    unpack_spectrum(input, &mut scratch[..m], twiddles, 1);
     fft.ifft(&mut scratch[..m])?;
     for i in 0..m {
         output[2 * i] = scratch[i].re;
@@ -836,57 +979,10 @@ pub trait RealFftImpl<T: RealFftNum>: FftImpl<T> {
 // Blanket implementation for any complex FFT provider.
 impl<T: RealFftNum, U: FftImpl<T>> RealFftImpl<T> for U {}
 
-/// Perform a real-input FFT using only stack allocation.
-///
-/// The output buffer must have length `N/2 + 1`.
-pub fn rfft_stack<const N: usize, const M: usize>(
-    input: &[f32; N],
-    output: &mut [Complex32; M],
-) -> Result<(), FftError> {
-    if N == 0 {
-        return Err(FftError::EmptyInput);
-    }
-    if M != N / 2 + 1 {
-        return Err(FftError::MismatchedLengths);
-    }
-    let mut buf = [Complex32::new(0.0, 0.0); N];
-    for (b, &x) in buf.iter_mut().zip(input.iter()) {
-        *b = Complex32::new(x, 0.0);
-    }
-    fft_inplace_stack(&mut buf)?;
-    output[..(N / 2 + 1)].copy_from_slice(&buf[..(N / 2 + 1)]);
-    Ok(())
-}
-
-/// Perform an inverse real-input FFT using only stack allocation.
-///
-/// The input buffer must have length `N/2 + 1`.
-pub fn irfft_stack<const N: usize, const M: usize>(
-    input: &[Complex32; M],
-    output: &mut [f32; N],
-) -> Result<(), FftError> {
-    if N == 0 {
-        return Err(FftError::EmptyInput);
-    }
-    if M != N / 2 + 1 {
-        return Err(FftError::MismatchedLengths);
-    }
-    let mut buf = [Complex32::new(0.0, 0.0); N];
-    buf[..(N / 2 + 1)].copy_from_slice(&input[..(N / 2 + 1)]);
-    for k in 1..N / 2 {
-        buf[N - k] = Complex32::new(input[k].re, -input[k].im);
-    }
-    ifft_inplace_stack(&mut buf)?;
-    for (o, &b) in output.iter_mut().zip(buf.iter()) {
-        *o = b.re;
-    }
-    Ok(())
-}
-
 #[cfg(all(feature = "internal-tests", test))]
 mod tests {
     use super::*;
-    use crate::fft::{Complex64, ScalarFftImpl};
+    use crate::fft::{Complex32, Complex64, ScalarFftImpl};
     use alloc::vec;
 
     #[test]
@@ -0,0 +1,308 @@
+//! Double-precision FFT kernels for the SIMD backends.
+//!
+//! Power-of-two transforms run a split-format radix-2 Stockham FFT whose
+//! butterfly rows are vectorised per architecture: AVX2/FMA (with an SSE2
+//! fallback) on x86_64, NEON `float64x2_t` on AArch64 and SIMD128 `f64x2` on
+//! WebAssembly. Other lengths defer to [`ScalarFftImpl`].
+//!
+//! Twiddle tables and work buffers live in a [`SimdState`] owned by each
+//! backend instance, so repeated transforms neither lock nor allocate.
+
+use crate::fft::{FftError, FftImpl, ScalarFftImpl};
+use crate::num::{Complex, Complex64, Float};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use hashbrown::HashMap;
+
+#[cfg(target_arch = "aarch64")]
+use core::arch::aarch64::*;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use core::arch::wasm32::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+/// One row of radix-2 butterflies in a Stockham pass:
+/// `out0 = even + w·odd` and `out1 = even - w·odd` over `len` elements.
+pub(crate) struct Row {
+    even_re: *const f64,
+    even_im: *const f64,
+    odd_re: *const f64,
+    odd_im: *const f64,
+    out0_re: *mut f64,
+    out0_im: *mut f64,
+    out1_re: *mut f64,
+    out1_im: *mut f64,
+    len: usize,
+}
+
+/// A vector kernel processes a prefix of the row and returns how many
+/// elements it handled; the driver finishes the tail in scalar code.
+///
+/// # Safety
+///
+/// Every pointer in the [`Row`] must be valid for `len` elements and the
+/// CPU must support the instructions the kernel was compiled for.
+pub(crate) type RowKernel = unsafe fn(&Row, Complex64) -> usize;
+
+/// Twiddle tables and split-format work buffer a SIMD backend reuses
+/// between calls.
+#[derive(Default)]
+pub(crate) struct SplitState<T: Float> {
+    /// `exp(-2πi k / n)` tables keyed by `n`.
+    twiddles: HashMap<usize, Arc<[Complex<T>]>>,
+    /// Split real/imaginary data and scratch, grown to the largest length seen.
+    buf: Vec<T>,
+}
+
+impl<T: Float> SplitState<T> {
+    /// `exp(-2πi k / n)` for `k = 0..len`, built once per `n`.
+    pub(crate) fn twiddles(&mut self, n: usize, len: usize) -> Arc<[Complex<T>]> {
+        Arc::clone(self.twiddles.entry(n).or_insert_with(|| {
+            (0..len)
+                .map(|k| {
+                    let (s, c) = libm::sincos(-2.0 * core::f64::consts::PI * k as f64 / n as f64);
+                    Complex::new(T::from_f64(c), T::from_f64(s))
+                })
+                .collect()
+        }))
+    }
+
+    /// Four split buffers of `n` elements: data and scratch, real and
+    /// imaginary.
+    pub(crate) fn buffers(&mut self, n: usize) -> [&mut [T]; 4] {
+        if self.buf.len() < 4 * n {
+            self.buf.resize(4 * n, T::zero());
+        }
+        let (re, rest) = self.buf[..4 * n].split_at_mut(n);
+        let (im, rest) = rest.split_at_mut(n);
+        let (scratch_re, scratch_im) = rest.split_at_mut(n);
+        [re, im, scratch_re, scratch_im]
+    }
+}
+
+/// [`SplitState`] behind an [`UnsafeCell`], mirroring how [`ScalarFftImpl`]
+/// keeps its planner: the owning backend is therefore not `Sync`.
+///
+/// It also owns the [`ScalarFftImpl`] used for lengths the vector path does
+/// not serve, so its mixed-radix, Rader and Bluestein plans persist across
+/// calls.
+#[derive(Default)]
+pub(crate) struct SimdState<T: Float> {
+    split: UnsafeCell<SplitState<T>>,
+    scalar: ScalarFftImpl<T>,
+}
+
+impl<T: Float> SimdState<T> {
+    /// # Safety
+    ///
+    /// Callers must ensure no other reference to the state is live while the
+    /// returned one is used.
+    #[allow(clippy::mut_from_ref)]
+    #[inline]
+    pub(crate) unsafe fn get(&self) -> &mut SplitState<T> {
+        &mut *self.split.get()
+    }
+
+    /// The instance's scalar fallback.
+    #[inline]
+    pub(crate) fn scalar(&self) -> &ScalarFftImpl<T> {
+        &self.scalar
+    }
+}
+
+/// In-place forward FFT of `input` using `kernel` for the butterfly rows.
+pub(crate) fn fft(
+    state: &SimdState<f64>,
+    input: &mut [Complex64],
+    kernel: RowKernel,
+) -> Result<(), FftError> {
+    let n = input.len();
+    if n == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if n <= 16 || !n.is_power_of_two() {
+        return state.scalar().fft(input);
+    }
+    // SAFETY: the state is only borrowed for the duration of this call and
+    // the scalar fallback above never reaches it.
+    let state = unsafe { state.get() };
+    let twiddles = state.twiddles(n, n / 2);
+    let [re, im, scratch_re, scratch_im] = state.buffers(n);
+    for (c, (r, i)) in input.iter().zip(re.iter_mut().zip(im.iter_mut())) {
+        *r = c.re;
+        *i = c.im;
+    }
+
+    let mut src = (re, im);
+    let mut dst = (scratch_re, scratch_im);
+    let mut n1 = 1usize;
+    let mut n2 = n;
+    while n1 < n {
+        n2 >>= 1;
+        for k in 0..n1 {
+            let w = twiddles[k * n2];
+            let even = 2 * k * n2;
+            let odd = even + n2;
+            let out0 = k * n2;
+            let out1 = (k + n1) * n2;
+            let row = Row {
+                even_re: src.0[even..].as_ptr(),
+                even_im: src.1[even..].as_ptr(),
+                odd_re: src.0[odd..].as_ptr(),
+                odd_im: src.1[odd..].as_ptr(),
+                out0_re: dst.0[out0..].as_mut_ptr(),
+                out0_im: dst.1[out0..].as_mut_ptr(),
+                out1_re: dst.0[out1..].as_mut_ptr(),
+                out1_im: dst.1[out1..].as_mut_ptr(),
+                len: n2,
+            };
+            // SAFETY: each pointer addresses `n2` elements of the split
+            // buffers, and the kernel was selected for the running CPU.
+            let done = unsafe { kernel(&row, w) };
+            for j in done..n2 {
+                let (er, ei) = (src.0[even + j], src.1[even + j]);
+                let (or, oi) = (src.0[odd + j], src.1[odd + j]);
+                let t_re = or * w.re - oi * w.im;
+                let t_im = or * w.im + oi * w.re;
+                dst.0[out0 + j] = er + t_re;
+                dst.1[out0 + j] = ei + t_im;
+                dst.0[out1 + j] = er - t_re;
+                dst.1[out1 + j] = ei - t_im;
+            }
+        }
+        core::mem::swap(&mut src, &mut dst);
+        n1 <<= 1;
+    }
+    for (c, (&r, &i)) in input.iter_mut().zip(src.0.iter().zip(src.1.iter())) {
+        *c = Complex::new(r, i);
+    }
+    Ok(())
+}
+
+/// In-place inverse FFT (scaled by `1/n`) using `kernel`.
+pub(crate) fn ifft(
+    state: &SimdState<f64>,
+    input: &mut [Complex64],
+    kernel: RowKernel,
+) -> Result<(), FftError> {
+    for c in input.iter_mut() {
+        c.im = -c.im;
+    }
+    fft(state, input, kernel)?;
+    let scale = 1.0 / input.len() as f64;
+    for c in input.iter_mut() {
+        c.re *= scale;
+        c.im = -c.im * scale;
+    }
+    Ok(())
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn row_avx2_fma(row: &Row, w: Complex64) -> usize {
+    let w_re = _mm256_set1_pd(w.re);
+    let w_im = _mm256_set1_pd(w.im);
+    let mut j = 0;
+    while j + 4 <= row.len {
+        let even_re = _mm256_loadu_pd(row.even_re.add(j));
+        let even_im = _mm256_loadu_pd(row.even_im.add(j));
+        let odd_re = _mm256_loadu_pd(row.odd_re.add(j));
+        let odd_im = _mm256_loadu_pd(row.odd_im.add(j));
+        let t_re = _mm256_fmsub_pd(odd_re, w_re, _mm256_mul_pd(odd_im, w_im));
+        let t_im = _mm256_fmadd_pd(odd_re, w_im, _mm256_mul_pd(odd_im, w_re));
+        _mm256_storeu_pd(row.out0_re.add(j), _mm256_add_pd(even_re, t_re));
+        _mm256_storeu_pd(row.out0_im.add(j), _mm256_add_pd(even_im, t_im));
+        _mm256_storeu_pd(row.out1_re.add(j), _mm256_sub_pd(even_re, t_re));
+        _mm256_storeu_pd(row.out1_im.add(j), _mm256_sub_pd(even_im, t_im));
+        j += 4;
+    }
+    j
+}
+
+#[cfg(target_arch = "x86_64")]
+unsafe fn row_sse2(row: &Row, w: Complex64) -> usize {
+    let w_re = _mm_set1_pd(w.re);
+    let w_im = _mm_set1_pd(w.im);
+    let mut j = 0;
+    while j + 2 <= row.len {
+        let even_re = _mm_loadu_pd(row.even_re.add(j));
+        let even_im = _mm_loadu_pd(row.even_im.add(j));
+        let odd_re = _mm_loadu_pd(row.odd_re.add(j));
+        let odd_im = _mm_loadu_pd(row.odd_im.add(j));
+        let t_re = _mm_sub_pd(_mm_mul_pd(odd_re, w_re), _mm_mul_pd(odd_im, w_im));
+        let t_im = _mm_add_pd(_mm_mul_pd(odd_re, w_im), _mm_mul_pd(odd_im, w_re));
+        _mm_storeu_pd(row.out0_re.add(j), _mm_add_pd(even_re, t_re));
+        _mm_storeu_pd(row.out0_im.add(j), _mm_add_pd(even_im, t_im));
+        _mm_storeu_pd(row.out1_re.add(j), _mm_sub_pd(even_re, t_re));
+        _mm_storeu_pd(row.out1_im.add(j), _mm_sub_pd(even_im, t_im));
+        j += 2;
+    }
+    j
+}
+
+/// Best x86_64 row kernel for the running CPU: AVX2/FMA when detected,
+/// otherwise SSE2, which every x86_64 CPU provides.
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn x86_64_kernel() -> RowKernel {
+    #[cfg(feature = "std")]
+    let fma =
+        std::arch::is_x86_feature_detected!("avx2") && std::arch::is_x86_feature_detected!("fma");
+    #[cfg(not(feature = "std"))]
+    let fma = cfg!(all(target_feature = "avx2", target_feature = "fma"));
+    if fma {
+        row_avx2_fma
+    } else {
+        row_sse2
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn row_neon(row: &Row, w: Complex64) -> usize {
+    let w_re = vdupq_n_f64(w.re);
+    let w_im = vdupq_n_f64(w.im);
+    let mut j = 0;
+    while j + 2 <= row.len {
+        let even_re = vld1q_f64(row.even_re.add(j));
+        let even_im = vld1q_f64(row.even_im.add(j));
+        let odd_re = vld1q_f64(row.odd_re.add(j));
+        let odd_im = vld1q_f64(row.odd_im.add(j));
+        // vfmsq(a, b, c) = a - b·c and vfmaq(a, b, c) = a + b·c
+        let t_re = vfmsq_f64(vmulq_f64(odd_re, w_re), odd_im, w_im);
+        let t_im = vfmaq_f64(vmulq_f64(odd_re, w_im), odd_im, w_re);
+        vst1q_f64(row.out0_re.add(j), vaddq_f64(even_re, t_re));
+        vst1q_f64(row.out0_im.add(j), vaddq_f64(even_im, t_im));
+        vst1q_f64(row.out1_re.add(j), vsubq_f64(even_re, t_re));
+        vst1q_f64(row.out1_im.add(j), vsubq_f64(even_im, t_im));
+        j += 2;
+    }
+    j
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub(crate) unsafe fn row_simd128(row: &Row, w: Complex64) -> usize {
+    let w_re = f64x2_splat(w.re);
+    let w_im = f64x2_splat(w.im);
+    let mut j = 0;
+    while j + 2 <= row.len {
+        let even_re = v128_load(row.even_re.add(j) as *const v128);
+        let even_im = v128_load(row.even_im.add(j) as *const v128);
+        let odd_re = v128_load(row.odd_re.add(j) as *const v128);
+        let odd_im = v128_load(row.odd_im.add(j) as *const v128);
+        let t_re = f64x2_sub(f64x2_mul(odd_re, w_re), f64x2_mul(odd_im, w_im));
+        let t_im = f64x2_add(f64x2_mul(odd_re, w_im), f64x2_mul(odd_im, w_re));
+        v128_store(row.out0_re.add(j) as *mut v128, f64x2_add(even_re, t_re));
+        v128_store(row.out0_im.add(j) as *mut v128, f64x2_add(even_im, t_im));
+        v128_store(row.out1_re.add(j) as *mut v128, f64x2_sub(even_re, t_re));
+        v128_store(row.out1_im.add(j) as *mut v128, f64x2_sub(even_im, t_im));
+        j += 2;
+    }
+    j
+}
+
+/// Portable fallback used by backends whose SIMD extension is unavailable.
+#[allow(dead_code)]
+pub(crate) unsafe fn row_scalar(_row: &Row, _w: Complex64) -> usize {
+    0
+}
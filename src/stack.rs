@@ -0,0 +1,394 @@
+//! Generic const-size transforms that never touch the heap.
+//!
+//! These are the `T: Float` counterparts of the `f32`, power-of-two
+//! `*_inplace_stack` helpers ([`fft_inplace_stack`](crate::fft::fft_inplace_stack),
+//! [`rfft_stack`](crate::rfft::rfft_stack), the DCT/DST and window helpers).
+//! Any `N >= 1` is accepted: the FFT is a self-sorting (Stockham) mixed-radix
+//! transform using radix-4, radix-2 and the 3/5/7/11/13-point butterflies,
+//! with a direct `O(p²)` butterfly for larger prime factors `p`.
+//!
+//! Twiddle factors are evaluated in `f64` by `const fn`s, so the table for
+//! each `N` is built at compile time and lives in read-only memory
+//! (`16·N` bytes per table); it is rounded to `T` as it is read. DCT-II/III
+//! and DST-II/III use a second table of the same size, and the cosine
+//! windows read the FFT table. DCT-I, DCT-IV and DST-IV are direct `O(N²)`
+//! sums whose angles are evaluated at run time.
+//!
+//! # Stack usage
+//!
+//! Besides the caller's arrays, each call places the following on the stack
+//! (`c = size_of::<Complex<T>>()`, i.e. 8 bytes for `f32` and 16 for
+//! `f64`):
+//!
+//! | Function | Stack | `N = 100`, `f64` | `N = 120`, `f64` |
+//! |----------|-------|------------------|------------------|
+//! | [`fft`], [`ifft`] | `N·c` | 1600 B | 1920 B |
+//! | [`rfft`], [`irfft`] | `2·N·c` | 3200 B | 3840 B |
+//! | [`dct2`], [`dct3`] | `2·N·c` | 3200 B | 3840 B |
+//! | [`dst2`], [`dst3`] | `2·N·c + N·size_of::<T>()` | 4000 B | 4800 B |
+//! | [`dct1`], [`dct4`], [`dst4`], windows | none | 0 B | 0 B |
+//!
+//! ```
+//! use kofft::stack;
+//! use kofft::Complex64;
+//!
+//! // A 120-point f64 frame: 120 = 4·2·3·5.
+//! let mut frame = [Complex64::new(1.0, 0.0); 120];
+//! stack::fft(&mut frame).unwrap();
+//! assert!((frame[0].re - 120.0).abs() < 1e-9);
+//! stack::ifft(&mut frame).unwrap();
+//! assert!((frame[7].re - 1.0).abs() < 1e-12);
+//! ```
+
+use crate::error::FftError;
+use crate::fft_kernels::{fft11, fft13, fft2, fft3, fft4, fft5, fft7};
+use crate::num::{Complex, Float};
+use crate::twiddles::sin_cos_turns;
+
+/// `[cos, -sin](2πk / period)` for `k = 0..N`, evaluated at compile time.
+const fn unit_roots<const N: usize>(period: usize) -> [[f64; 2]; N] {
+    let mut out = [[0.0; 2]; N];
+    let mut k = 0;
+    while k < N {
+        let (s, c) = sin_cos_turns(k as u128, period as u128);
+        out[k] = [c, -s];
+        k += 1;
+    }
+    out
+}
+
+/// Compile-time twiddle tables for size `N`.
+struct Roots<const N: usize>;
+
+impl<const N: usize> Roots<N> {
+    /// `exp(-2πik/N)`.
+    const FULL: &'static [[f64; 2]; N] = &unit_roots::<N>(N);
+    /// `exp(-πik/(2N))`, the DCT-II/III post-twiddle.
+    const QUARTER: &'static [[f64; 2]; N] = &unit_roots::<N>(4 * N);
+}
+
+#[inline(always)]
+fn root<T: Float>(table: &[[f64; 2]], k: usize) -> Complex<T> {
+    Complex::new(T::from_f64(table[k][0]), T::from_f64(table[k][1]))
+}
+
+/// Radix for the next Stockham stage of a length-`len` sub-transform.
+fn next_radix(len: usize) -> usize {
+    if len.is_multiple_of(4) {
+        return 4;
+    }
+    let mut p = 2;
+    while p * p <= len {
+        if len.is_multiple_of(p) {
+            return p;
+        }
+        p += 1;
+    }
+    len
+}
+
+/// One Stockham decimation-in-frequency stage: radix-`p` butterflies over
+/// sub-transforms of length `len` at stride `stride`, twiddles applied
+/// after the butterfly.
+fn stage<T: Float>(
+    src: &[Complex<T>],
+    dst: &mut [Complex<T>],
+    roots: &[[f64; 2]],
+    len: usize,
+    stride: usize,
+    p: usize,
+) {
+    let n = src.len();
+    let m = len / p;
+    let mut a = [Complex::zero(); 13];
+    for q in 0..m {
+        for k in 0..stride {
+            let at = |j: usize| src[k + stride * (q + m * j)];
+            if matches!(p, 2 | 3 | 4 | 5 | 7 | 11 | 13) {
+                for (j, v) in a[..p].iter_mut().enumerate() {
+                    *v = at(j);
+                }
+                match p {
+                    2 => fft2(&mut a[..2]),
+                    3 => fft3(&mut a[..3]),
+                    4 => fft4(&mut a[..4]),
+                    5 => fft5(&mut a[..5]),
+                    7 => fft7(&mut a[..7]),
+                    11 => fft11(&mut a[..11]),
+                    13 => fft13(&mut a[..13]),
+                    _ => {}
+                }
+                for (r, &v) in a[..p].iter().enumerate() {
+                    let tw = q * r * stride;
+                    dst[k + stride * (p * q + r)] =
+                        if tw == 0 { v } else { v.mul(root(roots, tw)) };
+                }
+            } else {
+                // Direct prime-length DFT: `ω_p^(jr) = ω_N^((jr mod p)·N/p)`.
+                let step = n / p;
+                for r in 0..p {
+                    let mut sum = at(0);
+                    for j in 1..p {
+                        sum = sum.add(at(j).mul(root(roots, (j * r % p) * step)));
+                    }
+                    let tw = q * r * stride;
+                    dst[k + stride * (p * q + r)] = if tw == 0 {
+                        sum
+                    } else {
+                        sum.mul(root(roots, tw))
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// In-place forward FFT of any length `N >= 1`, in `f32` or `f64`.
+///
+/// Uses one `[Complex<T>; N]` of stack scratch; see the
+/// [module documentation](self) for sizes.
+pub fn fft<T: Float, const N: usize>(buf: &mut [Complex<T>; N]) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    let roots = Roots::<N>::FULL;
+    let mut work = [Complex::<T>::zero(); N];
+    let (mut len, mut stride) = (N, 1);
+    let mut in_work = false;
+    while len > 1 {
+        let p = next_radix(len);
+        if in_work {
+            stage(&work, buf, roots, len, stride, p);
+        } else {
+            stage(buf, &mut work, roots, len, stride, p);
+        }
+        in_work = !in_work;
+        len /= p;
+        stride *= p;
+    }
+    if in_work {
+        *buf = work;
+    }
+    Ok(())
+}
+
+/// In-place inverse FFT of any length `N >= 1`, scaled by `1/N`.
+pub fn ifft<T: Float, const N: usize>(buf: &mut [Complex<T>; N]) -> Result<(), FftError> {
+    for c in buf.iter_mut() {
+        c.im = -c.im;
+    }
+    fft(buf)?;
+    let scale = T::one() / T::from_f64(N as f64);
+    for c in buf.iter_mut() {
+        *c = Complex::new(c.re * scale, -c.im * scale);
+    }
+    Ok(())
+}
+
+/// Real-input FFT of any length `N >= 1`; `output` must hold `N/2 + 1` bins.
+pub fn rfft<T: Float, const N: usize, const M: usize>(
+    input: &[T; N],
+    output: &mut [Complex<T>; M],
+) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if M != N / 2 + 1 {
+        return Err(FftError::MismatchedLengths);
+    }
+    let mut buf = [Complex::<T>::zero(); N];
+    for (b, &x) in buf.iter_mut().zip(input) {
+        *b = Complex::new(x, T::zero());
+    }
+    fft(&mut buf)?;
+    output.copy_from_slice(&buf[..M]);
+    Ok(())
+}
+
+/// Inverse of [`rfft`], scaled by `1/N`; `input` holds `N/2 + 1` bins.
+pub fn irfft<T: Float, const N: usize, const M: usize>(
+    input: &[Complex<T>; M],
+    output: &mut [T; N],
+) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if M != N / 2 + 1 {
+        return Err(FftError::MismatchedLengths);
+    }
+    let mut buf = [Complex::<T>::zero(); N];
+    buf[..M].copy_from_slice(input);
+    for k in M..N {
+        let c = input[N - k];
+        buf[k] = Complex::new(c.re, -c.im);
+    }
+    ifft(&mut buf)?;
+    for (o, b) in output.iter_mut().zip(&buf) {
+        *o = b.re;
+    }
+    Ok(())
+}
+
+/// Position of sample `i` in Makhoul's even-then-reversed-odd ordering.
+#[inline(always)]
+fn makhoul_index(i: usize, n: usize) -> usize {
+    if i.is_multiple_of(2) {
+        i / 2
+    } else {
+        n - 1 - i / 2
+    }
+}
+
+/// DCT-II, `X_k = Σ x_i cos(π(i + ½)k/N)`, via one `N`-point FFT
+/// (Makhoul's reordering) for any `N >= 1`.
+pub fn dct2<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    let mut v = [Complex::<T>::zero(); N];
+    for (i, &x) in input.iter().enumerate() {
+        v[makhoul_index(i, N)] = Complex::new(x, T::zero());
+    }
+    fft(&mut v)?;
+    let quarter = Roots::<N>::QUARTER;
+    for (k, (o, c)) in output.iter_mut().zip(&v).enumerate() {
+        *o = c.mul(root(quarter, k)).re;
+    }
+    Ok(())
+}
+
+/// DCT-III, `x_i = X_0/2 + Σ_{k≥1} X_k cos(πk(i + ½)/N)`, the inverse of
+/// [`dct2`] up to a factor `N/2`.
+pub fn dct3<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    let quarter = Roots::<N>::QUARTER;
+    let half = T::from_f64(0.5);
+    let mut v = [Complex::<T>::zero(); N];
+    v[0] = Complex::new(input[0] * half, T::zero());
+    for k in 1..N {
+        // `½·e^{iπk/2N}·(X_k - i·X_{N-k})`, conjugated for the forward FFT.
+        let w = root::<T>(quarter, k);
+        let z = Complex::new(input[k] * half, -input[N - k] * half);
+        let c = z.mul(Complex::new(w.re, -w.im));
+        v[k] = Complex::new(c.re, -c.im);
+    }
+    fft(&mut v)?;
+    for (i, o) in output.iter_mut().enumerate() {
+        *o = v[makhoul_index(i, N)].re;
+    }
+    Ok(())
+}
+
+/// DST-II, `X_k = Σ x_i sin(π(i + ½)(k + 1)/N)`, through [`dct2`] of the
+/// sign-alternated input.
+pub fn dst2<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    let mut alt = *input;
+    for x in alt.iter_mut().skip(1).step_by(2) {
+        *x = -*x;
+    }
+    dct2(&alt, output)?;
+    output.reverse();
+    Ok(())
+}
+
+/// DST-III, `x_k = (-1)^k X_{N-1}/2 + Σ_{i<N-1} X_i sin(π(i + 1)(k + ½)/N)`,
+/// the inverse of [`dst2`] up to a factor `N/2`.
+pub fn dst3<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    let mut rev = *input;
+    rev.reverse();
+    dct3(&rev, output)?;
+    for x in output.iter_mut().skip(1).step_by(2) {
+        *x = -*x;
+    }
+    Ok(())
+}
+
+/// `Σ x_i · f(π(2i + 1)(2k + 1)/(4N))`, evaluated in `f64`.
+fn type_iv<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N], f: fn(f64) -> f64) {
+    let factor = core::f64::consts::PI / (4 * N) as f64;
+    for (k, out) in output.iter_mut().enumerate() {
+        let mut sum = T::zero();
+        for (i, &x) in input.iter().enumerate() {
+            let arg = ((2 * i + 1) * (2 * k + 1) % (8 * N)) as f64;
+            sum = x.mul_add(T::from_f64(f(factor * arg)), sum);
+        }
+        *out = sum;
+    }
+}
+
+/// DCT-IV, `X_k = Σ x_i cos(π(i + ½)(k + ½)/N)` (direct, `O(N²)`).
+pub fn dct4<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    type_iv(input, output, libm::cos);
+    Ok(())
+}
+
+/// DST-IV, `X_k = Σ x_i sin(π(i + ½)(k + ½)/N)` (direct, `O(N²)`).
+pub fn dst4<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    type_iv(input, output, libm::sin);
+    Ok(())
+}
+
+/// DCT-I, `X_k = x_0 + (-1)^k x_{N-1} + 2 Σ_{0<i<N-1} x_i cos(πik/(N-1))`
+/// (direct, `O(N²)`); a single sample maps to `2·x_0`.
+pub fn dct1<T: Float, const N: usize>(input: &[T; N], output: &mut [T; N]) -> Result<(), FftError> {
+    if N == 0 {
+        return Err(FftError::EmptyInput);
+    }
+    if N == 1 {
+        output[0] = input[0] + input[0];
+        return Ok(());
+    }
+    let period = 2 * (N - 1);
+    let two = T::from_f64(2.0);
+    for (k, out) in output.iter_mut().enumerate() {
+        let last = if k.is_multiple_of(2) {
+            input[N - 1]
+        } else {
+            -input[N - 1]
+        };
+        let mut sum = input[0] + last;
+        for (i, &x) in input.iter().enumerate().take(N - 1).skip(1) {
+            let (_, c) = sin_cos_turns((i * k % period) as u128, period as u128);
+            sum = (two * x).mul_add(T::from_f64(c), sum);
+        }
+        *out = sum;
+    }
+    Ok(())
+}
+
+/// Periodic Hann window, `0.5 - 0.5 cos(2πi/N)`, from the compile-time table.
+pub fn hann<T: Float, const N: usize>(out: &mut [T; N]) {
+    cosine_window(out, &[0.5, 0.5]);
+}
+
+/// Periodic Hamming window, `0.54 - 0.46 cos(2πi/N)`.
+pub fn hamming<T: Float, const N: usize>(out: &mut [T; N]) {
+    cosine_window(out, &[0.54, 0.46]);
+}
+
+/// Periodic Blackman window, `0.42 - 0.5 cos(2πi/N) + 0.08 cos(4πi/N)`.
+pub fn blackman<T: Float, const N: usize>(out: &mut [T; N]) {
+    cosine_window(out, &[0.42, 0.5, 0.08]);
+}
+
+/// `Σ_h (-1)^h a_h cos(2πhi/N)`; harmonic `h` reads table entry `h·i mod N`.
+fn cosine_window<T: Float, const N: usize>(out: &mut [T; N], coeffs: &[f64]) {
+    let roots = Roots::<N>::FULL;
+    for (i, o) in out.iter_mut().enumerate() {
+        let mut sum = 0.0;
+        let mut sign = 1.0;
+        for (h, &a) in coeffs.iter().enumerate() {
+            sum += sign * a * roots[h * i % N][0];
+            sign = -sign;
+        }
+        *o = T::from_f64(sum);
+    }
+}
@@ -0,0 +1,368 @@
+//! Heapless FFT planner for targets without an allocator.
+//!
+//! [`StaticPlanner`] keeps its twiddle tables and scratch in fixed arrays
+//! sized by const generics, so it builds and runs with the `alloc` feature
+//! disabled. It drives the same scalar kernels as
+//! [`ScalarFftImpl`](crate::fft::ScalarFftImpl) for power-of-two lengths:
+//! the 2/4/8/16-point butterflies, the radix-2 Stockham passes, and the
+//! real-FFT pack/unpack step of [`RealFftPlan`](crate::rfft::RealFftPlan).
+//! Non-power-of-two lengths are served by [`crate::stack`].
+//!
+//! A table planned for length `n` holds `exp(-2πik/n)` for `k < n/2` and is
+//! shared by every power-of-two length dividing `n`, so planning the largest
+//! size first usually needs a single slot. Storage is `2·MAX_N` complex
+//! values: `MAX_N` of twiddles shared by the `SLOTS` tables and `MAX_N` of
+//! scratch. [`plan_with_table`](StaticPlanner::plan_with_table) instead
+//! borrows a compile-time [`TwiddleTable`] from read-only memory.
+//!
+//! ```
+//! use kofft::static_planner::StaticPlanner;
+//! use kofft::Complex32;
+//!
+//! let mut planner = StaticPlanner::<f32, 256, 2>::new();
+//! planner.plan(256).unwrap();
+//! let mut data = [Complex32::new(1.0, 0.0); 64];
+//! planner.fft(&mut data).unwrap(); // reuses the 256-point table
+//! assert!((data[0].re - 64.0).abs() < 1e-4);
+//! ```
+//!
+//! To keep the arrays out of the stack, place the planner in static memory
+//! and initialize it there with [`StaticPlanner::init`]:
+//!
+//! ```
+//! use core::mem::MaybeUninit;
+//! use kofft::static_planner::StaticPlanner;
+//!
+//! type Planner = StaticPlanner<f32, 1024, 2>;
+//! static mut STORAGE: MaybeUninit<Planner> = MaybeUninit::uninit();
+//!
+//! // SAFETY: the only reference ever taken to `STORAGE`.
+//! let planner: &'static mut Planner =
+//!     Planner::init(unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) });
+//! planner.plan(1024).unwrap();
+//! ```
+
+use core::mem::MaybeUninit;
+use core::ptr::addr_of_mut;
+
+use crate::error::FftError;
+use crate::fft_kernels::{
+    fft16, fft2, fft4, fft8, pack_spectrum, stockham_radix2, unpack_spectrum,
+};
+use crate::num::{Complex, Float};
+use crate::twiddles::TwiddleTable;
+
+/// A planned twiddle table holding `exp(-2πik/len)` for `k < len/2`.
+#[derive(Clone, Copy)]
+struct Slot<T: Float> {
+    len: usize,
+    table: Table<T>,
+}
+
+#[derive(Clone, Copy)]
+enum Table<T: Float> {
+    /// Starts at this offset of the planner's twiddle array.
+    Owned(usize),
+    /// Borrowed from a compile-time [`TwiddleTable`].
+    Static(&'static [Complex<T>]),
+}
+
+impl<T: Float> Table<T> {
+    fn resolve(self, owned: &[Complex<T>]) -> &[Complex<T>] {
+        match self {
+            Table::Owned(offset) => &owned[offset..],
+            Table::Static(table) => table,
+        }
+    }
+}
+
+/// FFT planner with fixed-capacity twiddle and scratch storage.
+///
+/// - `MAX_N`: largest complex or real transform length, and the total
+///   number of twiddles shared by all tables;
+/// - `SLOTS`: number of distinct twiddle tables.
+pub struct StaticPlanner<T: Float, const MAX_N: usize, const SLOTS: usize> {
+    twiddles: [Complex<T>; MAX_N],
+    scratch: [Complex<T>; MAX_N],
+    slots: [Option<Slot<T>>; SLOTS],
+    /// Number of twiddles in use.
+    used: usize,
+}
+
+impl<T: Float, const MAX_N: usize, const SLOTS: usize> Default for StaticPlanner<T, MAX_N, SLOTS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, const MAX_N: usize, const SLOTS: usize> StaticPlanner<T, MAX_N, SLOTS> {
+    /// Create an empty planner. The arrays are built on the stack; use
+    /// [`init`](Self::init) to construct it in place instead.
+    pub fn new() -> Self {
+        Self {
+            twiddles: [Complex::zero(); MAX_N],
+            scratch: [Complex::zero(); MAX_N],
+            slots: [None; SLOTS],
+            used: 0,
+        }
+    }
+
+    /// Initialize an empty planner in caller-provided storage, typically a
+    /// `static`, without copying it through the stack.
+    pub fn init(storage: &mut MaybeUninit<Self>) -> &mut Self {
+        let p = storage.as_mut_ptr();
+        // SAFETY: every field is written before `assume_init_mut`, through
+        // raw pointers that never read the uninitialized memory.
+        unsafe {
+            let twiddles = addr_of_mut!((*p).twiddles) as *mut Complex<T>;
+            let scratch = addr_of_mut!((*p).scratch) as *mut Complex<T>;
+            for i in 0..MAX_N {
+                twiddles.add(i).write(Complex::zero());
+                scratch.add(i).write(Complex::zero());
+            }
+            addr_of_mut!((*p).slots).write([None; SLOTS]);
+            addr_of_mut!((*p).used).write(0);
+            storage.assume_init_mut()
+        }
+    }
+
+    /// Make sure a twiddle table covering length `n` exists.
+    ///
+    /// # Errors
+    ///
+    /// - [`FftError::EmptyInput`] if `n == 0`;
+    /// - [`FftError::NonPowerOfTwoNoStd`] if `n` is not a power of two;
+    /// - [`FftError::MismatchedLengths`] if `n > MAX_N`;
+    /// - [`FftError::InvalidValue`] if no slot or twiddle space is left.
+    pub fn plan(&mut self, n: usize) -> Result<(), FftError> {
+        self.table(n).map(|_| ())
+    }
+
+    /// Serve length `N` and the power-of-two lengths dividing it from a
+    /// compile-time table in read-only memory, without using twiddle
+    /// storage. Only the first `N/2` factors are read.
+    ///
+    /// ```
+    /// use kofft::static_planner::StaticPlanner;
+    ///
+    /// let mut planner = StaticPlanner::<f32, 4096, 1>::new();
+    /// planner.plan_with_table(kofft::twiddles!(f32, 4096)).unwrap();
+    /// assert_eq!(planner.free_twiddles(), 4096);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - [`FftError::NonPowerOfTwoNoStd`] if `N` is not a power of two;
+    /// - [`FftError::InvalidValue`] if the table's period is not `N`, or no
+    ///   slot is left.
+    pub fn plan_with_table<const N: usize>(
+        &mut self,
+        table: &'static TwiddleTable<T, N>,
+    ) -> Result<(), FftError> {
+        if !N.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        if table.period() != N {
+            return Err(FftError::InvalidValue);
+        }
+        if self.slots.iter().flatten().any(|s| s.len % N == 0) {
+            return Ok(());
+        }
+        let free = self.slots.iter_mut().find(|s| s.is_none());
+        let free = free.ok_or(FftError::InvalidValue)?;
+        *free = Some(Slot {
+            len: N,
+            table: Table::Static(table.half()),
+        });
+        Ok(())
+    }
+
+    /// Lengths of the planned twiddle tables.
+    pub fn planned(&self) -> impl Iterator<Item = usize> + '_ {
+        self.slots.iter().flatten().map(|s| s.len)
+    }
+
+    /// Twiddles still available for new tables.
+    pub fn free_twiddles(&self) -> usize {
+        MAX_N - self.used
+    }
+
+    /// Table and stride serving length `n`, planning one if needed.
+    fn table(&mut self, n: usize) -> Result<(Table<T>, usize), FftError> {
+        if n == 0 {
+            return Err(FftError::EmptyInput);
+        }
+        if !n.is_power_of_two() {
+            return Err(FftError::NonPowerOfTwoNoStd);
+        }
+        if n > MAX_N {
+            return Err(FftError::MismatchedLengths);
+        }
+        if n <= 2 {
+            return Ok((Table::Owned(0), 1));
+        }
+        if let Some(s) = self.slots.iter().flatten().find(|s| s.len % n == 0) {
+            return Ok((s.table, s.len / n));
+        }
+        let half = n / 2;
+        let free = self.slots.iter().position(Option::is_none);
+        let (Some(free), true) = (free, self.used + half <= MAX_N) else {
+            return Err(FftError::InvalidValue);
+        };
+        let offset = self.used;
+        for (k, w) in self.twiddles[offset..offset + half].iter_mut().enumerate() {
+            // Same f64 evaluation as `FftPlanner::get_twiddles`.
+            let angle = -2.0 * core::f64::consts::PI * k as f64 / n as f64;
+            let (s, c) = libm::sincos(angle);
+            *w = Complex::new(T::from_f64(c), T::from_f64(s));
+        }
+        self.used += half;
+        self.slots[free] = Some(Slot {
+            len: n,
+            table: Table::Owned(offset),
+        });
+        Ok((Table::Owned(offset), 1))
+    }
+
+    /// In-place forward FFT of a power-of-two `data` of length `<= MAX_N`.
+    pub fn fft(&mut self, data: &mut [Complex<T>]) -> Result<(), FftError> {
+        let (table, stride) = self.table(data.len())?;
+        transform(
+            data,
+            &mut self.scratch,
+            table.resolve(&self.twiddles),
+            stride,
+        );
+        Ok(())
+    }
+
+    /// In-place inverse FFT, scaled by `1/n` like
+    /// [`FftImpl::ifft`](crate::fft::FftImpl::ifft).
+    pub fn ifft(&mut self, data: &mut [Complex<T>]) -> Result<(), FftError> {
+        let (table, stride) = self.table(data.len())?;
+        inverse(
+            data,
+            &mut self.scratch,
+            table.resolve(&self.twiddles),
+            stride,
+        );
+        Ok(())
+    }
+
+    /// Real-to-complex FFT of `input` (power-of-two length `n >= 2`) into
+    /// `output` (length `n/2 + 1`).
+    pub fn rfft(&mut self, input: &[T], output: &mut [Complex<T>]) -> Result<(), FftError> {
+        let n = input.len();
+        if n == 1 {
+            return Err(FftError::InvalidValue);
+        }
+        let m = n / 2;
+        if n > 0 && output.len() != m + 1 {
+            return Err(FftError::MismatchedLengths);
+        }
+        let (table, stride) = self.table(n)?;
+        for (o, pair) in output.iter_mut().zip(input.chunks_exact(2)) {
+            *o = Complex::new(pair[0], pair[1]);
+        }
+        let twiddles = table.resolve(&self.twiddles);
+        let (z, rest) = self.scratch.split_at_mut(m);
+        transform(&mut output[..m], rest, twiddles, 2 * stride);
+        z.copy_from_slice(&output[..m]);
+        pack_spectrum(z, output, twiddles, stride);
+        Ok(())
+    }
+
+    /// Complex-to-real inverse of [`rfft`](Self::rfft), scaled by `1/n`.
+    pub fn irfft(&mut self, input: &[Complex<T>], output: &mut [T]) -> Result<(), FftError> {
+        let n = output.len();
+        if n == 1 {
+            return Err(FftError::InvalidValue);
+        }
+        let m = n / 2;
+        if n > 0 && input.len() != m + 1 {
+            return Err(FftError::MismatchedLengths);
+        }
+        let (table, stride) = self.table(n)?;
+        let twiddles = table.resolve(&self.twiddles);
+        let (z, rest) = self.scratch.split_at_mut(m);
+        unpack_spectrum(input, z, twiddles, stride);
+        // The half-length inverse divides by `m`; unpacking already halved.
+        inverse(z, rest, twiddles, 2 * stride);
+        for (pair, c) in output.chunks_exact_mut(2).zip(z.iter()) {
+            pair[0] = c.re;
+            pair[1] = c.im;
+        }
+        Ok(())
+    }
+
+    /// One STFT frame: window `signal[start..]` (zero padded past the end)
+    /// into `frame_out` and transform it, as [`stft::frame`](crate::stft::frame).
+    pub fn stft_frame(
+        &mut self,
+        signal: &[T],
+        window: &[T],
+        start: usize,
+        frame_out: &mut [Complex<T>],
+    ) -> Result<(), FftError> {
+        if frame_out.len() != window.len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        for (i, (o, &w)) in frame_out.iter_mut().zip(window).enumerate() {
+            let x = signal.get(start + i).map_or(T::zero(), |&x| x * w);
+            *o = Complex::new(x, T::zero());
+        }
+        self.fft(frame_out)
+    }
+
+    /// Inverse-transform `frame` and overlap-add it, windowed, into
+    /// `output[start..]`, as [`stft::inverse_frame`](crate::stft::inverse_frame).
+    pub fn istft_frame(
+        &mut self,
+        frame: &mut [Complex<T>],
+        window: &[T],
+        start: usize,
+        output: &mut [T],
+    ) -> Result<(), FftError> {
+        if frame.len() != window.len() {
+            return Err(FftError::MismatchedLengths);
+        }
+        self.ifft(frame)?;
+        for (o, (c, &w)) in output.iter_mut().skip(start).zip(frame.iter().zip(window)) {
+            *o = *o + c.re * w;
+        }
+        Ok(())
+    }
+}
+
+/// Forward transform with the kernels `ScalarFftImpl` uses for each size.
+fn transform<T: Float>(
+    data: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+    stride: usize,
+) {
+    match data.len() {
+        1 => {}
+        2 => fft2(data),
+        4 => fft4(data),
+        8 => fft8(data),
+        16 => fft16(data),
+        n => stockham_radix2(data, &mut scratch[..n], twiddles, stride),
+    }
+}
+
+fn inverse<T: Float>(
+    data: &mut [Complex<T>],
+    scratch: &mut [Complex<T>],
+    twiddles: &[Complex<T>],
+    stride: usize,
+) {
+    for c in data.iter_mut() {
+        c.im = -c.im;
+    }
+    transform(data, scratch, twiddles, stride);
+    let scale = T::one() / T::from_f64(data.len() as f64);
+    for c in data.iter_mut() {
+        *c = Complex::new(c.re * scale, -c.im * scale);
+    }
+}
@@ -61,7 +61,7 @@
 //! ```
 
 extern crate alloc;
-use crate::fft::{Complex32, FftError, FftImpl};
+use crate::fft::{Complex32, FftError, FftImpl, SharedFftPlan};
 use alloc::vec;
 
 /// Compute the STFT of a real-valued signal.
@@ -214,7 +214,8 @@ impl<'a, Fft: crate::fft::FftImpl<f32>> StftStream<'a, Fft> {
 /// * `window` - analysis window
 /// * `hop_size` - hop size between adjacent frames
 /// * `output` - pre-allocated buffer for FFT frames
-/// * `fft` - FFT implementation to reuse cached plans
+/// * `fft` - unused; kept for API compatibility. All workers share one
+///   [`SharedFftPlan`], see [`parallel_with_plan`].
 ///
 /// Returns [`FftError::InvalidHopSize`] if `hop_size` is zero.
 ///
@@ -236,17 +237,43 @@ pub fn parallel<Fft: FftImpl<f32>>(
     output: &mut [alloc::vec::Vec<Complex32>],
     fft: &Fft,
 ) -> Result<(), FftError> {
-    use crate::fft::ScalarFftImpl;
-    use rayon::prelude::*;
     let _ = fft;
+    if hop_size == 0 {
+        return Err(FftError::InvalidHopSize);
+    }
+    if output.is_empty() {
+        return Ok(());
+    }
+    let plan = SharedFftPlan::new(window.len())?;
+    parallel_with_plan(signal, window, hop_size, output, &plan)
+}
+
+#[cfg(feature = "parallel")]
+/// Parallel STFT that runs every frame through one shared `plan`.
+///
+/// `plan` must have the window's length. Frames are spread over
+/// [`plan.parallelism()`](SharedFftPlan::parallelism); each worker allocates
+/// its own scratch once and reuses it for all frames it processes.
+pub fn parallel_with_plan(
+    signal: &[f32],
+    window: &[f32],
+    hop_size: usize,
+    output: &mut [alloc::vec::Vec<Complex32>],
+    plan: &SharedFftPlan<f32>,
+) -> Result<(), FftError> {
     if hop_size == 0 {
         return Err(FftError::InvalidHopSize);
     }
     let win_len = window.len();
-    output
-        .par_iter_mut()
-        .enumerate()
-        .try_for_each(|(frame_idx, frame)| {
+    if plan.size() != win_len {
+        return Err(FftError::MismatchedLengths);
+    }
+    plan.parallelism().try_for_each_chunk_mut(
+        output,
+        1,
+        || alloc::vec![Complex32::zero(); plan.scratch_len()],
+        |scratch, frame_idx, frames| {
+            let frame = &mut frames[0];
             let start = frame_idx * hop_size;
             frame.clear();
             for i in 0..win_len {
@@ -257,9 +284,9 @@ pub fn parallel<Fft: FftImpl<f32>>(
                 };
                 frame.push(Complex32::new(x, 0.0));
             }
-            let fft_local = ScalarFftImpl::<f32>::default();
-            fft_local.fft(frame)
-        })
+            plan.fft(frame, scratch)
+        },
+    )
 }
 
 #[cfg(feature = "parallel")]
@@ -271,7 +298,8 @@ pub fn parallel<Fft: FftImpl<f32>>(
 /// * `window` - synthesis window
 /// * `hop_size` - hop size between frames
 /// * `output` - buffer to receive the reconstructed signal
-/// * `fft` - FFT implementation to reuse cached plans
+/// * `fft` - unused; kept for API compatibility. All workers share one
+///   [`SharedFftPlan`], see [`inverse_parallel_with_plan`].
 ///
 /// Returns [`FftError::InvalidHopSize`] if `hop_size` is zero.
 ///
@@ -293,42 +321,72 @@ pub fn inverse_parallel<Fft: FftImpl<f32>>(
     output: &mut [f32],
     fft: &Fft,
 ) -> Result<(), FftError> {
-    use crate::fft::ScalarFftImpl;
-    use rayon::prelude::*;
     let _ = fft;
+    if hop_size == 0 {
+        return Err(FftError::InvalidHopSize);
+    }
+    if frames.is_empty() {
+        output.iter_mut().for_each(|x| *x = 0.0);
+        return Ok(());
+    }
+    let plan = SharedFftPlan::new(window.len())?;
+    inverse_parallel_with_plan(frames, window, hop_size, output, &plan)
+}
+
+#[cfg(feature = "parallel")]
+/// Parallel inverse STFT that runs every frame through one shared `plan`.
+///
+/// `plan` must have the window's length and every frame must have that
+/// length too. Frames are transformed on
+/// [`plan.parallelism()`](SharedFftPlan::parallelism) and overlap-added in
+/// order, so the result does not depend on the pool.
+pub fn inverse_parallel_with_plan(
+    frames: &[alloc::vec::Vec<Complex32>],
+    window: &[f32],
+    hop_size: usize,
+    output: &mut [f32],
+    plan: &SharedFftPlan<f32>,
+) -> Result<(), FftError> {
     if hop_size == 0 {
         return Err(FftError::InvalidHopSize);
     }
     let win_len = window.len();
-    type Accum = (usize, alloc::vec::Vec<f32>, alloc::vec::Vec<f32>);
-    type AccumResult = Result<alloc::vec::Vec<Accum>, FftError>;
-    let partials: AccumResult = frames
-        .par_iter()
-        .enumerate()
-        .map(|(frame_idx, frame)| {
-            let start = frame_idx * hop_size;
-            let mut time_buf = frame.clone();
-            let fft_local = ScalarFftImpl::<f32>::default();
-            fft_local.ifft(&mut time_buf)?;
-            let mut acc = alloc::vec::Vec::with_capacity(win_len);
-            acc.resize(win_len, 0.0);
-            let mut norm = alloc::vec::Vec::with_capacity(win_len);
-            norm.resize(win_len, 0.0);
+    if plan.size() != win_len {
+        return Err(FftError::MismatchedLengths);
+    }
+    // Windowed time-domain frames, computed in parallel and overlap-added
+    // in frame order below.
+    let mut partials = alloc::vec![0.0f32; frames.len() * win_len];
+    plan.parallelism().try_for_each_chunk_mut(
+        &mut partials,
+        win_len,
+        || {
+            (
+                alloc::vec![Complex32::zero(); win_len],
+                alloc::vec![Complex32::zero(); plan.scratch_len()],
+            )
+        },
+        |(time_buf, scratch), frame_idx, acc| {
+            let frame = &frames[frame_idx];
+            if frame.len() != win_len {
+                return Err(FftError::MismatchedLengths);
+            }
+            time_buf.copy_from_slice(frame);
+            plan.ifft(time_buf, scratch)?;
             for i in 0..win_len {
                 acc[i] = time_buf[i].re * window[i];
-                norm[i] = window[i] * window[i];
             }
-            Ok((start, acc, norm))
-        })
-        .collect();
-    let partials = partials?;
+            Ok(())
+        },
+    )?;
     let mut norm = alloc::vec::Vec::with_capacity(output.len());
     norm.resize(output.len(), 0.0);
-    for (start, acc_frame, norm_frame) in partials {
+    for (frame_idx, acc_frame) in partials.chunks_exact(win_len).enumerate() {
+        let start = frame_idx * hop_size;
         for i in 0..win_len {
             if start + i < output.len() {
                 output[start + i] += acc_frame[i];
-                norm[start + i] += norm_frame[i];
+                norm[start + i] += window[i] * window[i];
             }
         }
     }
@@ -371,6 +429,34 @@ pub fn frame<Fft: FftImpl<f32>>(
     fft.fft(frame_out)
 }
 
+/// [`frame`] with a precomputed plan and caller-supplied scratch; never
+/// allocates.
+///
+/// `plan` must have the window's length and `scratch` must hold at least
+/// [`SharedFftPlan::scratch_len`] samples.
+pub fn frame_with_scratch(
+    signal: &[f32],
+    window: &[f32],
+    start: usize,
+    frame_out: &mut [Complex32],
+    plan: &SharedFftPlan<f32>,
+    scratch: &mut [Complex32],
+) -> Result<(), FftError> {
+    let win_len = window.len();
+    if frame_out.len() != win_len {
+        return Err(FftError::MismatchedLengths);
+    }
+    for i in 0..win_len {
+        let x = if start + i < signal.len() {
+            signal[start + i] * window[i]
+        } else {
+            0.0
+        };
+        frame_out[i] = Complex32::new(x, 0.0);
+    }
+    plan.fft(frame_out, scratch)
+}
+
 /// Streaming, no_std, no-alloc ISTFT: process one frame at a time using fixed-size buffers.
 ///
 /// - `frame`: input FFT frame (length = window.len())
@@ -398,6 +484,29 @@ pub fn inverse_frame<Fft: FftImpl<f32>>(
     Ok(())
 }
 
+/// [`inverse_frame`] with a precomputed plan and caller-supplied scratch;
+/// never allocates.
+pub fn inverse_frame_with_scratch(
+    frame: &mut [Complex32],
+    window: &[f32],
+    start: usize,
+    output: &mut [f32],
+    plan: &SharedFftPlan<f32>,
+    scratch: &mut [Complex32],
+) -> Result<(), FftError> {
+    let win_len = window.len();
+    if frame.len() != win_len {
+        return Err(FftError::MismatchedLengths);
+    }
+    plan.ifft(frame, scratch)?;
+    for i in 0..win_len {
+        if start + i < output.len() {
+            output[start + i] += frame[i].re * window[i];
+        }
+    }
+    Ok(())
+}
+
 /// Streaming inverse STFT (ISTFT) helper implementing overlap-add with
 /// normalization.
 ///
@@ -0,0 +1,190 @@
+//! Compile-time twiddle tables.
+//!
+//! [`TwiddleTable<T, N>`] holds `exp(-2πik/N)` for `k = 0..N`, evaluated by a
+//! `const fn` in `f64` and rounded once to `T`. Declared as a `const` or
+//! `static`, or through the [`twiddles!`](crate::twiddles!) macro, the table
+//! is built by the compiler and placed in read-only data, so on flash-based
+//! MCUs it costs flash instead of RAM and no start-up time.
+//!
+//! Any `N` works. A radix-`r` stage of length `L` dividing `N` reads its
+//! twiddles `exp(-2πijk/L)` as `table.get(j·k·N/L)`, and the first half is
+//! the layout the planners use for power-of-two lengths:
+//! [`StaticPlanner::plan_with_table`](crate::static_planner::StaticPlanner::plan_with_table)
+//! borrows it in place, and `FftPlanner::load_twiddles` and
+//! `RfftPlanner::load_twiddles` seed their caches from it.
+//!
+//! ```
+//! use kofft::twiddles::TwiddleTable;
+//!
+//! const TW: TwiddleTable<f32, 12> = TwiddleTable::new();
+//! // Radix-3 twiddle exp(-2πi/3) of a 12-point table.
+//! let w = TW.get(4);
+//! assert!((w.re + 0.5).abs() < 1e-7);
+//!
+//! let big: &'static TwiddleTable<f64, 4096> = kofft::twiddles!(f64, 4096);
+//! assert_eq!(big.half().len(), 2048);
+//! ```
+
+use crate::num::{Complex, Float};
+
+/// `sin` and `cos` of `2π·k/period`, exact to `f64` rounding.
+///
+/// The angle is reduced to `[-π/4, π/4]` with integer arithmetic before a
+/// Taylor series, so large `k` loses no precision.
+pub(crate) const fn sin_cos_turns(k: u128, period: u128) -> (f64, f64) {
+    let k = k % period;
+    // Nearest quarter turn and the remainder in units of 1/(4·period) turns.
+    let quadrant = (8 * k + period) / (2 * period);
+    let rest = (4 * k) as i128 - (quadrant * period) as i128;
+    let y = core::f64::consts::FRAC_PI_2 * rest as f64 / period as f64;
+    let y2 = y * y;
+    let (mut s, mut c) = (y, 1.0);
+    let (mut ts, mut tc) = (y, 1.0);
+    let mut i = 1;
+    while i < 12 {
+        let j = 2.0 * i as f64;
+        ts *= -y2 / (j * (j + 1.0));
+        tc *= -y2 / ((j - 1.0) * j);
+        s += ts;
+        c += tc;
+        i += 1;
+    }
+    match quadrant % 4 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+/// `N` twiddle factors `exp(-2πik/period)`, `k = 0..N`, built at compile
+/// time. `period` is `N` unless the table was made with
+/// [`with_period`](TwiddleTable::with_period).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TwiddleTable<T: Float, const N: usize> {
+    period: usize,
+    table: [Complex<T>; N],
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Precisions a [`TwiddleTable`] can be evaluated in at compile time:
+/// `f32` and `f64`.
+pub trait ConstFloat: Float + sealed::Sealed {}
+
+impl ConstFloat for f32 {}
+impl ConstFloat for f64 {}
+
+/// Round `x` to `T` in a `const fn`, where `Float::from_f64` is unavailable.
+const fn round_to<T: ConstFloat>(x: f64) -> T {
+    union Pun<A: Copy, B: Copy> {
+        from: A,
+        to: B,
+    }
+    // SAFETY: `ConstFloat` is sealed, so `T` is `f32` or `f64` and its size
+    // tells which; both sides of the union are then the same type.
+    unsafe {
+        if core::mem::size_of::<T>() == 4 {
+            Pun::<f32, T> { from: x as f32 }.to
+        } else {
+            Pun::<f64, T> { from: x }.to
+        }
+    }
+}
+
+impl<T: ConstFloat, const N: usize> TwiddleTable<T, N> {
+    /// `exp(-2πik/N)` for `k = 0..N`.
+    pub const fn new() -> Self {
+        Self::with_period(N)
+    }
+
+    /// `exp(-2πik/period)` for `k = 0..N`, e.g. the first `N` factors of a
+    /// longer transform or the `exp(-iπk/N)` real-FFT pack table
+    /// (`period = 2N`).
+    ///
+    /// # Panics
+    ///
+    /// If `period == 0`; in a `const` this is a compile error.
+    pub const fn with_period(period: usize) -> Self {
+        assert!(period > 0, "twiddle period must be non-zero");
+        let zero = round_to::<T>(0.0);
+        let mut table = [Complex { re: zero, im: zero }; N];
+        let mut k = 0;
+        while k < N {
+            let (s, c) = sin_cos_turns(k as u128, period as u128);
+            table[k] = Complex {
+                re: round_to(c),
+                im: round_to(-s),
+            };
+            k += 1;
+        }
+        Self { period, table }
+    }
+}
+
+impl<T: ConstFloat, const N: usize> Default for TwiddleTable<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, const N: usize> TwiddleTable<T, N> {
+    /// Period of the factors: `table[k] = exp(-2πik/period)`.
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Number of factors, `N`.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// `exp(-2πik/period)`, with `k` reduced modulo `N`.
+    pub const fn get(&self, k: usize) -> Complex<T> {
+        self.table[k % N]
+    }
+
+    /// All `N` factors.
+    pub const fn as_slice(&self) -> &[Complex<T>] {
+        &self.table
+    }
+
+    /// The first `N/2` factors: the per-length layout of the planners'
+    /// power-of-two twiddle caches.
+    pub const fn half(&self) -> &[Complex<T>] {
+        self.table.split_at(N / 2).0
+    }
+}
+
+impl<T: Float, const N: usize> AsRef<[Complex<T>]> for TwiddleTable<T, N> {
+    fn as_ref(&self) -> &[Complex<T>] {
+        &self.table
+    }
+}
+
+/// A `&'static` [`TwiddleTable`] built at compile time and stored in
+/// read-only data.
+///
+/// `twiddles!(T, N)` holds `exp(-2πik/N)` and `twiddles!(T, N, period)`
+/// holds `exp(-2πik/period)`, for `k = 0..N` and `T` either `f32` or `f64` ([`ConstFloat`]).
+///
+/// ```
+/// let tw = kofft::twiddles!(f32, 4096);
+/// assert_eq!(tw.get(1024).im, -1.0);
+/// ```
+#[macro_export]
+macro_rules! twiddles {
+    ($t:ty, $n:expr) => {
+        $crate::twiddles!($t, $n, $n)
+    };
+    ($t:ty, $n:expr, $period:expr) => {{
+        static TABLE: $crate::twiddles::TwiddleTable<$t, { $n }> =
+            $crate::twiddles::TwiddleTable::<$t, { $n }>::with_period($period);
+        &TABLE
+    }};
+}
@@ -5,9 +5,13 @@
 #![allow(clippy::excessive_precision)]
 
 extern crate alloc;
+#[cfg(feature = "parallel")]
+use crate::parallel::Parallelism;
 use alloc::vec;
 use alloc::vec::Vec;
 
+pub use crate::mcu::wavelet::{haar_forward_inplace_stack, haar_inverse_inplace_stack};
+
 /// Forward Haar wavelet transform (single level)
 pub fn haar_forward(input: &[f32]) -> (Vec<f32>, Vec<f32>) {
     let n = input.len() / 2;
@@ -50,6 +54,85 @@ pub fn batch_inverse(avgs: &[Vec<f32>], diffs: &[Vec<f32>]) -> Vec<Vec<f32>> {
         .collect()
 }
 
+/// Settings for the parallel Haar batch APIs.
+///
+/// The Haar transform needs no precomputed state, so the plan only carries
+/// the [`Parallelism`] its batches are spread over, the way
+/// [`SharedFftPlan`](crate::fft::SharedFftPlan) does for FFT batches.
+#[cfg(feature = "parallel")]
+#[derive(Clone, Default)]
+pub struct HaarPlan {
+    parallelism: Parallelism,
+}
+
+#[cfg(feature = "parallel")]
+impl HaarPlan {
+    /// Plan on Rayon's global pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spread the inputs of batch APIs given this plan over `parallelism`
+    /// instead of Rayon's global pool.
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Workers used by the batch APIs this plan is passed to.
+    pub fn parallelism(&self) -> &Parallelism {
+        &self.parallelism
+    }
+}
+
+/// Parallel [`batch_forward`]: contiguous runs of inputs are spread over
+/// [`plan.parallelism()`](HaarPlan::parallelism). The output is
+/// bit-identical to [`batch_forward`].
+#[cfg(feature = "parallel")]
+pub fn batch_forward_parallel(
+    plan: &HaarPlan,
+    inputs: &[Vec<f32>],
+) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+    let par = plan.parallelism();
+    let mut out = vec![(Vec::new(), Vec::new()); inputs.len()];
+    let chunk = par.batch_chunk(inputs.len());
+    par.for_each_chunk_mut(
+        &mut out,
+        chunk,
+        || (),
+        |_, i, run| {
+            for (slot, input) in run.iter_mut().zip(&inputs[i * chunk..]) {
+                *slot = haar_forward(input);
+            }
+        },
+    );
+    out.into_iter().unzip()
+}
+
+/// Parallel [`batch_inverse`]; see [`batch_forward_parallel`].
+#[cfg(feature = "parallel")]
+pub fn batch_inverse_parallel(
+    plan: &HaarPlan,
+    avgs: &[Vec<f32>],
+    diffs: &[Vec<f32>],
+) -> Vec<Vec<f32>> {
+    let par = plan.parallelism();
+    let mut out = vec![Vec::new(); avgs.len().min(diffs.len())];
+    let chunk = par.batch_chunk(out.len());
+    par.for_each_chunk_mut(
+        &mut out,
+        chunk,
+        || (),
+        |_, i, run| {
+            let pairs = avgs[i * chunk..].iter().zip(&diffs[i * chunk..]);
+            for (slot, (a, d)) in run.iter_mut().zip(pairs) {
+                *slot = haar_inverse(a, d);
+            }
+        },
+    );
+    out
+}
+
 /// Multi-level decomposition using a single-level forward function.
 pub fn multi_level_forward<F>(input: &[f32], levels: usize, forward: F) -> (Vec<f32>, Vec<Vec<f32>>)
 where
@@ -116,38 +199,6 @@ where
         .collect()
 }
 
-/// MCU/stack-only, const-generic, in-place Haar wavelet forward (N must be even, no heap)
-/// Output buffers avg and diff must be of length N/2.
-pub fn haar_forward_inplace_stack<const N: usize>(
-    input: &[f32; N],
-    avg: &mut [f32],
-    diff: &mut [f32],
-) {
-    let n = N / 2;
-    assert!(
-        avg.len() == n && diff.len() == n,
-        "Output buffers must be of length N/2"
-    );
-    for i in 0..n {
-        avg[i] = (input[2 * i] + input[2 * i + 1]) / 2.0;
-        diff[i] = (input[2 * i] - input[2 * i + 1]) / 2.0;
-    }
-}
-
-/// MCU/stack-only, const-generic, in-place Haar wavelet inverse (N must be even, no heap)
-/// Output buffer out must be of length 2*N.
-pub fn haar_inverse_inplace_stack<const N: usize>(avg: &[f32], diff: &[f32], out: &mut [f32]) {
-    let n = avg.len();
-    assert!(
-        diff.len() == n && out.len() == 2 * n,
-        "Output buffer must be of length 2*N"
-    );
-    for i in 0..n {
-        out[2 * i] = avg[i] + diff[i];
-        out[2 * i + 1] = avg[i] - diff[i];
-    }
-}
-
 /// Daubechies-2 (db2) wavelet transform (single level)
 /// Note: For short signals, perfect roundtrip is not guaranteed due to mathematical boundary effects.
 /// This is a property of the db2 wavelet, not a bug in the implementation.
@@ -1,24 +1,17 @@
 //! Common window functions for STFT and DSP
 
+use alloc::vec;
+use alloc::vec::Vec;
 use core::f32::consts::PI;
+use core::fmt;
+use core::str::FromStr;
 
-#[cfg(not(feature = "std"))]
-use crate::num::Float;
-use libm::sqrtf;
+use crate::fft::SharedFftPlan;
+use crate::num::{Complex, Float};
+use crate::FftError;
+use libm::{cos, fabs, log10, sin, sqrt};
 
-fn bessel0(x: f32) -> f32 {
-    // Approximate I0(x) using a series expansion
-    let mut sum = 1.0;
-    let y = x * x / 4.0;
-    let mut t = y;
-    let mut k = 1.0;
-    for n in 1..20 {
-        k *= n as f32;
-        sum += t / (k * k);
-        t *= y;
-    }
-    sum
-}
+pub use crate::mcu::window::{blackman_inplace_stack, hamming_inplace_stack, hann_inplace_stack};
 
 /// Generate a Hann window of length `len`.
 pub fn hann(len: usize) -> alloc::vec::Vec<f32> {
@@ -47,54 +40,337 @@ pub fn blackman(len: usize) -> alloc::vec::Vec<f32> {
         .collect()
 }
 
-/// Generate a Kaiser window of length `len` and shape parameter `beta`.
-#[cfg(feature = "std")]
+/// Generate a symmetric Kaiser window of length `len` and shape parameter
+/// `beta`: [`WindowSpec::Kaiser`] with [`Symmetry::Symmetric`].
 pub fn kaiser(len: usize, beta: f32) -> alloc::vec::Vec<f32> {
-    let denom = bessel0(beta);
-    let m = (len - 1) as f32 / 2.0;
-    (0..len)
-        .map(|i| {
-            let r = (i as f32 - m) / m;
-            bessel0(beta * sqrtf(1.0 - r * r)) / denom
-        })
-        .collect()
+    WindowSpec::Kaiser { beta }.generate(len, Symmetry::Symmetric)
 }
 
-#[cfg(not(feature = "std"))]
-pub fn kaiser(len: usize, beta: f32) -> alloc::vec::Vec<f32> {
-    let denom = bessel0(beta);
-    let m = (len - 1) as f32 / 2.0;
-    (0..len)
-        .map(|i| {
-            let r = (i as f32 - m) / m;
-            bessel0(beta * sqrtf(1.0 - r * r)) / denom
-        })
-        .collect()
+/// Periodic or symmetric form of a window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Symmetry {
+    /// The first `len` samples of the `len + 1` symmetric window, as used
+    /// for spectral analysis: the window tiles with period `len`.
+    #[default]
+    Periodic,
+    /// Symmetric about the centre, as used for FIR design.
+    Symmetric,
+}
+
+/// A window function and its parameters, as a value.
+///
+/// Specs parse from strings such as `"hann"`, `"kaiser:8.6"` or
+/// `"tukey:0.25"`; [`Display`](fmt::Display) writes the same form back. The
+/// shapes follow `scipy.signal.windows`. The periodic Hann, Hamming and
+/// Blackman windows match [`hann`], [`hamming`] and [`blackman`].
+///
+/// ```
+/// use kofft::window::{Symmetry, WindowSpec};
+///
+/// let spec: WindowSpec = "kaiser:8.6".parse().unwrap();
+/// assert_eq!(spec, WindowSpec::Kaiser { beta: 8.6 });
+/// let mut frame = vec![1.0f64; 1024];
+/// spec.apply(&mut frame, Symmetry::Periodic);
+///
+/// // correct a bin-centred tone's amplitude for the window
+/// let m = WindowSpec::Hann.metrics(1024, Symmetry::Periodic).unwrap();
+/// assert!((m.coherent_gain - 0.5).abs() < 1e-9);
+/// assert!((m.enbw - 1.5).abs() < 1e-9);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowSpec {
+    /// Boxcar: all ones.
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+    /// Kaiser window with shape `beta` (≥ 0); about `8.6` matches
+    /// Blackman-Harris sidelobes.
+    Kaiser {
+        beta: f32,
+    },
+    /// Tapered cosine: `alpha` (in `[0, 1]`) of the window is a cosine
+    /// taper, from rectangular at `0` to Hann at `1`.
+    Tukey {
+        alpha: f32,
+    },
+    /// Triangular, with zero end points.
+    Bartlett,
+    Bohman,
+    /// Nuttall's 4-term Blackman-Harris window.
+    Nuttall,
+}
+
+impl WindowSpec {
+    /// The window as `len` samples.
+    pub fn generate(&self, len: usize, symmetry: Symmetry) -> Vec<f32> {
+        let mut out = vec![0.0; len];
+        self.fill(&mut out, symmetry);
+        out
+    }
+
+    /// Write the window into `out`, sized by `out.len()`.
+    pub fn fill(&self, out: &mut [f32], symmetry: Symmetry) {
+        let len = out.len();
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = self.sample(i, len, symmetry) as f32;
+        }
+    }
+
+    /// Multiply `data` by the window in place, sized by `data.len()`.
+    pub fn apply<T: Float>(&self, data: &mut [T], symmetry: Symmetry) {
+        let len = data.len();
+        for (i, x) in data.iter_mut().enumerate() {
+            *x = *x * T::from_f64(self.sample(i, len, symmetry));
+        }
+    }
+
+    /// [`metrics`] of the `len`-sample window.
+    pub fn metrics(&self, len: usize, symmetry: Symmetry) -> Result<WindowMetrics, FftError> {
+        let w: Vec<f64> = (0..len).map(|i| self.sample(i, len, symmetry)).collect();
+        response_metrics(&w)
+    }
+
+    /// Sample `i` of the `len`-sample window.
+    fn sample(&self, i: usize, len: usize, symmetry: Symmetry) -> f64 {
+        let span = match symmetry {
+            Symmetry::Periodic => len,
+            Symmetry::Symmetric => len - 1,
+        };
+        if span == 0 {
+            return 1.0;
+        }
+        // position across the symmetric window, 0 to 1
+        let t = i as f64 / span as f64;
+        let tau = 2.0 * core::f64::consts::PI;
+        match *self {
+            WindowSpec::Rectangular => 1.0,
+            WindowSpec::Hann => 0.5 - 0.5 * cos(tau * t),
+            WindowSpec::Hamming => 0.54 - 0.46 * cos(tau * t),
+            WindowSpec::Blackman => 0.42 - 0.5 * cos(tau * t) + 0.08 * cos(2.0 * tau * t),
+            WindowSpec::Kaiser { beta } => {
+                let beta = beta as f64;
+                let r = 2.0 * t - 1.0;
+                bessel0(beta * sqrt((1.0 - r * r).max(0.0))) / bessel0(beta)
+            }
+            WindowSpec::Tukey { alpha } => {
+                let alpha = alpha as f64;
+                if t < alpha / 2.0 {
+                    0.5 * (1.0 + cos(core::f64::consts::PI * (2.0 * t / alpha - 1.0)))
+                } else if t > 1.0 - alpha / 2.0 {
+                    0.5 * (1.0 + cos(core::f64::consts::PI * (2.0 * (t - 1.0) / alpha + 1.0)))
+                } else {
+                    1.0
+                }
+            }
+            WindowSpec::Bartlett => 1.0 - fabs(2.0 * t - 1.0),
+            WindowSpec::Bohman => {
+                let x = fabs(2.0 * t - 1.0);
+                let pi = core::f64::consts::PI;
+                (1.0 - x) * cos(pi * x) + sin(pi * x) / pi
+            }
+            WindowSpec::Nuttall => {
+                0.355768 - 0.487396 * cos(tau * t) + 0.144232 * cos(2.0 * tau * t)
+                    - 0.012604 * cos(3.0 * tau * t)
+            }
+        }
+    }
 }
 
-/// MCU/stack-only, const-generic, in-place Hann window (no heap)
-pub fn hann_inplace_stack<const N: usize>(out: &mut [f32; N]) {
-    for (i, x) in out.iter_mut().enumerate() {
-        *x = 0.5 - 0.5 * (2.0 * PI * i as f32 / N as f32).cos();
+impl FromStr for WindowSpec {
+    type Err = FftError;
+
+    /// Parse `name` or `name:parameter`, ignoring case. Kaiser needs its
+    /// `beta`; Tukey's `alpha` defaults to `0.5`. Returns
+    /// [`FftError::InvalidValue`] for unknown names, stray or missing
+    /// parameters and parameters out of range.
+    fn from_str(s: &str) -> Result<Self, FftError> {
+        let (name, param) = match s.trim().split_once(':') {
+            Some((name, param)) => {
+                let param: f32 = param.trim().parse().map_err(|_| FftError::InvalidValue)?;
+                (name.trim(), Some(param))
+            }
+            None => (s.trim(), None),
+        };
+        let is = |n: &str| name.eq_ignore_ascii_case(n);
+        let spec = match param {
+            _ if is("kaiser") => WindowSpec::Kaiser {
+                beta: param.ok_or(FftError::InvalidValue)?,
+            },
+            _ if is("tukey") => WindowSpec::Tukey {
+                alpha: param.unwrap_or(0.5),
+            },
+            Some(_) => return Err(FftError::InvalidValue),
+            None if is("rectangular") || is("rect") || is("boxcar") => WindowSpec::Rectangular,
+            None if is("hann") || is("hanning") => WindowSpec::Hann,
+            None if is("hamming") => WindowSpec::Hamming,
+            None if is("blackman") => WindowSpec::Blackman,
+            None if is("bartlett") || is("triangular") => WindowSpec::Bartlett,
+            None if is("bohman") => WindowSpec::Bohman,
+            None if is("nuttall") => WindowSpec::Nuttall,
+            None => return Err(FftError::InvalidValue),
+        };
+        match spec {
+            WindowSpec::Kaiser { beta } if !(beta >= 0.0 && beta.is_finite()) => {
+                Err(FftError::InvalidValue)
+            }
+            WindowSpec::Tukey { alpha } if !(0.0..=1.0).contains(&alpha) => {
+                Err(FftError::InvalidValue)
+            }
+            spec => Ok(spec),
+        }
     }
 }
 
-/// MCU/stack-only, const-generic, in-place Hamming window (no heap)
-pub fn hamming_inplace_stack<const N: usize>(out: &mut [f32; N]) {
-    for (i, x) in out.iter_mut().enumerate() {
-        *x = 0.54 - 0.46 * (2.0 * PI * i as f32 / N as f32).cos();
+impl fmt::Display for WindowSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowSpec::Rectangular => f.write_str("rectangular"),
+            WindowSpec::Hann => f.write_str("hann"),
+            WindowSpec::Hamming => f.write_str("hamming"),
+            WindowSpec::Blackman => f.write_str("blackman"),
+            WindowSpec::Kaiser { beta } => write!(f, "kaiser:{beta}"),
+            WindowSpec::Tukey { alpha } => write!(f, "tukey:{alpha}"),
+            WindowSpec::Bartlett => f.write_str("bartlett"),
+            WindowSpec::Bohman => f.write_str("bohman"),
+            WindowSpec::Nuttall => f.write_str("nuttall"),
+        }
     }
 }
 
-/// MCU/stack-only, const-generic, in-place Blackman window (no heap)
-pub fn blackman_inplace_stack<const N: usize>(out: &mut [f32; N]) {
-    let a0 = 0.42;
-    let a1 = 0.5;
-    let a2 = 0.08;
-    for (i, out_val) in out.iter_mut().enumerate() {
-        let x = i as f32 / N as f32;
-        *out_val = a0 - a1 * (2.0 * PI * x).cos() + a2 * (4.0 * PI * x).cos();
+/// Spectral figures of merit of a window, after Harris (1978). Frequencies
+/// are in bins of `fs / len`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowMetrics {
+    /// Mean sample: the amplitude a bin-centred sine reads, relative to a
+    /// rectangular window. Divide by it to correct amplitudes.
+    pub coherent_gain: f64,
+    /// Equivalent noise bandwidth, in bins. Divide noise power by it to
+    /// correct densities.
+    pub enbw: f64,
+    /// Amplitude loss for a sine midway between two bins, in dB (positive).
+    pub scalloping_loss: f64,
+    /// Highest sidelobe relative to the main lobe, in dB (negative);
+    /// `-inf` if the response has no sidelobes.
+    pub highest_sidelobe: f64,
+    /// Full main lobe width where the response is 6 dB (half amplitude)
+    /// down, in bins.
+    pub bandwidth_6db: f64,
+}
+
+/// Frequency-domain oversampling used to locate the lobes of a window's
+/// response before refining them.
+const METRICS_OVERSAMPLE: usize = 16;
+
+/// [`WindowMetrics`] of an arbitrary window.
+///
+/// Returns [`FftError::EmptyInput`] for an empty window and
+/// [`FftError::InvalidValue`] if its samples sum to zero or are not finite.
+pub fn metrics(window: &[f32]) -> Result<WindowMetrics, FftError> {
+    let w: Vec<f64> = window.iter().map(|&v| v as f64).collect();
+    response_metrics(&w)
+}
+
+fn response_metrics(w: &[f64]) -> Result<WindowMetrics, FftError> {
+    if w.is_empty() {
+        return Err(FftError::EmptyInput);
+    }
+    let n = w.len() as f64;
+    let sum: f64 = w.iter().sum();
+    let energy: f64 = w.iter().map(|v| v * v).sum();
+    if sum == 0.0 || !sum.is_finite() || !energy.is_finite() {
+        return Err(FftError::InvalidValue);
     }
+    // |W(f)| / |W(0)| at `f` bins
+    let response = |f: f64| {
+        let step = 2.0 * core::f64::consts::PI * f / n;
+        let (re, im) = w.iter().enumerate().fold((0.0, 0.0), |(re, im), (k, &v)| {
+            let a = step * k as f64;
+            (re + v * cos(a), im - v * sin(a))
+        });
+        sqrt(re * re + im * im) / fabs(sum)
+    };
+
+    // coarse response on [0, len/2] bins
+    let size = (w.len() * METRICS_OVERSAMPLE).next_power_of_two();
+    let plan = SharedFftPlan::<f64>::new(size)?;
+    let mut spectrum = vec![Complex::zero(); size];
+    for (s, &v) in spectrum.iter_mut().zip(w) {
+        *s = Complex::new(v, 0.0);
+    }
+    let mut scratch = vec![Complex::zero(); plan.scratch_len()];
+    plan.fft(&mut spectrum, &mut scratch)?;
+    let coarse: Vec<f64> = spectrum[..=size / 2]
+        .iter()
+        .map(|c| sqrt(c.re * c.re + c.im * c.im) / fabs(sum))
+        .collect();
+    let bins = |k: usize| k as f64 * n / size as f64;
+
+    let half = coarse.iter().position(|&m| m < 0.5);
+    let bandwidth_6db = match half {
+        Some(k) => {
+            // bisect the crossing between the neighbouring grid points
+            let (mut lo, mut hi) = (bins(k - 1), bins(k));
+            for _ in 0..60 {
+                let mid = 0.5 * (lo + hi);
+                if response(mid) < 0.5 {
+                    hi = mid;
+                } else {
+                    lo = mid;
+                }
+            }
+            lo + hi
+        }
+        None => f64::INFINITY,
+    };
+
+    // the main lobe ends at the first minimum past the half-amplitude point
+    let mut null = half.unwrap_or(coarse.len() - 1);
+    while null + 1 < coarse.len() && coarse[null + 1] < coarse[null] {
+        null += 1;
+    }
+    let highest_sidelobe = if null + 1 < coarse.len() {
+        let peak = (null..coarse.len())
+            .max_by(|&a, &b| coarse[a].total_cmp(&coarse[b]))
+            .unwrap_or(null);
+        // golden-section search around the grid peak
+        let (mut lo, mut hi) = (bins(peak.max(null + 1) - 1), bins((peak + 1).min(size / 2)));
+        let g = (sqrt(5.0) - 1.0) / 2.0;
+        for _ in 0..80 {
+            let a = hi - g * (hi - lo);
+            let b = lo + g * (hi - lo);
+            if response(a) < response(b) {
+                lo = a;
+            } else {
+                hi = b;
+            }
+        }
+        20.0 * log10(response(0.5 * (lo + hi)).max(coarse[peak]))
+    } else {
+        f64::NEG_INFINITY
+    };
+
+    Ok(WindowMetrics {
+        coherent_gain: sum / n,
+        enbw: n * energy / (sum * sum),
+        scalloping_loss: -20.0 * log10(response(0.5)),
+        highest_sidelobe,
+        bandwidth_6db,
+    })
+}
+
+/// Modified Bessel function of the first kind, order zero, by its power
+/// series.
+fn bessel0(x: f64) -> f64 {
+    let y = x * x / 4.0;
+    let (mut sum, mut term) = (1.0, 1.0);
+    let mut k = 1.0;
+    while term > sum * f64::EPSILON {
+        term *= y / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
 }
 
 #[cfg(all(feature = "internal-tests", test))]
@@ -2,29 +2,19 @@
 //! no_std + alloc compatible
 
 extern crate alloc;
-#[cfg(not(feature = "std"))]
-use crate::num::Float;
+use crate::window::{Symmetry, WindowSpec};
 use alloc::vec;
 use alloc::vec::Vec;
 use core::f32::consts::PI;
-use libm::{cosf, fabsf, floorf, sinf};
+use libm::{cosf, fabsf, sinf};
 
-/// Tukey window (tapered cosine)
+/// Symmetric Tukey window (tapered cosine), with `alpha` clamped to
+/// `[0, 1]`: [`WindowSpec::Tukey`] with [`Symmetry::Symmetric`].
 pub fn tukey(len: usize, alpha: f32) -> Vec<f32> {
-    let alpha = alpha.clamp(0.0, 1.0);
-    let mut w = vec![0.0; len];
-    let edge = floorf(alpha * (len as f32 - 1.0) / 2.0) as usize;
-    for (n, w_n) in w.iter_mut().enumerate() {
-        *w_n = if n < edge {
-            0.5 * (1.0 + (PI * (2.0 * n as f32 / (alpha * (len as f32 - 1.0)) - 1.0)).cos())
-        } else if n < len - edge {
-            1.0
-        } else {
-            0.5 * (1.0
-                + (PI * (2.0 * n as f32 / (alpha * (len as f32 - 1.0)) - 2.0 / alpha + 1.0)).cos())
-        };
+    WindowSpec::Tukey {
+        alpha: alpha.clamp(0.0, 1.0),
     }
-    w
+    .generate(len, Symmetry::Symmetric)
 }
 
 /// Bartlett (triangular) window
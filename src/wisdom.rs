@@ -0,0 +1,265 @@
+//! Planner "wisdom": a portable record of measured planning decisions.
+//!
+//! [`export_wisdom`] serialises the strategies an [`FftPlanner`] has measured
+//! together with the parallel FFT knobs (see [`ParallelFftConfig`]) into a
+//! small line-based text blob. [`import_wisdom`] validates and applies such a
+//! blob so that a freshly started process can reuse earlier measurements
+//! instead of timing every size again.
+//!
+//! ```text
+//! kofft-wisdom 1
+//! cpu x86_64 sse2,sse4.1,avx,avx2,fma
+//! float f32
+//! strategy 1000 mixed-radix
+//! strategy 4096 split-radix
+//! parallel 0 0 0 0 0 52428800
+//! ```
+//!
+//! The first line carries the format version. The `cpu` line records the
+//! architecture and SIMD features detected when the blob was written;
+//! decisions measured on a different feature set are rejected. `float` must
+//! match the planner's precision. The `parallel` line lists threshold, L1
+//! cache bytes, per-core work, block size, threads and calibrated per-core
+//! work, and is ignored by builds without the `parallel` feature.
+//!
+//! [`ParallelFftConfig`]: crate::fft::ParallelFftConfig
+//!
+//! ```
+//! use kofft::fft::FftPlanner;
+//! use kofft::wisdom::{export_wisdom, import_wisdom};
+//! use kofft::PlanMode;
+//!
+//! let mut planner = FftPlanner::<f32>::with_mode(PlanMode::Measure);
+//! planner.plan_strategy(1000);
+//! let blob = export_wisdom(&planner);
+//!
+//! let mut restored = FftPlanner::<f32>::with_mode(PlanMode::Measure);
+//! assert_eq!(import_wisdom(&mut restored, &blob), Ok(1));
+//! assert_eq!(restored.remembered_strategy(1000), planner.remembered_strategy(1000));
+//! ```
+
+use crate::fft::{FftPlanner, FftStrategy};
+use crate::num::Float;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Current wisdom format version.
+pub const WISDOM_VERSION: u32 = 1;
+
+const MAGIC: &str = "kofft-wisdom";
+
+/// Reasons a wisdom blob cannot be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WisdomError {
+    /// The version, `cpu` or `float` line is missing, or this is not a
+    /// kofft wisdom blob.
+    MissingHeader,
+    /// The blob was written by an incompatible format version.
+    UnsupportedVersion(u32),
+    /// The blob was recorded on a CPU with a different feature set.
+    CpuMismatch { recorded: String, current: String },
+    /// The blob was recorded for a different floating-point precision.
+    PrecisionMismatch { recorded: String, current: String },
+    /// Line `line` (1-based) could not be parsed.
+    Malformed { line: usize },
+}
+
+/// Architecture and SIMD features relevant to plan selection, e.g.
+/// `"x86_64 sse2,avx,avx2,fma"`.
+pub fn cpu_signature() -> String {
+    let mut features: Vec<&str> = Vec::new();
+    #[cfg(target_arch = "x86_64")]
+    {
+        for (name, present) in [
+            ("sse2", std::arch::is_x86_feature_detected!("sse2")),
+            ("sse4.1", std::arch::is_x86_feature_detected!("sse4.1")),
+            ("avx", std::arch::is_x86_feature_detected!("avx")),
+            ("avx2", std::arch::is_x86_feature_detected!("avx2")),
+            ("fma", std::arch::is_x86_feature_detected!("fma")),
+            ("avx512f", std::arch::is_x86_feature_detected!("avx512f")),
+        ] {
+            if present {
+                features.push(name);
+            }
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            features.push("neon");
+        }
+    }
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    features.push("simd128");
+    if features.is_empty() {
+        features.push("none");
+    }
+    format!("{} {}", std::env::consts::ARCH, features.join(","))
+}
+
+/// Stable tag for the planner precision written on the `float` line, e.g.
+/// `"f32"`. Unlike `core::any::type_name` it does not depend on the compiler.
+fn precision_tag<T: Float>() -> &'static str {
+    use core::any::TypeId;
+    let id = TypeId::of::<T>();
+    if id == TypeId::of::<f32>() {
+        return "f32";
+    }
+    if id == TypeId::of::<f64>() {
+        return "f64";
+    }
+    #[cfg(feature = "f16")]
+    {
+        if id == TypeId::of::<half::f16>() {
+            return "f16";
+        }
+        if id == TypeId::of::<half::bf16>() {
+            return "bf16";
+        }
+    }
+    "other"
+}
+
+fn strategy_name(strategy: FftStrategy) -> &'static str {
+    match strategy {
+        FftStrategy::Radix2 => "radix2",
+        FftStrategy::Radix4 => "radix4",
+        FftStrategy::SplitRadix => "split-radix",
+        FftStrategy::MixedRadix => "mixed-radix",
+        FftStrategy::Bluestein => "bluestein",
+        FftStrategy::FourStep => "four-step",
+        FftStrategy::Auto => "auto",
+    }
+}
+
+fn parse_strategy(name: &str) -> Option<FftStrategy> {
+    Some(match name {
+        "radix2" => FftStrategy::Radix2,
+        "radix4" => FftStrategy::Radix4,
+        "split-radix" => FftStrategy::SplitRadix,
+        "mixed-radix" => FftStrategy::MixedRadix,
+        "bluestein" => FftStrategy::Bluestein,
+        "four-step" => FftStrategy::FourStep,
+        _ => return None,
+    })
+}
+
+/// Serialise the strategies remembered by `planner` and the current parallel
+/// FFT knobs.
+pub fn export_wisdom<T: Float>(planner: &FftPlanner<T>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{MAGIC} {WISDOM_VERSION}");
+    let _ = writeln!(out, "cpu {}", cpu_signature());
+    let _ = writeln!(out, "float {}", precision_tag::<T>());
+    for (n, strategy) in planner.strategies() {
+        let _ = writeln!(out, "strategy {n} {}", strategy_name(strategy));
+    }
+    #[cfg(feature = "parallel")]
+    {
+        let c = crate::fft::parallel_fft_config();
+        let _ = writeln!(
+            out,
+            "parallel {} {} {} {} {} {}",
+            c.threshold,
+            c.l1_cache_bytes,
+            c.per_core_work,
+            c.block_size,
+            c.threads,
+            c.calibrated_per_core_work
+        );
+    }
+    out
+}
+
+/// Validate `blob` and load its decisions into `planner`.
+///
+/// Nothing is applied unless the whole blob parses and matches this CPU and
+/// precision. Returns the number of strategies imported.
+pub fn import_wisdom<T: Float>(
+    planner: &mut FftPlanner<T>,
+    blob: &str,
+) -> Result<usize, WisdomError> {
+    let mut lines = blob
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'));
+
+    let (_, header) = lines.next().ok_or(WisdomError::MissingHeader)?;
+    let version = header
+        .strip_prefix(MAGIC)
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .ok_or(WisdomError::MissingHeader)?;
+    if version != WISDOM_VERSION {
+        return Err(WisdomError::UnsupportedVersion(version));
+    }
+
+    let mut strategies = Vec::new();
+    let mut parallel: Option<[usize; 6]> = None;
+    let (mut saw_cpu, mut saw_float) = (false, false);
+    for (line_no, line) in lines {
+        let malformed = WisdomError::Malformed { line: line_no };
+        let (key, rest) = line.split_once(' ').ok_or(malformed.clone())?;
+        let rest = rest.trim();
+        match key {
+            "cpu" => {
+                let current = cpu_signature();
+                if rest != current {
+                    return Err(WisdomError::CpuMismatch {
+                        recorded: rest.into(),
+                        current,
+                    });
+                }
+                saw_cpu = true;
+            }
+            "float" => {
+                let current = precision_tag::<T>();
+                if rest != current {
+                    return Err(WisdomError::PrecisionMismatch {
+                        recorded: rest.into(),
+                        current: current.into(),
+                    });
+                }
+                saw_float = true;
+            }
+            "strategy" => {
+                let (n, name) = rest.split_once(' ').ok_or(malformed.clone())?;
+                let n = n.parse::<usize>().map_err(|_| malformed.clone())?;
+                let strategy = parse_strategy(name.trim()).ok_or(malformed.clone())?;
+                strategies.push((n, strategy));
+            }
+            "parallel" => {
+                let values: Vec<usize> = rest
+                    .split_whitespace()
+                    .map(|v| v.parse::<usize>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| malformed.clone())?;
+                parallel = Some(values.try_into().map_err(|_| malformed)?);
+            }
+            _ => return Err(malformed),
+        }
+    }
+    if !saw_cpu || !saw_float {
+        return Err(WisdomError::MissingHeader);
+    }
+
+    for &(n, strategy) in &strategies {
+        planner.remember_strategy(n, strategy);
+    }
+    #[cfg(feature = "parallel")]
+    if let Some([threshold, l1, work, block, threads, calibrated]) = parallel {
+        crate::fft::set_parallel_fft_config(&crate::fft::ParallelFftConfig {
+            threshold,
+            l1_cache_bytes: l1,
+            per_core_work: work,
+            block_size: block,
+            threads,
+            calibrated_per_core_work: calibrated,
+        });
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel;
+    Ok(strategies.len())
+}
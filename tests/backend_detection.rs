@@ -1,15 +1,20 @@
 #[cfg(target_arch = "x86_64")]
 #[test]
 fn selects_x86_backend() {
-    let backend = kofft::fft::new_fft_impl();
+    let backend = kofft::fft::new_fft_impl::<f32>();
     let any = backend.as_ref() as &dyn core::any::Any;
+    #[cfg(feature = "avx512")]
+    if std::arch::is_x86_feature_detected!("avx512f") {
+        assert!(any.is::<kofft::fft::SimdFftAvx512Impl>());
+        return;
+    }
     assert!(any.is::<kofft::fft::SimdFftX86_64Impl>());
 }
 
 #[cfg(target_arch = "aarch64")]
 #[test]
 fn selects_aarch64_backend() {
-    let backend = kofft::fft::new_fft_impl();
+    let backend = kofft::fft::new_fft_impl::<f32>();
     let any = backend.as_ref() as &dyn core::any::Any;
     assert!(any.is::<kofft::fft::SimdFftAarch64Impl>());
 }
@@ -17,7 +22,32 @@ fn selects_aarch64_backend() {
 #[cfg(target_arch = "wasm32")]
 #[test]
 fn selects_wasm_backend() {
-    let backend = kofft::fft::new_fft_impl();
+    let backend = kofft::fft::new_fft_impl::<f32>();
     let any = backend.as_ref() as &dyn core::any::Any;
     assert!(any.is::<kofft::fft::SimdFftWasmImpl>());
 }
+
+#[test]
+fn generic_constructor_covers_both_precisions() {
+    use kofft::fft::{Complex32, Complex64};
+
+    let fft32 = kofft::fft::new_fft_impl::<f32>();
+    let fft64 = kofft::fft::new_fft_impl::<f64>();
+    #[cfg(target_arch = "x86_64")]
+    {
+        let any = fft64.as_ref() as &dyn core::any::Any;
+        assert!(
+            any.is::<kofft::fft::SimdFftX86_64Impl>() || cfg!(feature = "avx512"),
+            "unexpected f64 backend"
+        );
+    }
+    let mut a = vec![Complex32::new(1.0, 0.0); 64];
+    let mut b = vec![Complex64::new(1.0, 0.0); 64];
+    fft32.fft(&mut a).unwrap();
+    fft64.fft(&mut b).unwrap();
+    assert_eq!(a[0].re, 64.0);
+    assert_eq!(b[0].re, 64.0);
+    assert!(b[1..]
+        .iter()
+        .all(|c| c.re.abs() < 1e-12 && c.im.abs() < 1e-12));
+}
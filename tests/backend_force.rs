@@ -0,0 +1,27 @@
+#![cfg(feature = "std")]
+// `force` changes process-wide state, so this test lives in its own binary
+// instead of racing the registry tests that read `backend::active()`.
+
+use kofft::backend::{self, BackendError};
+use kofft::fft::Complex32;
+
+#[test]
+fn force_overrides_detection() {
+    assert_eq!(
+        backend::force("no-such-backend"),
+        Err(BackendError::Unknown("no-such-backend".into()))
+    );
+    backend::force("Scalar").unwrap();
+    assert_eq!(backend::forced(), Some("scalar"));
+    assert_eq!(backend::active().name, "scalar");
+    let fft = kofft::fft::new_fft_impl::<f32>();
+    let any = fft.as_ref() as &dyn core::any::Any;
+    assert!(any.is::<kofft::fft::ScalarFftImpl<f32>>());
+    backend::clear_forced();
+    assert_eq!(backend::forced(), None);
+
+    let fft = backend::create::<f32>("scalar").unwrap();
+    let mut data = vec![Complex32::new(1.0, 0.0); 8];
+    fft.fft(&mut data).unwrap();
+    assert_eq!(data[0].re, 8.0);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "std")]
+
+use kofft::backend::{self, Precision, BACKEND_ENV};
+use kofft::fft::FftStrategy;
+use std::process::Command;
+
+#[test]
+fn lists_scalar_and_native_backends() {
+    let all = backend::backends();
+    let scalar = all.iter().find(|b| b.name == "scalar").unwrap();
+    assert!(scalar.available);
+    assert_eq!((scalar.simd_width_f32, scalar.simd_width_f64), (0, 0));
+    assert!(scalar.precisions.contains(&Precision::F64));
+    assert!(scalar.strategies.contains(&FftStrategy::Bluestein));
+    #[cfg(target_arch = "x86_64")]
+    {
+        let x86 = backend::info("x86_64").unwrap();
+        assert!(x86.available);
+        assert!(x86.simd_width_f64 >= 128);
+        // only the f64 transforms have x86_64 vector kernels
+        assert_eq!(x86.simd_width_f32, 0);
+    }
+    assert!(all.iter().any(|b| b == &backend::active()));
+}
+
+/// Child half of `environment_variable_pins_backend`: checks the active
+/// backend against the name the parent expects.
+#[test]
+#[ignore = "run by environment_variable_pins_backend in a child process"]
+fn active_backend_matches_expectation() {
+    let expected = std::env::var("KOFFT_EXPECTED_BACKEND").unwrap();
+    assert_eq!(backend::active().name, expected);
+}
+
+#[test]
+fn environment_variable_pins_backend() {
+    let exe = std::env::current_exe().unwrap();
+    let run = |value: &str, expected: &str| {
+        let output = Command::new(&exe)
+            .env(BACKEND_ENV, value)
+            .env("KOFFT_EXPECTED_BACKEND", expected)
+            .args(["--exact", "active_backend_matches_expectation", "--ignored"])
+            .output()
+            .expect("run active backend test");
+        assert!(output.status.success(), "{BACKEND_ENV}={value}");
+    };
+    run("scalar", "scalar");
+    // Unknown names fall back to detection.
+    run("bogus", backend::active().name);
+}
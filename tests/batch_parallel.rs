@@ -0,0 +1,246 @@
+#![cfg(feature = "parallel")]
+//! Parallel batch, multi-channel and 2D transforms give the same bits on
+//! every pool.
+
+use std::sync::Arc;
+
+use kofft::dct::{self, DctPlan, DctType};
+use kofft::dst::{self, DstPlan, DstType};
+use kofft::fft::{self, Complex32, Complex64, FftError, ScalarFftImpl, SharedFftPlan};
+use kofft::hartley::{self, HartleyPlan};
+use kofft::ndfft;
+use kofft::parallel::{Executor, Parallelism};
+use kofft::wavelet::{self, HaarPlan};
+
+/// Runs every task on its own scoped thread.
+struct ScopedExecutor;
+
+impl Executor for ScopedExecutor {
+    fn threads(&self) -> usize {
+        3
+    }
+
+    fn run(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+        std::thread::scope(|s| {
+            for i in 0..tasks {
+                s.spawn(move || task(i));
+            }
+        });
+    }
+}
+
+fn pools() -> Vec<Parallelism> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+    vec![
+        Parallelism::global(),
+        Parallelism::rayon(Arc::new(pool)),
+        Parallelism::executor(Arc::new(ScopedExecutor)),
+    ]
+}
+
+fn real_batches(count: usize, n: usize) -> Vec<Vec<f32>> {
+    (0..count)
+        .map(|b| {
+            (0..n)
+                .map(|i| ((i * 7 + b * 3) as f32 * 0.11).sin() + b as f32 * 0.01)
+                .collect()
+        })
+        .collect()
+}
+
+fn complex_batches(count: usize, n: usize) -> Vec<Vec<Complex32>> {
+    real_batches(count, n)
+        .into_iter()
+        .map(|b| b.iter().map(|&v| Complex32::new(v, 0.5 - v)).collect())
+        .collect()
+}
+
+#[test]
+fn fft_batch_matches_plan_on_each_buffer() {
+    // power-of-two, mixed-radix and Bluestein lengths
+    for n in [64usize, 60, 37] {
+        let input = complex_batches(23, n);
+        let serial = SharedFftPlan::<f32>::new(n)
+            .unwrap()
+            .with_parallelism(Parallelism::serial());
+        let mut expected = input.clone();
+        let mut scratch = vec![Complex32::zero(); serial.scratch_len()];
+        for b in &mut expected {
+            serial.fft(b, &mut scratch).unwrap();
+        }
+        let mut serial_batches = input.clone();
+        fft::batch_parallel(&serial, &mut serial_batches).unwrap();
+        assert_eq!(serial_batches, expected);
+
+        for par in pools() {
+            let plan = SharedFftPlan::<f32>::new(n).unwrap().with_parallelism(par);
+            let mut data = input.clone();
+            fft::multi_channel_parallel(&plan, &mut data).unwrap();
+            assert_eq!(data, expected, "n={n}");
+            fft::batch_inverse_parallel(&plan, &mut data).unwrap();
+            for (a, b) in data.iter().flatten().zip(input.iter().flatten()) {
+                assert!((a.re - b.re).abs() < 1e-4 && (a.im - b.im).abs() < 1e-4);
+            }
+        }
+    }
+}
+
+#[test]
+fn fft_batch_rejects_mismatched_buffers() {
+    let plan = SharedFftPlan::<f32>::new(16).unwrap();
+    let mut data = complex_batches(9, 16);
+    data[5].push(Complex32::zero());
+    assert_eq!(
+        fft::batch_parallel(&plan, &mut data),
+        Err(FftError::MismatchedLengths)
+    );
+}
+
+#[test]
+fn dct_and_dst_batches_match_serial() {
+    let n = 48;
+    let input = real_batches(17, n);
+    for kind in [DctType::I, DctType::II, DctType::III, DctType::IV] {
+        let mut plan = DctPlan::new(kind, n).unwrap();
+        let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+        let expected: Vec<Vec<f32>> = input
+            .iter()
+            .map(|b| {
+                let mut out = vec![0.0; n];
+                plan.execute_with_scratch(b, &mut out, &mut scratch)
+                    .unwrap();
+                out
+            })
+            .collect();
+        plan = plan.with_parallelism(Parallelism::serial());
+        let mut serial = expected.clone();
+        dct::batch_inverse_parallel(&plan, &mut serial).unwrap();
+        for par in pools() {
+            plan = plan.with_parallelism(par);
+            let mut data = input.clone();
+            dct::batch_parallel(&plan, &mut data).unwrap();
+            assert_eq!(data, expected, "{kind:?}");
+            dct::batch_inverse_parallel(&plan, &mut data).unwrap();
+            assert_eq!(data, serial, "{kind:?}");
+        }
+    }
+    for kind in [DstType::I, DstType::II, DstType::III, DstType::IV] {
+        let mut plan = DstPlan::new(kind, n)
+            .unwrap()
+            .with_parallelism(Parallelism::serial());
+        let mut expected = input.clone();
+        dst::batch_parallel(&plan, &mut expected).unwrap();
+        for par in pools() {
+            plan = plan.with_parallelism(par);
+            let mut data = input.clone();
+            dst::batch_parallel(&plan, &mut data).unwrap();
+            assert_eq!(data, expected, "{kind:?}");
+        }
+    }
+}
+
+#[test]
+fn hartley_and_wavelet_batches_match_serial() {
+    // one even length through the real FFT, one odd through the complex FFT
+    for n in [32, 21] {
+        let input = real_batches(11, n);
+        let mut plan = HartleyPlan::new(n).unwrap();
+        let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+        let expected: Vec<Vec<f32>> = input
+            .iter()
+            .map(|b| {
+                let mut out = vec![0.0; n];
+                plan.execute_with_scratch(b, &mut out, &mut scratch)
+                    .unwrap();
+                out
+            })
+            .collect();
+        let mut reference = input.clone();
+        hartley::batch(&mut reference).unwrap();
+        for (a, b) in expected.iter().flatten().zip(reference.iter().flatten()) {
+            assert!((a - b).abs() < 1e-4, "{a} vs {b}");
+        }
+        for par in pools() {
+            plan = plan.with_parallelism(par);
+            let mut data = input.clone();
+            hartley::batch_parallel(&plan, &mut data).unwrap();
+            assert_eq!(data, expected, "n = {n}");
+        }
+    }
+
+    let mut input = real_batches(11, 32);
+    input.extend(real_batches(6, 20));
+    let (avg, diff) = wavelet::batch_forward(&input);
+    let restored = wavelet::batch_inverse(&avg, &diff);
+    for par in pools() {
+        let plan = HaarPlan::new().with_parallelism(par);
+        let (a, d) = wavelet::batch_forward_parallel(&plan, &input);
+        assert_eq!((&a, &d), (&avg, &diff));
+        assert_eq!(wavelet::batch_inverse_parallel(&plan, &a, &d), restored);
+    }
+}
+
+#[test]
+fn hartley_batch_rejects_other_lengths() {
+    let plan = HartleyPlan::new(16).unwrap();
+    let mut data = vec![vec![0.0; 16], vec![0.0; 12]];
+    assert_eq!(
+        hartley::batch_parallel(&plan, &mut data),
+        Err(FftError::MismatchedLengths)
+    );
+}
+
+#[test]
+fn fft2d_matches_serial_on_every_pool() {
+    // more columns than one gather block, and a ragged last block
+    let (rows, cols) = (12usize, 40usize);
+    let x: Vec<Complex64> = (0..rows * cols)
+        .map(|i| Complex64::new((i as f64 * 0.21).sin(), (i as f64 * 0.05).cos()))
+        .collect();
+    let mut reference = x.clone();
+    let mut scratch = vec![Complex64::zero(); rows];
+    ndfft::fft2d_inplace(
+        &mut reference,
+        rows,
+        cols,
+        &ScalarFftImpl::default(),
+        &mut scratch,
+    )
+    .unwrap();
+
+    let plans = |par: Parallelism| {
+        (
+            SharedFftPlan::<f64>::new(cols)
+                .unwrap()
+                .with_parallelism(par.clone()),
+            SharedFftPlan::<f64>::new(rows)
+                .unwrap()
+                .with_parallelism(par),
+        )
+    };
+    let (row_plan, col_plan) = plans(Parallelism::serial());
+    let mut serial = x.clone();
+    ndfft::fft2d_inplace_parallel(&mut serial, rows, cols, &row_plan, &col_plan).unwrap();
+    for (a, b) in serial.iter().zip(&reference) {
+        assert!((a.re - b.re).abs() < 1e-10 && (a.im - b.im).abs() < 1e-10);
+    }
+
+    for par in pools() {
+        let (row_plan, col_plan) = plans(par);
+        let mut data = x.clone();
+        ndfft::fft2d_inplace_parallel(&mut data, rows, cols, &row_plan, &col_plan).unwrap();
+        assert_eq!(data, serial);
+        ndfft::ifft2d_inplace_parallel(&mut data, rows, cols, &row_plan, &col_plan).unwrap();
+        for (a, b) in data.iter().zip(&x) {
+            assert!((a.re - b.re).abs() < 1e-12 && (a.im - b.im).abs() < 1e-12);
+        }
+    }
+
+    assert_eq!(
+        ndfft::fft2d_inplace_parallel(&mut serial, cols, rows, &row_plan, &col_plan),
+        Err(FftError::MismatchedLengths)
+    );
+}
@@ -64,3 +64,21 @@ fn bluestein_fft_matches_dft_and_reuses_scratch() {
     fft.fft(&mut data).unwrap();
     assert_eq!(allocs(), 0);
 }
+
+#[test]
+fn shared_planner_handles_shrinking_sizes() {
+    // Scratch buffers grow to the largest size seen; smaller transforms
+    // afterwards must only touch their own prefix.
+    let fft = ScalarFftImpl::<f32>::default();
+    for &n in &[64usize, 7, 32, 3, 15, 5] {
+        let input: Vec<Complex32> = (0..n)
+            .map(|i| Complex32::new((i % 5) as f32, (i % 3) as f32 - 1.0))
+            .collect();
+        let expected = dft(&input);
+        let mut data = input.clone();
+        fft.fft(&mut data).unwrap();
+        for (a, b) in data.iter().zip(expected.iter()) {
+            assert!((a.re - b.re).abs() < 1e-3 && (a.im - b.im).abs() < 1e-3);
+        }
+    }
+}
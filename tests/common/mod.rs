@@ -0,0 +1,56 @@
+//! Deterministic inputs and tolerance checks shared by the integration tests.
+//!
+//! Each test binary compiles its own copy and uses only part of it.
+#![allow(dead_code)]
+
+use kofft::fft::Complex64;
+
+/// A different signal for every `seed`, `|x| < 1.3`.
+pub fn seeded_signal(n: usize, seed: f64) -> Vec<f64> {
+    (0..n)
+        .map(|i| ((i as f64 + seed) * 0.37).sin() + 0.3 * ((i as f64) * seed).cos())
+        .collect()
+}
+
+/// Assert equal lengths and an absolute error below `tol` at every index.
+pub fn close(a: &[f64], b: &[f64], tol: f64) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).abs() < tol, "index {i}: {x} vs {y}");
+    }
+}
+
+/// Two incommensurate tones as the real and imaginary parts, `|z| < 1.2`.
+pub fn complex_signal(n: usize) -> Vec<Complex64> {
+    (0..n)
+        .map(|i| Complex64::new((i as f64 * 0.37).sin(), 0.5 * (i as f64 * 1.3).cos()))
+        .collect()
+}
+
+/// Assert equal lengths and an error below `tol * (1 + |b|)` at every index.
+pub fn assert_close(a: &[f32], b: &[f32], tol: f32) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!(
+            (x - y).abs() <= tol * (1.0 + y.abs()),
+            "index {i}: {x} vs {y}"
+        );
+    }
+}
+
+/// Two incommensurate tones, `|x| < 1.5`.
+pub fn signal(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| (i as f64 * 0.37).sin() + 0.5 * (i as f64 * 1.3).cos())
+        .collect()
+}
+
+/// [`signal`] in single precision.
+pub fn signal_f32(n: usize) -> Vec<f32> {
+    signal(n).into_iter().map(|v| v as f32).collect()
+}
+
+/// [`signal`] scaled so that `|x| < |amplitude|`, for fixed-point inputs.
+pub fn scaled_signal(n: usize, amplitude: f64) -> Vec<f64> {
+    signal(n).into_iter().map(|v| v * amplitude / 1.5).collect()
+}
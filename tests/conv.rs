@@ -0,0 +1,150 @@
+//! Convolution and correlation against direct sums and scipy's layout.
+
+use kofft::conv::{self, Method, Mode, OverlapAdd, OverlapSave};
+use kofft::fft::{Complex64, FftError};
+
+mod common;
+use common::{close, seeded_signal};
+
+#[test]
+fn matches_scipy_reference_values() {
+    let a = [1.0f64, 2.0, 3.0, 4.0];
+    let b = [1.0f64, 0.0, -1.0];
+    // scipy.signal.convolve / correlate
+    let expected = [
+        (Mode::Full, vec![1.0, 2.0, 2.0, 2.0, -3.0, -4.0]),
+        (Mode::Same, vec![2.0, 2.0, 2.0, -3.0]),
+        (Mode::Valid, vec![2.0, 2.0]),
+    ];
+    let correlated = [
+        (Mode::Full, vec![-1.0, -2.0, -2.0, -2.0, 3.0, 4.0]),
+        (Mode::Same, vec![-2.0, -2.0, -2.0, 3.0]),
+        (Mode::Valid, vec![-2.0, -2.0]),
+    ];
+    for method in [Method::Direct, Method::Fft] {
+        for (mode, want) in &expected {
+            let got = conv::convolve_with(&a, &b, *mode, method).unwrap();
+            close(&got, want, 1e-12);
+        }
+        for (mode, want) in &correlated {
+            let got = conv::correlate_with(&a, &b, *mode, method).unwrap();
+            close(&got, want, 1e-12);
+        }
+        // the second input longer than the first
+        let got = conv::correlate_with(&b, &a, Mode::Valid, method).unwrap();
+        close(&got, &[-2.0, -2.0], 1e-12);
+        let got = conv::convolve_with(&b, &a, Mode::Same, method).unwrap();
+        close(&got, &[2.0, 2.0, 2.0], 1e-12);
+    }
+}
+
+#[test]
+fn fft_agrees_with_direct_for_real_and_complex() {
+    for (n, m) in [(1, 1), (7, 3), (100, 37), (257, 256), (1000, 129)] {
+        let a = seeded_signal(n, 1.1);
+        let b = seeded_signal(m, 2.3);
+        for mode in [Mode::Full, Mode::Same, Mode::Valid] {
+            let direct = conv::convolve_with(&a, &b, mode, Method::Direct).unwrap();
+            let fft = conv::convolve_with(&a, &b, mode, Method::Fft).unwrap();
+            close(&fft, &direct, 1e-9);
+            let auto = conv::correlate(&a, &b, mode).unwrap();
+            let direct = conv::correlate_with(&a, &b, mode, Method::Direct).unwrap();
+            close(&auto, &direct, 1e-9);
+        }
+
+        let ca: Vec<Complex64> = a
+            .iter()
+            .zip(seeded_signal(n, 0.7))
+            .map(|(&re, im)| Complex64::new(re, im))
+            .collect();
+        let cb: Vec<Complex64> = b
+            .iter()
+            .zip(seeded_signal(m, 3.1))
+            .map(|(&re, im)| Complex64::new(re, im))
+            .collect();
+        for mode in [Mode::Full, Mode::Same, Mode::Valid] {
+            let direct = conv::correlate_complex_with(&ca, &cb, mode, Method::Direct).unwrap();
+            let fft = conv::correlate_complex_with(&ca, &cb, mode, Method::Fft).unwrap();
+            assert_eq!(direct.len(), fft.len());
+            for (x, y) in fft.iter().zip(&direct) {
+                assert!((x.re - y.re).abs() < 1e-9 && (x.im - y.im).abs() < 1e-9);
+            }
+            let auto = conv::convolve_complex(&ca, &cb, mode).unwrap();
+            let direct = conv::convolve_complex_with(&ca, &cb, mode, Method::Direct).unwrap();
+            for (x, y) in auto.iter().zip(&direct) {
+                assert!((x.re - y.re).abs() < 1e-9 && (x.im - y.im).abs() < 1e-9);
+            }
+        }
+    }
+    // zero-lag complex autocorrelation is the signal energy
+    let x = [Complex64::new(1.0, 2.0), Complex64::new(-0.5, 1.0)];
+    let r = conv::correlate_complex(&x, &x, Mode::Full).unwrap();
+    assert!((r[1].re - 6.25).abs() < 1e-12 && r[1].im.abs() < 1e-12);
+}
+
+#[test]
+fn streaming_matches_full_convolution() {
+    let x = seeded_signal(1000, 0.9);
+    for taps in [1usize, 5, 64, 301] {
+        let h = seeded_signal(taps, 1.7);
+        let expected = conv::convolve_with(&x, &h, Mode::Full, Method::Direct).unwrap();
+        for block in [1usize, 16, 100, 512] {
+            let mut ola = OverlapAdd::new(&h, block).unwrap();
+            let mut ols = OverlapSave::new(&h, block).unwrap();
+            assert_eq!((ola.block_len(), ola.filter_len()), (block, taps));
+            let mut y_add = vec![0.0; x.len()];
+            let mut y_save = vec![0.0; x.len()];
+            // ragged chunks, never longer than a block
+            let mut pos = 0;
+            for step in (1..).map(|i| 1 + (i * 7) % block) {
+                if pos == x.len() {
+                    break;
+                }
+                let end = x.len().min(pos + step);
+                ola.process(&x[pos..end], &mut y_add[pos..end]).unwrap();
+                ols.process(&x[pos..end], &mut y_save[pos..end]).unwrap();
+                pos = end;
+            }
+            y_add.extend_from_slice(ola.flush());
+            y_save.extend_from_slice(ols.flush());
+            close(&y_add, &expected, 1e-9);
+            close(&y_save, &expected, 1e-9);
+
+            // flushing resets the stream
+            let mut out = vec![0.0; block.min(taps)];
+            let unit: Vec<f64> = (0..out.len()).map(|i| (i == 0) as u8 as f64).collect();
+            ols.process(&unit, &mut out).unwrap();
+            close(&out, &h[..out.len()], 1e-12);
+        }
+    }
+}
+
+#[test]
+fn rejects_empty_and_oversized_input() {
+    assert_eq!(
+        conv::convolve::<f32>(&[], &[1.0], Mode::Full),
+        Err(FftError::EmptyInput)
+    );
+    assert_eq!(
+        conv::correlate_complex::<f64>(&[Complex64::zero()], &[], Mode::Same),
+        Err(FftError::EmptyInput)
+    );
+    assert!(matches!(
+        OverlapAdd::<f32>::new(&[], 8),
+        Err(FftError::EmptyInput)
+    ));
+    assert!(matches!(
+        OverlapSave::<f32>::new(&[1.0], 0),
+        Err(FftError::EmptyInput)
+    ));
+    let mut ola = OverlapAdd::<f32>::new(&[1.0, 2.0], 4).unwrap();
+    let mut out = [0.0; 5];
+    assert_eq!(
+        ola.process(&[0.0; 5], &mut out),
+        Err(FftError::MismatchedLengths)
+    );
+    assert_eq!(
+        ola.process(&[0.0; 4], &mut out),
+        Err(FftError::MismatchedLengths)
+    );
+}
@@ -17,19 +17,19 @@ fn fft_matches_scalar() {
     #[cfg(target_arch = "x86_64")]
     {
         use kofft::fft::SimdFftX86_64Impl;
-        let simd = SimdFftX86_64Impl;
+        let simd = SimdFftX86_64Impl::default();
         simd.fft(&mut simd_input).unwrap();
     }
     #[cfg(target_arch = "aarch64")]
     {
         use kofft::fft::SimdFftAarch64Impl;
-        let simd = SimdFftAarch64Impl;
+        let simd = SimdFftAarch64Impl::default();
         simd.fft(&mut simd_input).unwrap();
     }
     #[cfg(target_arch = "wasm32")]
     {
         use kofft::fft::SimdFftWasmImpl;
-        let simd = SimdFftWasmImpl;
+        let simd = SimdFftWasmImpl::default();
         simd.fft(&mut simd_input).unwrap();
     }
 
@@ -37,3 +37,155 @@ fn fft_matches_scalar() {
         assert!((a.re - b.re).abs() < 1e-5 && (a.im - b.im).abs() < 1e-5);
     }
 }
+
+#[test]
+fn f64_backend_matches_scalar() {
+    use kofft::fft::Complex64;
+
+    #[cfg(target_arch = "x86_64")]
+    let simd = kofft::fft::SimdFftX86_64Impl::default();
+    #[cfg(target_arch = "aarch64")]
+    let simd = kofft::fft::SimdFftAarch64Impl::default();
+    #[cfg(target_arch = "wasm32")]
+    let simd = kofft::fft::SimdFftWasmImpl::default();
+    let scalar = ScalarFftImpl::<f64>::default();
+
+    // One instance serves growing and shrinking lengths from its cached state.
+    for n in [1usize, 8, 32, 64, 1024, 4096, 256, 60, 97] {
+        let input: Vec<Complex64> = (0..n)
+            .map(|i| Complex64::new((i as f64 * 0.31).sin(), (i as f64 * 0.07).cos()))
+            .collect();
+        let mut expected = input.clone();
+        scalar.fft(&mut expected).unwrap();
+        let mut data = input.clone();
+        FftImpl::<f64>::fft(&simd, &mut data).unwrap();
+        for (a, b) in data.iter().zip(&expected) {
+            assert!(
+                (a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9,
+                "n={n}"
+            );
+        }
+        FftImpl::<f64>::ifft(&simd, &mut data).unwrap();
+        for (a, b) in data.iter().zip(&input) {
+            assert!(
+                (a.re - b.re).abs() < 1e-12 && (a.im - b.im).abs() < 1e-12,
+                "n={n}"
+            );
+        }
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+mod avx512 {
+    use kofft::fft::{
+        Complex32, Complex64, FftImpl, FftStrategy, ScalarFftImpl, SimdFftAvx512Impl,
+    };
+    use kofft::rfft::RealFftImpl;
+
+    fn supported() -> bool {
+        if !std::arch::is_x86_feature_detected!("avx512f") {
+            eprintln!("skipping: CPU lacks avx512f");
+            return false;
+        }
+        true
+    }
+
+    #[test]
+    fn complex_matches_scalar() {
+        if !supported() {
+            return;
+        }
+        // SplitRadix has no AVX-512 kernel and says so; it runs the scalar code
+        let info = kofft::backend::info("avx512").unwrap();
+        assert!(!info.strategies.contains(&FftStrategy::SplitRadix));
+        let simd = SimdFftAvx512Impl::default();
+        let scalar32 = ScalarFftImpl::<f32>::default();
+        let scalar64 = ScalarFftImpl::<f64>::default();
+        for n in [8usize, 32, 64, 128, 512, 2048, 8192, 48] {
+            for strategy in [
+                FftStrategy::Radix2,
+                FftStrategy::Radix4,
+                FftStrategy::SplitRadix,
+            ] {
+                let input: Vec<Complex64> = (0..n)
+                    .map(|i| Complex64::new((i as f64 * 0.37).sin(), (i as f64 * 0.11).cos()))
+                    .collect();
+                let mut expected = input.clone();
+                scalar64.fft(&mut expected).unwrap();
+                let mut data = input.clone();
+                FftImpl::<f64>::fft_with_strategy(&simd, &mut data, strategy).unwrap();
+                for (a, b) in data.iter().zip(&expected) {
+                    assert!(
+                        (a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9,
+                        "f64 n={n} {strategy:?}"
+                    );
+                }
+
+                let input32: Vec<Complex32> = input
+                    .iter()
+                    .map(|c| Complex32::new(c.re as f32, c.im as f32))
+                    .collect();
+                let mut expected = input32.clone();
+                scalar32.fft(&mut expected).unwrap();
+                let mut data = input32.clone();
+                FftImpl::<f32>::fft_with_strategy(&simd, &mut data, strategy).unwrap();
+                let tol = 1e-4 * (n as f32).sqrt();
+                for (a, b) in data.iter().zip(&expected) {
+                    assert!(
+                        (a.re - b.re).abs() < tol && (a.im - b.im).abs() < tol,
+                        "f32 n={n} {strategy:?}"
+                    );
+                }
+                FftImpl::<f32>::ifft(&simd, &mut data).unwrap();
+                for (a, b) in data.iter().zip(&input32) {
+                    assert!((a.re - b.re).abs() < 1e-4 && (a.im - b.im).abs() < 1e-4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn real_fft_packing_matches_scalar() {
+        if !supported() {
+            return;
+        }
+        let simd = SimdFftAvx512Impl::default();
+        for n in [8usize, 34, 64, 256, 1000, 4096] {
+            let signal: Vec<f64> = (0..n).map(|i| (i as f64 * 0.3).sin() + 0.25).collect();
+            let mut input = signal.clone();
+            let mut spectrum = vec![Complex64::zero(); n / 2 + 1];
+            simd.rfft(&mut input, &mut spectrum).unwrap();
+            let mut expected: Vec<Complex64> =
+                signal.iter().map(|&x| Complex64::new(x, 0.0)).collect();
+            ScalarFftImpl::<f64>::default().fft(&mut expected).unwrap();
+            for (a, b) in spectrum.iter().zip(&expected) {
+                assert!(
+                    (a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9,
+                    "n={n}"
+                );
+            }
+            let mut back = vec![0.0f64; n];
+            simd.irfft(&mut spectrum, &mut back).unwrap();
+            for (a, b) in back.iter().zip(&signal) {
+                assert!((a - b).abs() < 1e-12, "n={n}");
+            }
+
+            let signal32: Vec<f32> = signal.iter().map(|&x| x as f32).collect();
+            let mut input = signal32.clone();
+            let mut spectrum = vec![Complex32::zero(); n / 2 + 1];
+            simd.rfft(&mut input, &mut spectrum).unwrap();
+            let tol = 1e-5 * n as f64;
+            for (a, b) in spectrum.iter().zip(&expected) {
+                assert!(
+                    (a.re as f64 - b.re).abs() < tol && (a.im as f64 - b.im).abs() < tol,
+                    "n={n}"
+                );
+            }
+            let mut back = vec![0.0f32; n];
+            simd.irfft(&mut spectrum, &mut back).unwrap();
+            for (a, b) in back.iter().zip(&signal32) {
+                assert!((a - b).abs() < 1e-4, "n={n}");
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Q15/Q31 fixed-point FFTs against the floating-point path.
+
+use kofft::fft::{Complex64, FftError, FftImpl, ScalarFftImpl};
+use kofft::fixed::{self, ComplexFixed, ComplexQ15, ComplexQ31, Fixed};
+
+mod common;
+use common::scaled_signal;
+
+fn quantize<S: Fixed>(x: &[f64]) -> Vec<S> {
+    x.iter().map(|&v| S::from_f32(v as f32)).collect()
+}
+
+fn value<S: Fixed>(s: S) -> f64 {
+    s.to_i64() as f64 / (1u64 << S::FRAC_BITS) as f64
+}
+
+/// Worst error of `out · 2^exp` against `reference`, in units of the output
+/// LSB `2^(exp - FRAC_BITS)`.
+fn error_lsb<S: Fixed>(out: &[ComplexFixed<S>], exp: i32, reference: &[Complex64]) -> f64 {
+    let scale = (exp as f64).exp2();
+    let lsb = scale / (1u64 << S::FRAC_BITS) as f64;
+    out.iter()
+        .zip(reference)
+        .map(|(o, r)| {
+            let dr = value(o.re) * scale - r.re;
+            let di = value(o.im) * scale - r.im;
+            dr.abs().max(di.abs()) / lsb
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Rounding noise grows by about half an LSB per stage plus the twiddle
+/// quantization; allow one LSB per stage.
+fn bound(n: usize) -> f64 {
+    n.trailing_zeros() as f64 + 1.0
+}
+
+/// A band-limited spectrum rarely needs block shifts on the way back, and
+/// rounding noise from unscaled stages adds in power: allow `√n` on top.
+fn inverse_bound(n: usize) -> f64 {
+    bound(n) + (n as f64).sqrt()
+}
+
+fn complex_matches_float<S: Fixed>(n: usize) {
+    let re = quantize::<S>(&scaled_signal(n, 0.9));
+    let im = quantize::<S>(&scaled_signal(n, -0.5));
+    let mut data: Vec<ComplexFixed<S>> = re
+        .iter()
+        .zip(&im)
+        .map(|(&r, &i)| ComplexFixed::new(r, i))
+        .collect();
+    let mut reference: Vec<Complex64> = data
+        .iter()
+        .map(|c| Complex64::new(value(c.re), value(c.im)))
+        .collect();
+    ScalarFftImpl::<f64>::default().fft(&mut reference).unwrap();
+
+    let exp = fixed::fft(&mut data).unwrap();
+    let err = error_lsb(&data, exp, &reference);
+    assert!(err <= bound(n), "Q{} n={n}: {err} LSB", S::FRAC_BITS);
+
+    // The inverse matches the float inverse of the fixed-point spectrum...
+    let mut inverse: Vec<Complex64> = data
+        .iter()
+        .map(|c| Complex64::new(value(c.re), value(c.im)))
+        .collect();
+    ScalarFftImpl::<f64>::default().ifft(&mut inverse).unwrap();
+    let scale = (exp as f64).exp2();
+    inverse
+        .iter_mut()
+        .for_each(|c| *c = Complex64::new(c.re * scale, c.im * scale));
+    let exp_inv = fixed::ifft(&mut data).unwrap();
+    let err = error_lsb(&data, exp + exp_inv, &inverse);
+    assert!(
+        err <= inverse_bound(n),
+        "Q{} n={n} inverse: {err} LSB",
+        S::FRAC_BITS
+    );
+
+    // ...and recovers the input to within the forward quantization step.
+    let step = (exp as f64 - S::FRAC_BITS as f64).exp2() * bound(n);
+    let scale = ((exp + exp_inv) as f64).exp2();
+    for (d, (&r, &i)) in data.iter().zip(re.iter().zip(&im)) {
+        assert!((value(d.re) * scale - value(r)).abs() <= step);
+        assert!((value(d.im) * scale - value(i)).abs() <= step);
+    }
+}
+
+fn real_matches_float<S: Fixed>(n: usize) {
+    let x = quantize::<S>(&scaled_signal(n, 0.95));
+    let mut reference: Vec<Complex64> = x.iter().map(|&v| Complex64::new(value(v), 0.0)).collect();
+    ScalarFftImpl::<f64>::default().fft(&mut reference).unwrap();
+
+    let mut spectrum = vec![ComplexFixed::<S>::zero(); n / 2 + 1];
+    let exp = fixed::rfft(&x, &mut spectrum).unwrap();
+    let err = error_lsb(&spectrum, exp, &reference[..n / 2 + 1]);
+    assert!(err <= bound(n), "Q{} n={n}: {err} LSB", S::FRAC_BITS);
+
+    // Hermitian completion of the fixed-point spectrum as the float
+    // reference for the inverse.
+    let scale = (exp as f64).exp2();
+    let mut full = vec![Complex64::zero(); n];
+    for (k, c) in spectrum.iter().enumerate() {
+        let v = Complex64::new(value(c.re) * scale, value(c.im) * scale);
+        full[k] = v;
+        if k > 0 && k < n / 2 {
+            full[n - k] = Complex64::new(v.re, -v.im);
+        }
+    }
+    ScalarFftImpl::<f64>::default().ifft(&mut full).unwrap();
+
+    let mut back = vec![S::default(); n];
+    let exp_inv = fixed::irfft(&spectrum, &mut back).unwrap();
+    let scale = ((exp + exp_inv) as f64).exp2();
+    let lsb = scale / (1u64 << S::FRAC_BITS) as f64;
+    let step = (exp as f64 - S::FRAC_BITS as f64).exp2() * bound(n);
+    for ((b, r), &orig) in back.iter().zip(&full).zip(&x) {
+        let err = (value(*b) * scale - r.re).abs() / lsb;
+        assert!(
+            err <= inverse_bound(n),
+            "Q{} n={n} inverse: {err} LSB",
+            S::FRAC_BITS
+        );
+        assert!((value(*b) * scale - value(orig)).abs() <= step);
+    }
+}
+
+#[test]
+fn q15_and_q31_complex_fft_match_float_within_quantization() {
+    for n in [1usize, 2, 8, 64, 256, 1024] {
+        complex_matches_float::<i16>(n);
+        complex_matches_float::<i32>(n);
+    }
+}
+
+#[test]
+fn q15_and_q31_real_fft_match_float_within_quantization() {
+    for n in [2usize, 4, 16, 128, 1024] {
+        real_matches_float::<i16>(n);
+        real_matches_float::<i32>(n);
+    }
+}
+
+#[test]
+fn full_scale_input_does_not_wrap() {
+    // Alternating ±1 puts all energy into the Nyquist bin, the worst case
+    // for growth; block floating point must shift instead of overflowing.
+    let mut buf = [ComplexQ15::zero(); 64];
+    for (i, c) in buf.iter_mut().enumerate() {
+        c.re = if i % 2 == 0 { i16::MAX } else { i16::MIN };
+    }
+    let exp = fixed::fft_inplace_stack(&mut buf).unwrap();
+    let nyquist = value(buf[32].re) * (exp as f64).exp2();
+    assert!((nyquist - 64.0).abs() < 0.01, "{nyquist}");
+    assert!(buf
+        .iter()
+        .enumerate()
+        .all(|(k, c)| k == 32 || c.re.abs() <= 1));
+}
+
+#[test]
+fn stack_entry_points_match_slice_versions() {
+    let x: [i32; 16] = core::array::from_fn(|i| i32::from_f32(scaled_signal(16, 0.7)[i] as f32));
+    let mut spectrum = [ComplexQ31::zero(); 9];
+    let exp = fixed::rfft_stack(&x, &mut spectrum).unwrap();
+    let mut expected = [ComplexQ31::zero(); 9];
+    assert_eq!(fixed::rfft(&x, &mut expected).unwrap(), exp);
+    assert_eq!(spectrum, expected);
+
+    let mut back = [0i32; 16];
+    fixed::irfft_stack(&spectrum, &mut back).unwrap();
+
+    let mut buf: [ComplexQ31; 16] = core::array::from_fn(|i| ComplexQ31::new(x[i], 0));
+    let e1 = fixed::fft_inplace_stack(&mut buf).unwrap();
+    let e2 = fixed::ifft_inplace_stack(&mut buf).unwrap();
+    let scale = ((e1 + e2) as f64).exp2();
+    for (b, &orig) in buf.iter().zip(&x) {
+        assert!((value(b.re) * scale - value(orig)).abs() < 1e-7);
+    }
+}
+
+#[test]
+fn invalid_lengths_are_rejected() {
+    let mut empty: [ComplexQ15; 0] = [];
+    assert_eq!(fixed::fft(&mut empty), Err(FftError::EmptyInput));
+    let mut odd = [ComplexQ15::zero(); 12];
+    assert_eq!(fixed::fft(&mut odd), Err(FftError::NonPowerOfTwoNoStd));
+    let mut long = vec![ComplexQ15::zero(); 2 * fixed::MAX_LEN];
+    assert_eq!(fixed::fft(&mut long), Err(FftError::InvalidValue));
+    let mut short = [ComplexQ15::zero(); 4];
+    assert_eq!(
+        fixed::rfft(&[0i16; 8], &mut short),
+        Err(FftError::MismatchedLengths)
+    );
+}
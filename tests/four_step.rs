@@ -0,0 +1,141 @@
+//! Four-step FFT against the direct algorithms, and its planner wiring.
+
+use kofft::fft::{
+    Complex32, Complex64, FftError, FftImpl, FftPlanner, FftStrategy, ScalarFftImpl, SharedFftPlan,
+};
+use kofft::four_step::{FourStepPlan, FOUR_STEP_MIN_LEN};
+
+mod common;
+use common::complex_signal;
+
+/// Largest error relative to the largest reference magnitude.
+fn rel_err(a: &[Complex64], b: &[Complex64]) -> f64 {
+    let peak = b.iter().map(|c| c.re.hypot(c.im)).fold(0.0, f64::max);
+    let err = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| (x.re - y.re).hypot(x.im - y.im))
+        .fold(0.0, f64::max);
+    err / peak
+}
+
+#[test]
+fn matches_direct_fft_for_composite_lengths() {
+    let reference = ScalarFftImpl::<f64>::default();
+    // square and non-square powers of two, mixed-radix and non-power-of-two
+    // column lengths
+    for n in [16usize, 1 << 12, 1 << 13, 1000, 6 * 1024, 3 * 7 * 11 * 13] {
+        let x = complex_signal(n);
+        let mut expected = x.clone();
+        reference.fft(&mut expected).unwrap();
+
+        let plan = FourStepPlan::<f64>::new(n).unwrap();
+        let (n1, n2) = plan.factors();
+        assert_eq!(n1 * n2, n);
+        assert!(n1 <= n2);
+        let mut scratch = vec![Complex64::zero(); plan.scratch_len()];
+        let mut data = x.clone();
+        plan.fft(&mut data, &mut scratch).unwrap();
+        assert!(rel_err(&data, &expected) < 1e-13, "n={n}");
+        plan.ifft(&mut data, &mut scratch).unwrap();
+        assert!(rel_err(&data, &x) < 1e-13, "n={n}");
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_matches_serial() {
+    let n = 1 << 14;
+    let plan = FourStepPlan::<f64>::new(n).unwrap();
+    let x = complex_signal(n);
+    let mut serial = x.clone();
+    let mut scratch = vec![Complex64::zero(); plan.scratch_len()];
+    plan.fft(&mut serial, &mut scratch).unwrap();
+    let mut parallel = x.clone();
+    let mut scratch = vec![Complex64::zero(); n];
+    plan.fft_parallel(&mut parallel, &mut scratch).unwrap();
+    assert_eq!(parallel, serial);
+    plan.ifft_parallel(&mut parallel, &mut scratch).unwrap();
+    assert!(rel_err(&parallel, &x) < 1e-13);
+}
+
+#[test]
+fn strategy_and_planner_selection() {
+    let n = 1 << 12;
+    let x = complex_signal(n);
+    let fft = ScalarFftImpl::<f64>::default();
+    let mut expected = x.clone();
+    fft.fft_with_strategy(&mut expected, FftStrategy::SplitRadix)
+        .unwrap();
+    let mut data = x.clone();
+    fft.fft_with_strategy(&mut data, FftStrategy::FourStep)
+        .unwrap();
+    assert!(rel_err(&data, &expected) < 1e-13);
+
+    let mut planner = FftPlanner::<f32>::new();
+    assert_eq!(
+        planner.plan_strategy(FOUR_STEP_MIN_LEN),
+        FftStrategy::FourStep
+    );
+    assert_ne!(
+        planner.plan_strategy(FOUR_STEP_MIN_LEN / 2),
+        FftStrategy::FourStep
+    );
+}
+
+#[test]
+fn huge_transforms_use_four_step() {
+    let n = FOUR_STEP_MIN_LEN;
+    let x: Vec<Complex32> = complex_signal(n)
+        .iter()
+        .map(|c| Complex32::new(c.re as f32, c.im as f32))
+        .collect();
+    let mut expected: Vec<Complex64> = complex_signal(n)
+        .iter()
+        .map(|c| Complex64::new(c.re as f32 as f64, c.im as f32 as f64))
+        .collect();
+    ScalarFftImpl::<f64>::default().fft(&mut expected).unwrap();
+
+    let mut data = x.clone();
+    ScalarFftImpl::<f32>::default().fft(&mut data).unwrap();
+    let widened: Vec<Complex64> = data
+        .iter()
+        .map(|c| Complex64::new(c.re as f64, c.im as f64))
+        .collect();
+    assert!(rel_err(&widened, &expected) < 1e-5);
+
+    let plan = SharedFftPlan::<f32>::new(n).unwrap();
+    let mut shared = x.clone();
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    plan.fft(&mut shared, &mut scratch).unwrap();
+    let widened: Vec<Complex64> = shared
+        .iter()
+        .map(|c| Complex64::new(c.re as f64, c.im as f64))
+        .collect();
+    assert!(rel_err(&widened, &expected) < 1e-5);
+}
+
+#[test]
+fn rejects_prime_and_mismatched_lengths() {
+    assert!(matches!(
+        FourStepPlan::<f32>::new(0),
+        Err(FftError::EmptyInput)
+    ));
+    assert!(matches!(
+        FourStepPlan::<f32>::new(4099),
+        Err(FftError::InvalidValue)
+    ));
+    let plan = FourStepPlan::<f32>::new(64).unwrap();
+    let mut data = vec![Complex32::zero(); 32];
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    assert_eq!(
+        plan.fft(&mut data, &mut scratch),
+        Err(FftError::MismatchedLengths)
+    );
+    let mut data = vec![Complex32::zero(); 64];
+    let mut short = vec![Complex32::zero(); 8];
+    assert_eq!(
+        plan.fft(&mut data, &mut short),
+        Err(FftError::MismatchedLengths)
+    );
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "f16")]
+//! Half-precision storage: transforms computed in `f32` and rounded once.
+
+use kofft::fft::{Complex32, FftError, FftImpl, ScalarFftImpl};
+use kofft::half_precision::{self, bf16, f16, ComplexBf16, ComplexF16, HalfFloat};
+use kofft::rfft::RealFftPlan;
+use kofft::stft;
+use kofft::window::hann;
+
+mod common;
+use common::signal_f32;
+
+fn narrow<H: HalfFloat>(x: &[f32]) -> Vec<H> {
+    let mut out = vec![H::zero(); x.len()];
+    half_precision::from_f32(x, &mut out).unwrap();
+    out
+}
+
+fn widen<H: HalfFloat>(x: &[H]) -> Vec<f32> {
+    let mut out = vec![0.0; x.len()];
+    half_precision::to_f32(x, &mut out).unwrap();
+    out
+}
+
+/// Complex FFT of half-precision storage against the `f32` FFT of the same
+/// (already rounded) input: only the final rounding may differ, so the error
+/// is one half-precision ulp of the largest bin.
+fn complex_matches_f32<H: HalfFloat>(eps: f32) {
+    let n = 64;
+    let fft = ScalarFftImpl::<f32>::default();
+    let re = narrow::<H>(&signal_f32(n));
+    let mut data: Vec<_> = re.iter().map(|&r| kofft::Complex::new(r, -r)).collect();
+    let mut reference = vec![Complex32::zero(); n];
+    half_precision::complex_to_f32(&data, &mut reference).unwrap();
+    let input = reference.clone();
+    fft.fft(&mut reference).unwrap();
+
+    half_precision::fft(&mut data, &fft).unwrap();
+    let mut out = vec![Complex32::zero(); n];
+    half_precision::complex_to_f32(&data, &mut out).unwrap();
+    let peak = reference
+        .iter()
+        .fold(0.0f32, |m, c| m.max(c.re.abs()).max(c.im.abs()));
+    for (a, b) in out.iter().zip(&reference) {
+        assert!((a.re - b.re).abs() <= eps * peak);
+        assert!((a.im - b.im).abs() <= eps * peak);
+    }
+
+    half_precision::ifft(&mut data, &fft).unwrap();
+    half_precision::complex_to_f32(&data, &mut out).unwrap();
+    for (a, b) in out.iter().zip(&input) {
+        assert!((a.re - b.re).abs() <= 4.0 * eps, "{} vs {}", a.re, b.re);
+        assert!((a.im - b.im).abs() <= 4.0 * eps, "{} vs {}", a.im, b.im);
+    }
+}
+
+#[test]
+fn f16_and_bf16_complex_fft_match_f32() {
+    complex_matches_f32::<f16>(f16::EPSILON.to_f32());
+    complex_matches_f32::<bf16>(bf16::EPSILON.to_f32());
+}
+
+#[test]
+fn real_fft_round_trips_through_f16() {
+    let n = 128;
+    let plan = RealFftPlan::<f32>::new(n).unwrap();
+    let x = narrow::<f16>(&signal_f32(n));
+    let mut spectrum = vec![ComplexF16::zero(); plan.spectrum_len()];
+    half_precision::rfft(&plan, &x, &mut spectrum).unwrap();
+
+    let mut reference = vec![Complex32::zero(); plan.spectrum_len()];
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    plan.rfft_with_scratch(&widen(&x), &mut reference, &mut scratch)
+        .unwrap();
+    let expected: Vec<ComplexF16> = reference
+        .iter()
+        .map(|c| ComplexF16::new(f16::from_f32(c.re), f16::from_f32(c.im)))
+        .collect();
+    assert_eq!(spectrum, expected);
+
+    let mut back = vec![f16::ZERO; n];
+    half_precision::irfft(&plan, &spectrum, &mut back).unwrap();
+    for (a, b) in widen(&back).iter().zip(widen(&x)) {
+        assert!((a - b).abs() <= 4.0 * f16::EPSILON.to_f32(), "{a} vs {b}");
+    }
+}
+
+#[test]
+fn stft_frames_are_rounded_f32_frames() {
+    let fft = ScalarFftImpl::<f32>::default();
+    let (win_len, hop) = (32, 8);
+    let window = hann(win_len);
+    let x = narrow::<bf16>(&signal_f32(200));
+    let frames = 200usize.div_ceil(hop);
+
+    let mut half_frames = vec![Vec::new(); frames];
+    half_precision::stft(&x, &window, hop, &mut half_frames, &fft).unwrap();
+    let mut f32_frames = vec![Vec::new(); frames];
+    stft::stft(&widen(&x), &window, hop, &mut f32_frames, &fft).unwrap();
+    for (h, f) in half_frames.iter().zip(&f32_frames) {
+        let expected: Vec<ComplexBf16> = f
+            .iter()
+            .map(|c| ComplexBf16::new(bf16::from_f32(c.re), bf16::from_f32(c.im)))
+            .collect();
+        assert_eq!(h, &expected);
+    }
+
+    let mut back = vec![bf16::ZERO; 200];
+    half_precision::istft(&half_frames, &window, hop, &mut back, &fft).unwrap();
+    // Skip the edges, where the window sum is too small to normalize.
+    for (a, b) in widen(&back)
+        .iter()
+        .zip(widen(&x))
+        .skip(win_len)
+        .take(200 - 2 * win_len)
+    {
+        assert!((a - b).abs() <= 0.05, "{a} vs {b}");
+    }
+}
+
+#[test]
+fn mismatched_lengths_are_rejected() {
+    let mut short = [f16::ZERO; 3];
+    assert_eq!(
+        half_precision::from_f32(&[0.0; 4], &mut short),
+        Err(FftError::MismatchedLengths)
+    );
+    let plan = RealFftPlan::<f32>::new(8).unwrap();
+    let mut spectrum = [ComplexF16::zero(); 4];
+    assert_eq!(
+        half_precision::rfft(&plan, &[f16::ZERO; 8], &mut spectrum),
+        Err(FftError::MismatchedLengths)
+    );
+    let fft = ScalarFftImpl::<f32>::default();
+    let mut frames = vec![Vec::new(); 1];
+    assert_eq!(
+        half_precision::stft(&[f16::ZERO; 8], &[1.0; 4], 0, &mut frames, &fft),
+        Err(FftError::InvalidHopSize)
+    );
+}
@@ -0,0 +1,97 @@
+use kofft::fft::{
+    fft11, fft13, fft3, fft5, fft7, Complex, Complex32, Complex64, FftImpl, ScalarFftImpl,
+};
+use kofft::num::Float;
+
+/// Reference DFT bin evaluated in `f64`.
+fn dft_bin<T: Float>(input: &[Complex<T>], k: usize, to_f64: fn(T) -> f64) -> Complex64 {
+    let n = input.len();
+    let mut sum = Complex64::zero();
+    for (j, x) in input.iter().enumerate() {
+        let angle = -2.0 * std::f64::consts::PI * ((j * k) % n) as f64 / n as f64;
+        let x = Complex64::new(to_f64(x.re), to_f64(x.im));
+        sum = sum.add(x.mul(Complex64::expi(angle)));
+    }
+    sum
+}
+
+fn signal<T: Float>(n: usize) -> Vec<Complex<T>> {
+    (0..n)
+        .map(|i| {
+            let t = i as f64;
+            Complex::new(
+                T::from_f64((t * 0.731).sin() + 0.25),
+                T::from_f64((t * 0.193).cos() - 0.5 * (t * 0.057).sin()),
+            )
+        })
+        .collect()
+}
+
+/// Compare every bin for small sizes and a spread of bins for large ones.
+fn check<T: Float>(n: usize, tol: f64, to_f64: fn(T) -> f64) {
+    let input = signal::<T>(n);
+    let mut data = input.clone();
+    let fft = ScalarFftImpl::<T>::default();
+    fft.fft(&mut data).unwrap();
+    let step = if n <= 2048 { 1 } else { n / 61 };
+    let mut k = 0;
+    while k < n {
+        let want = dft_bin(&input, k, to_f64);
+        let err = (to_f64(data[k].re) - want.re).hypot(to_f64(data[k].im) - want.im);
+        assert!(err < tol * n as f64, "n={n} k={k} err={err}");
+        k += step;
+    }
+    fft.ifft(&mut data).unwrap();
+    for (a, b) in data.iter().zip(&input) {
+        assert!((to_f64(a.re) - to_f64(b.re)).abs() < tol * 100.0, "n={n}");
+        assert!((to_f64(a.im) - to_f64(b.im)).abs() < tol * 100.0, "n={n}");
+    }
+}
+
+const SIZES: &[usize] = &[
+    3, 5, 6, 7, 11, 12, 13, 17, 60, 97, 121, 169, 210, 1000, 1009, 2310, 4099, 44100, 48000,
+];
+
+#[test]
+fn mixed_radix_matches_dft_f32() {
+    for &n in SIZES {
+        check::<f32>(n, 1e-5, |x| x as f64);
+    }
+}
+
+#[test]
+fn mixed_radix_matches_dft_f64() {
+    for &n in SIZES {
+        check::<f64>(n, 1e-12, |x| x);
+    }
+}
+
+#[test]
+fn small_prime_butterflies_match_dft() {
+    for n in [3usize, 5, 7, 11, 13] {
+        let input = signal::<f32>(n);
+        let mut data = input.clone();
+        match n {
+            3 => fft3(&mut data),
+            5 => fft5(&mut data),
+            7 => fft7(&mut data),
+            11 => fft11(&mut data),
+            _ => fft13(&mut data),
+        }
+        for (k, out) in data.iter().enumerate() {
+            let want = dft_bin(&input, k, |x: f32| x as f64);
+            assert!((out.re as f64 - want.re).abs() < 1e-5, "n={n} k={k}");
+            assert!((out.im as f64 - want.im).abs() < 1e-5, "n={n} k={k}");
+        }
+    }
+}
+
+#[test]
+fn mixed_radix_reuses_plan_for_complex32() {
+    let fft = ScalarFftImpl::<f32>::default();
+    let mut a: Vec<Complex32> = signal(1000);
+    let mut b = a.clone();
+    fft.fft(&mut a).unwrap();
+    fft.fft_mixed_radix(&mut b).unwrap();
+    assert_eq!(a, b);
+}
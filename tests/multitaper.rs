@@ -0,0 +1,211 @@
+//! DPSS tapers against their defining eigenproblem, and multitaper PSDs.
+
+use kofft::fft::FftError;
+use kofft::multitaper::{self, dpss, Multitaper, Weighting};
+use kofft::psd::{self, Detrend, PsdConfig, Scaling, Sides};
+
+fn noise(n: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        })
+        .collect()
+}
+
+#[test]
+fn tapers_solve_the_sinc_eigenproblem() {
+    for (len, nw, count) in [(64usize, 4.0, 7usize), (101, 2.5, 4), (300, 1.5, 3)] {
+        let (tapers, ratios) = dpss(len, nw, count).unwrap();
+        let w = nw / len as f64;
+        let kernel = |m: usize, n: usize| {
+            if m == n {
+                2.0 * w
+            } else {
+                let d = m as f64 - n as f64;
+                (2.0 * std::f64::consts::PI * w * d).sin() / (std::f64::consts::PI * d)
+            }
+        };
+        for (k, (v, &ratio)) in tapers.iter().zip(&ratios).enumerate() {
+            // concentrated in the band: A·v = λ·v
+            for m in 0..len {
+                let av: f64 = (0..len).map(|n| kernel(m, n) * v[n]).sum();
+                assert!((av - ratio * v[m]).abs() < 1e-10, "taper {k}, sample {m}");
+            }
+            for (j, u) in tapers.iter().enumerate() {
+                let dot: f64 = u.iter().zip(v).map(|(a, b)| a * b).sum();
+                assert!((dot - (j == k) as u8 as f64).abs() < 1e-10);
+            }
+            // scipy's signs: even tapers symmetric with a positive sum, odd
+            // ones antisymmetric and starting positive
+            let parity = if k % 2 == 0 { 1.0 } else { -1.0 };
+            for i in 0..len {
+                assert!((v[i] - parity * v[len - 1 - i]).abs() < 1e-10);
+            }
+            if k % 2 == 0 {
+                assert!(v.iter().sum::<f64>() > 0.0);
+            } else {
+                assert!(v.iter().find(|x| x.abs() > 1e-3).unwrap() > &0.0);
+            }
+        }
+        assert!(ratios.windows(2).all(|r| r[0] > r[1]));
+        assert!(ratios[0] > 0.99 && ratios[0] < 1.0);
+    }
+
+    // two samples: the tridiagonal matrix is [[c/4, 1/2], [1/2, c/4]]
+    let (tapers, ratios) = dpss(2, 0.25, 2).unwrap();
+    let h = std::f64::consts::FRAC_1_SQRT_2;
+    assert!((tapers[0][0] - h).abs() < 1e-15 && (tapers[0][1] - h).abs() < 1e-15);
+    assert!((tapers[1][0].abs() - h).abs() < 1e-15 && (tapers[1][0] + tapers[1][1]).abs() < 1e-15);
+    let lead = 0.25 + (std::f64::consts::PI * 0.25).sin() / std::f64::consts::PI;
+    assert!((ratios[0] - lead).abs() < 1e-15);
+}
+
+#[test]
+fn single_taper_matches_the_periodogram() {
+    let x = noise(256, 9);
+    let config = PsdConfig {
+        fs: 100.0,
+        nfft: Some(512),
+        detrend: Detrend::Linear,
+        ..Default::default()
+    };
+    let mut mt = Multitaper::new(x.len(), 3.0, 1, &config)
+        .unwrap()
+        .with_weighting(Weighting::Unity);
+    let mut pxx = vec![0.0; mt.output_len()];
+    mt.estimate(&x, &mut pxx).unwrap();
+    let window: Vec<f32> = mt.tapers()[0].iter().map(|&v| v as f32).collect();
+    let (f, expected) = psd::periodogram(&x, Some(&window), &config).unwrap();
+    assert_eq!(mt.frequencies(), f);
+    for (a, b) in pxx.iter().zip(&expected) {
+        assert!((a - b).abs() < 1e-5 * b.abs().max(1e-3), "{a} vs {b}");
+    }
+}
+
+#[test]
+fn white_noise_density_integrates_to_variance() {
+    let x = noise(4096, 11);
+    let variance = x.iter().map(|v| v * v).sum::<f64>() / x.len() as f64;
+    let config = PsdConfig {
+        fs: 250.0,
+        ..Default::default()
+    };
+    for weighting in [Weighting::Unity, Weighting::Eigenvalue, Weighting::Adaptive] {
+        let mut mt = Multitaper::new(x.len(), 4.0, 7, &config)
+            .unwrap()
+            .with_weighting(weighting);
+        let mut pxx = vec![0.0; mt.output_len()];
+        mt.estimate(&x, &mut pxx).unwrap();
+        let f = mt.frequencies();
+        let power = pxx.iter().sum::<f64>() * (f[1] - f[0]);
+        assert!((power - variance).abs() < 0.02 * variance, "{weighting:?}");
+    }
+
+    // two-sided splits the one-sided estimate across ±f
+    let (_, one) = multitaper::psd(&x, 4.0, &config).unwrap();
+    let two_sided = PsdConfig {
+        sides: Sides::TwoSided,
+        ..config
+    };
+    let (f, two) = multitaper::psd(&x, 4.0, &two_sided).unwrap();
+    assert_eq!(two.len(), x.len());
+    assert_eq!(f[x.len() - 1], -f[1]);
+    assert!((two[0] - one[0]).abs() < 1e-15);
+    for k in 1..x.len() / 2 {
+        assert!((two[k] - one[k] / 2.0).abs() < 1e-12 * one[k]);
+        assert_eq!(two[k], two[x.len() - k]);
+    }
+}
+
+#[test]
+fn adaptive_weighting_suppresses_broadband_leakage() {
+    // a tone 60 dB above white noise
+    let n = 2048;
+    let fs = 1000.0;
+    let x: Vec<f64> = noise(n, 13)
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v + 300.0 * (2.0 * std::f64::consts::PI * 125.0 * i as f64 / fs).sin())
+        .collect();
+    let config = PsdConfig {
+        fs,
+        ..Default::default()
+    };
+    let floor = 2.0 / 12.0 / fs;
+    let far = |weighting| {
+        let mut mt = Multitaper::new(n, 4.0, 7, &config)
+            .unwrap()
+            .with_weighting(weighting);
+        let mut pxx = vec![0.0; mt.output_len()];
+        mt.estimate(&x, &mut pxx).unwrap();
+        // bins 40 Hz and more from the tone
+        let f = mt.frequencies();
+        let far: Vec<f64> = pxx
+            .iter()
+            .zip(&f)
+            .filter(|(_, &f)| (f - 125.0).abs() > 40.0 && f > 0.0 && f < fs / 2.0)
+            .map(|(&p, _)| p)
+            .collect();
+        far.iter().sum::<f64>() / far.len() as f64
+    };
+    let adaptive = far(Weighting::Adaptive);
+    assert!(
+        (adaptive - floor).abs() < 0.2 * floor,
+        "{adaptive} vs {floor}"
+    );
+    assert!(far(Weighting::Unity) > 2.0 * adaptive);
+}
+
+#[test]
+fn rejects_invalid_parameters() {
+    assert_eq!(dpss(0, 2.0, 1).unwrap_err(), FftError::EmptyInput);
+    assert_eq!(dpss(64, 2.0, 0).unwrap_err(), FftError::EmptyInput);
+    assert_eq!(dpss(4, 1.0, 5).unwrap_err(), FftError::InvalidValue);
+    assert_eq!(dpss(64, 32.0, 3).unwrap_err(), FftError::InvalidValue);
+    assert_eq!(dpss(64, 0.0, 3).unwrap_err(), FftError::InvalidValue);
+
+    let new = |config: PsdConfig| Multitaper::<f64>::new(64, 2.0, 3, &config).err();
+    for config in [
+        PsdConfig {
+            nfft: Some(63),
+            ..Default::default()
+        },
+        PsdConfig {
+            nfft: Some(66),
+            ..Default::default()
+        },
+        PsdConfig {
+            scaling: Scaling::Spectrum,
+            ..Default::default()
+        },
+        PsdConfig {
+            fs: f64::NAN,
+            ..Default::default()
+        },
+    ] {
+        let expected = if config.nfft == Some(66) {
+            None
+        } else {
+            Some(FftError::InvalidValue)
+        };
+        assert_eq!(new(config), expected);
+    }
+
+    let mut mt = Multitaper::<f64>::new(63, 2.0, 3, &PsdConfig::default()).unwrap();
+    assert_eq!((mt.signal_len(), mt.nfft(), mt.output_len()), (63, 64, 33));
+    let mut out = vec![0.0; 33];
+    assert_eq!(
+        mt.estimate(&[0.0; 64], &mut out),
+        Err(FftError::MismatchedLengths)
+    );
+    assert_eq!(
+        mt.estimate(&[0.0; 63], &mut out[..32]),
+        Err(FftError::MismatchedLengths)
+    );
+    mt.estimate(&[0.0; 63], &mut out).unwrap();
+    assert!(out.iter().all(|&p| p == 0.0));
+}
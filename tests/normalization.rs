@@ -0,0 +1,267 @@
+//! `Normalization` conventions across the plan types: every variant
+//! round-trips, the scale factors land where numpy puts them, and the
+//! orthonormal DCT/DST matrices are orthogonal.
+
+use kofft::dct::{self, DctPlan, DctType};
+use kofft::dst::{self, DstPlan, DstType};
+use kofft::fft::{Complex32, Complex64, FftPlan, FftStrategy, ScalarFftImpl};
+use kofft::hartley::HartleyPlanner;
+use kofft::ndfft::{fft2d_inplace_norm, ifft2d_inplace_norm};
+use kofft::rfft::RealFftPlan;
+use kofft::Normalization;
+
+mod common;
+use common::{assert_close, signal_f32};
+
+const ALL: [Normalization; 4] = [
+    Normalization::Backward,
+    Normalization::Forward,
+    Normalization::Ortho,
+    Normalization::None,
+];
+
+fn energy(x: &[f32]) -> f32 {
+    x.iter().map(|v| v * v).sum()
+}
+
+#[test]
+fn complex_plan_scales_like_numpy() {
+    let n = 48;
+    let input: Vec<Complex64> = (0..n)
+        .map(|i| Complex64::new((i as f64 * 0.3).sin(), (i as f64 * 0.7).cos()))
+        .collect();
+    let mut reference = input.clone();
+    FftPlan::<f64>::new(n, FftStrategy::Auto)
+        .fft(&mut reference)
+        .unwrap();
+    for norm in ALL {
+        let plan = FftPlan::<f64>::new(n, FftStrategy::Auto).with_normalization(norm);
+        assert_eq!(plan.normalization(), norm);
+        let forward = match norm {
+            Normalization::Forward => 1.0 / n as f64,
+            Normalization::Ortho => 1.0 / (n as f64).sqrt(),
+            _ => 1.0,
+        };
+        let mut data = input.clone();
+        plan.fft(&mut data).unwrap();
+        for (a, b) in data.iter().zip(&reference) {
+            assert!((a.re - b.re * forward).abs() < 1e-9);
+            assert!((a.im - b.im * forward).abs() < 1e-9);
+        }
+        plan.ifft(&mut data).unwrap();
+        let round_trip = if norm == Normalization::None {
+            n as f64
+        } else {
+            1.0
+        };
+        for (a, b) in data.iter().zip(&input) {
+            assert!((a.re - b.re * round_trip).abs() < 1e-9);
+            assert!((a.im - b.im * round_trip).abs() < 1e-9);
+        }
+
+        // The scratch and split-format paths follow the same convention.
+        let mut scratch = vec![Complex64::zero(); plan.split_scratch_len()];
+        let mut with_scratch = input.clone();
+        plan.fft_with_scratch(&mut with_scratch, &mut scratch)
+            .unwrap();
+        let mut re: Vec<f64> = input.iter().map(|c| c.re).collect();
+        let mut im: Vec<f64> = input.iter().map(|c| c.im).collect();
+        plan.fft_split(&mut re, &mut im).unwrap();
+        for ((a, r), b) in with_scratch.iter().zip(&re).zip(&reference) {
+            assert!((a.re - b.re * forward).abs() < 1e-9);
+            assert!((r - b.re * forward).abs() < 1e-9);
+        }
+        plan.ifft_split(&mut re, &mut im).unwrap();
+        for (r, b) in re.iter().zip(&input) {
+            assert!((r - b.re * round_trip).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn real_plan_round_trips_under_every_normalization() {
+    let n = 64;
+    let x = signal_f32(n);
+    for norm in ALL {
+        let plan = RealFftPlan::<f32>::new(n).unwrap().with_normalization(norm);
+        let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+        let mut spectrum = vec![Complex32::zero(); plan.spectrum_len()];
+        let mut back = vec![0.0f32; n];
+        plan.rfft_with_scratch(&x, &mut spectrum, &mut scratch)
+            .unwrap();
+        plan.irfft_with_scratch(&spectrum, &mut back, &mut scratch)
+            .unwrap();
+        if norm == Normalization::None {
+            back.iter_mut().for_each(|v| *v /= n as f32);
+        }
+        assert_close(&back, &x, 1e-4);
+        if norm == Normalization::Ortho {
+            // Parseval: the one-sided spectrum counts interior bins twice.
+            let spec_energy: f32 = spectrum
+                .iter()
+                .enumerate()
+                .map(|(k, c)| {
+                    let w = if k == 0 || k == n / 2 { 1.0 } else { 2.0 };
+                    w * (c.re * c.re + c.im * c.im)
+                })
+                .sum();
+            assert!((spec_energy - energy(&x)).abs() < 1e-3 * energy(&x));
+        }
+    }
+}
+
+/// Columns of the plan's forward matrix, obtained from unit impulses.
+fn matrix(n: usize, run: impl Fn(&[f32], &mut [f32])) -> Vec<Vec<f32>> {
+    (0..n)
+        .map(|j| {
+            let mut e = vec![0.0f32; n];
+            e[j] = 1.0;
+            let mut col = vec![0.0f32; n];
+            run(&e, &mut col);
+            col
+        })
+        .collect()
+}
+
+fn assert_orthogonal(columns: &[Vec<f32>]) {
+    for (i, a) in columns.iter().enumerate() {
+        for (j, b) in columns.iter().enumerate() {
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((dot - expected).abs() < 1e-4, "({i}, {j}): {dot}");
+        }
+    }
+}
+
+#[test]
+fn dct_plans_invert_and_orthonormalize() {
+    for n in [1usize, 2, 3, 8, 17] {
+        let x = signal_f32(n);
+        for kind in [DctType::I, DctType::II, DctType::III, DctType::IV] {
+            for norm in ALL {
+                let plan = DctPlan::new(kind, n).unwrap().with_normalization(norm);
+                let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+                let mut y = vec![0.0f32; n];
+                let mut back = vec![0.0f32; n];
+                plan.execute_with_scratch(&x, &mut y, &mut scratch).unwrap();
+                plan.inverse_with_scratch(&y, &mut back, &mut scratch)
+                    .unwrap();
+                if norm != Normalization::None {
+                    assert_close(&back, &x, 1e-4);
+                }
+                if norm == Normalization::Ortho {
+                    assert!((energy(&y) - energy(&x)).abs() < 1e-4 * energy(&x));
+                    assert_orthogonal(&matrix(n, |input, out| {
+                        plan.execute_with_scratch(input, out, &mut scratch.clone())
+                            .unwrap()
+                    }));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn dst_plans_invert_and_orthonormalize() {
+    for n in [1usize, 2, 3, 8, 17] {
+        let x = signal_f32(n);
+        for kind in [DstType::I, DstType::II, DstType::III, DstType::IV] {
+            for norm in ALL {
+                let plan = DstPlan::new(kind, n).unwrap().with_normalization(norm);
+                let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+                let mut y = vec![0.0f32; n];
+                let mut back = vec![0.0f32; n];
+                plan.execute_with_scratch(&x, &mut y, &mut scratch).unwrap();
+                plan.inverse_with_scratch(&y, &mut back, &mut scratch)
+                    .unwrap();
+                if norm != Normalization::None {
+                    assert_close(&back, &x, 1e-4);
+                }
+                if norm == Normalization::Ortho {
+                    assert!((energy(&y) - energy(&x)).abs() < 1e-4 * energy(&x));
+                    assert_orthogonal(&matrix(n, |input, out| {
+                        plan.execute_with_scratch(input, out, &mut scratch.clone())
+                            .unwrap()
+                    }));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn ortho_dct2_matches_scipy() {
+    // scipy.fft.dct([1, 2, 3, 4], type=2, norm="ortho")
+    let expected = [5.0, -2.2304424, 0.0, -0.15851267];
+    let plan = DctPlan::new(DctType::II, 4)
+        .unwrap()
+        .with_normalization(Normalization::Ortho);
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    let mut y = [0.0f32; 4];
+    plan.execute_with_scratch(&[1.0, 2.0, 3.0, 4.0], &mut y, &mut scratch)
+        .unwrap();
+    assert_close(&y, &expected, 1e-5);
+}
+
+#[test]
+fn unscaled_dst2_dst3_pair_has_gain_n_over_2() {
+    let x = signal_f32(9);
+    let back = dst::dst3(&dst::dst2(&x));
+    let scaled: Vec<f32> = back.iter().map(|v| v * 2.0 / 9.0).collect();
+    assert_close(&scaled, &x, 1e-4);
+    let back = dct::dct3(&dct::dct2(&x));
+    let scaled: Vec<f32> = back.iter().map(|v| v * 2.0 / 9.0).collect();
+    assert_close(&scaled, &x, 1e-4);
+}
+
+#[test]
+fn hartley_round_trips_under_every_normalization() {
+    for n in [7usize, 16] {
+        let x = signal_f32(n);
+        for norm in ALL {
+            let mut planner = HartleyPlanner::<f32>::new().with_normalization(norm);
+            let mut y = vec![0.0f32; n];
+            let mut back = vec![0.0f32; n];
+            planner.dht(&x, &mut y).unwrap();
+            planner.idht(&y, &mut back).unwrap();
+            if norm == Normalization::None {
+                back.iter_mut().for_each(|v| *v /= n as f32);
+            }
+            assert_close(&back, &x, 1e-4);
+            if norm == Normalization::Ortho {
+                // The orthonormal DHT is an involution.
+                planner.dht_inplace(&mut y).unwrap();
+                assert_close(&y, &x, 1e-4);
+            }
+        }
+    }
+}
+
+#[test]
+fn nd_fft_round_trips_under_every_normalization() {
+    let (rows, cols) = (4, 6);
+    let fft = ScalarFftImpl::<f32>::default();
+    let input: Vec<Complex32> = signal_f32(rows * cols)
+        .into_iter()
+        .map(|v| Complex32::new(v, -v))
+        .collect();
+    for norm in ALL {
+        let mut data = input.clone();
+        let mut col = vec![Complex32::zero(); rows];
+        fft2d_inplace_norm(&mut data, rows, cols, &fft, &mut col, norm).unwrap();
+        if norm == Normalization::Forward {
+            let mean = input.iter().fold(Complex32::zero(), |acc, &c| acc.add(c));
+            assert!((data[0].re - mean.re / (rows * cols) as f32).abs() < 1e-5);
+        }
+        ifft2d_inplace_norm(&mut data, rows, cols, &fft, &mut col, norm).unwrap();
+        let round_trip = if norm == Normalization::None {
+            (rows * cols) as f32
+        } else {
+            1.0
+        };
+        for (a, b) in data.iter().zip(&input) {
+            assert!((a.re - b.re * round_trip).abs() < 1e-4);
+            assert!((a.im - b.im * round_trip).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "parallel")]
+//! Plans and batch APIs running on caller-chosen workers.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use kofft::fft::{
+    Complex32, Complex64, FftError, FftImpl, FftPlanner, ParallelFftConfig, ScalarFftImpl,
+    SharedFftPlan,
+};
+use kofft::four_step::FourStepPlan;
+use kofft::parallel::{Executor, Parallelism};
+use kofft::stft;
+
+mod common;
+use common::signal_f32;
+
+/// Runs every task on its own scoped thread and counts them.
+#[derive(Default)]
+struct CountingExecutor {
+    tasks: AtomicUsize,
+}
+
+impl Executor for CountingExecutor {
+    fn threads(&self) -> usize {
+        3
+    }
+
+    fn run(&self, tasks: usize, task: &(dyn Fn(usize) + Sync)) {
+        self.tasks.fetch_add(tasks, Ordering::Relaxed);
+        std::thread::scope(|s| {
+            for i in 0..tasks {
+                s.spawn(move || task(i));
+            }
+        });
+    }
+}
+
+fn always() -> ParallelFftConfig {
+    ParallelFftConfig {
+        threshold: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn per_handle_threshold() {
+    assert!(!Parallelism::serial().should_parallelize(usize::MAX));
+    let par = Parallelism::global().with_config(ParallelFftConfig {
+        threshold: 4096,
+        ..Default::default()
+    });
+    assert!(par.should_parallelize(4096));
+    assert!(!par.should_parallelize(4095));
+    assert_eq!(par.config().threshold, 4096);
+    assert_eq!(Parallelism::serial().threads(), 1);
+    assert_eq!(
+        Parallelism::executor(Arc::new(CountingExecutor::default())).threads(),
+        3
+    );
+}
+
+#[test]
+fn four_step_on_custom_executor_matches_serial() {
+    let n = 1 << 12;
+    let x: Vec<Complex64> = (0..n)
+        .map(|i| Complex64::new((i as f64 * 0.3).sin(), (i as f64 * 0.05).cos()))
+        .collect();
+    let executor = Arc::new(CountingExecutor::default());
+    let plan = FourStepPlan::<f64>::new(n)
+        .unwrap()
+        .with_parallelism(Parallelism::executor(executor.clone()));
+    let mut serial = x.clone();
+    let mut scratch = vec![Complex64::zero(); plan.scratch_len()];
+    plan.fft(&mut serial, &mut scratch).unwrap();
+    let mut data = x.clone();
+    plan.fft_parallel(&mut data, &mut scratch).unwrap();
+    assert_eq!(data, serial);
+    assert!(executor.tasks.load(Ordering::Relaxed) > 0);
+
+    plan.ifft_parallel(&mut data, &mut scratch).unwrap();
+    for (a, b) in data.iter().zip(&x) {
+        assert!((a.re - b.re).abs() < 1e-12 && (a.im - b.im).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn planner_parallelism_drives_scalar_impl() {
+    let executor = Arc::new(CountingExecutor::default());
+    let mut planner = FftPlanner::<f32>::new();
+    planner.set_parallelism(Parallelism::executor(executor.clone()).with_config(always()));
+    let fft = ScalarFftImpl::with_planner(planner);
+    let x: Vec<Complex32> = signal_f32(128)
+        .into_iter()
+        .map(|v| Complex32::new(v, -v))
+        .collect();
+    let mut data = x.clone();
+    fft.fft(&mut data).unwrap();
+    fft.ifft(&mut data).unwrap();
+    assert!(executor.tasks.load(Ordering::Relaxed) > 0);
+    for (a, b) in data.iter().zip(&x) {
+        assert!((a.re - b.re).abs() < 1e-5 && (a.im - b.im).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn stft_on_private_pool_matches_serial() {
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+    let x = signal_f32(1000);
+    let window = kofft::window::hann(64);
+    let hop = 16;
+    let frames = x.len().div_ceil(hop);
+    let plan = SharedFftPlan::<f32>::new(64)
+        .unwrap()
+        .with_parallelism(Parallelism::rayon(pool));
+
+    let mut expected = vec![vec![]; frames];
+    stft::stft(&x, &window, hop, &mut expected, &ScalarFftImpl::default()).unwrap();
+    let mut spectra = vec![vec![]; frames];
+    stft::parallel_with_plan(&x, &window, hop, &mut spectra, &plan).unwrap();
+    for (a, b) in spectra.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((a.re - b.re).abs() < 1e-4 && (a.im - b.im).abs() < 1e-4);
+    }
+
+    let mut out = vec![0.0; x.len()];
+    stft::inverse_parallel_with_plan(&spectra, &window, hop, &mut out, &plan).unwrap();
+    let serial = SharedFftPlan::<f32>::new(64)
+        .unwrap()
+        .with_parallelism(Parallelism::serial());
+    let mut serial_out = vec![0.0; x.len()];
+    stft::inverse_parallel_with_plan(&spectra, &window, hop, &mut serial_out, &serial).unwrap();
+    assert_eq!(out, serial_out);
+}
+
+#[test]
+fn executor_reports_task_errors() {
+    let executor = Arc::new(CountingExecutor::default());
+    let plan = SharedFftPlan::<f32>::new(8)
+        .unwrap()
+        .with_parallelism(Parallelism::executor(executor));
+    let mut frames = vec![vec![Complex32::zero(); 8]; 10];
+    frames[7].pop();
+    let mut out = vec![0.0; 100];
+    assert_eq!(
+        stft::inverse_parallel_with_plan(&frames, &kofft::window::hann(8), 4, &mut out, &plan),
+        Err(FftError::MismatchedLengths)
+    );
+}
@@ -0,0 +1,153 @@
+//! Partitioned convolution against direct convolution, impulse-response
+//! swaps, and the no-allocation guarantee.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use kofft::conv::{self, Method, Mode, PartitionedConvolver, Partitioning};
+use kofft::fft::FftError;
+
+mod common;
+use common::{close, seeded_signal};
+
+/// Counts allocations made by the current thread.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Decaying noise-like response.
+fn response(n: usize, seed: f64) -> Vec<f64> {
+    seeded_signal(n, seed)
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v * (-(i as f64) / n as f64).exp())
+        .collect()
+}
+
+fn run(conv: &mut PartitionedConvolver<f64>, x: &[f64]) -> Vec<f64> {
+    let b = conv.block_len();
+    let mut y = vec![0.0; x.len()];
+    for (inp, out) in x.chunks(b).zip(y.chunks_mut(b)) {
+        conv.process(inp, out).unwrap();
+    }
+    y
+}
+
+#[test]
+fn matches_direct_convolution() {
+    let x = seeded_signal(64 * 80, 0.9);
+    for ir_len in [1usize, 63, 64, 1000, 3001] {
+        let h = response(ir_len, 1.7);
+        let expected = conv::convolve_with(&x, &h, Mode::Full, Method::Direct).unwrap();
+        for (block, partitioning) in [
+            (4, Partitioning::Uniform),
+            (64, Partitioning::Uniform),
+            (16, Partitioning::NonUniform { max_block: 16 }),
+            (16, Partitioning::NonUniform { max_block: 256 }),
+            (64, Partitioning::NonUniform { max_block: 1024 }),
+        ] {
+            let mut conv = PartitionedConvolver::new(&h, block, partitioning).unwrap();
+            let covered: usize = conv.layout().map(|(len, parts)| len * parts).sum();
+            assert!(covered >= ir_len);
+            let y = run(&mut conv, &x);
+            close(&y, &expected[..x.len()], 1e-9);
+        }
+    }
+}
+
+#[test]
+fn swaps_response_with_a_one_block_fade() {
+    let b = 32;
+    let old = response(2000, 1.3);
+    let new = response(1500, 2.9);
+    let x = seeded_signal(b * 200, 0.4);
+    let y_old = conv::convolve_with(&x, &old, Mode::Full, Method::Fft).unwrap();
+    let y_new = conv::convolve_with(&x, &new, Mode::Full, Method::Fft).unwrap();
+
+    for partitioning in [
+        Partitioning::Uniform,
+        Partitioning::NonUniform { max_block: 512 },
+    ] {
+        let mut conv = PartitionedConvolver::new(&old, b, partitioning).unwrap();
+        let swap_at = 20 * b;
+        let mut y = run(&mut conv, &x[..swap_at]);
+        conv.set_impulse_response(&new).unwrap();
+        assert!(conv.is_swapping());
+        assert_eq!(conv.set_impulse_response(&old), Err(FftError::InvalidValue));
+        y.extend(run(&mut conv, &x[swap_at..]));
+        assert!(!conv.is_swapping());
+
+        let fade = swap_at + conv.swap_delay();
+        close(&y[..fade], &y_old[..fade], 1e-9);
+        for i in 0..b {
+            let w = (i + 1) as f64 / b as f64;
+            let t = fade + i;
+            let want = y_old[t] + (y_new[t] - y_old[t]) * w;
+            assert!((y[t] - want).abs() < 1e-9, "fade sample {i}");
+        }
+        close(&y[fade + b..], &y_new[fade + b..x.len()], 1e-9);
+    }
+}
+
+#[test]
+fn processing_and_swapping_do_not_allocate() {
+    let h = response(4800, 0.7);
+    let mut conv =
+        PartitionedConvolver::new(&h, 64, Partitioning::NonUniform { max_block: 1024 }).unwrap();
+    let x = seeded_signal(64, 0.2);
+    let shorter = response(3000, 1.1);
+    let mut out = vec![0.0; 64];
+    let before = allocations();
+    for i in 0..100 {
+        if i == 10 {
+            conv.set_impulse_response(&shorter).unwrap();
+        }
+        conv.process(&x, &mut out).unwrap();
+    }
+    conv.reset();
+    assert_eq!(allocations(), before);
+}
+
+#[test]
+fn rejects_bad_configuration() {
+    assert!(matches!(
+        PartitionedConvolver::<f32>::new(&[], 64, Partitioning::Uniform),
+        Err(FftError::EmptyInput)
+    ));
+    assert!(matches!(
+        PartitionedConvolver::<f32>::new(&[1.0], 64, Partitioning::NonUniform { max_block: 96 }),
+        Err(FftError::InvalidValue)
+    ));
+    let mut conv =
+        PartitionedConvolver::<f32>::new(&[1.0; 100], 16, Partitioning::Uniform).unwrap();
+    assert_eq!(conv.max_ir_len(), 100);
+    assert_eq!(
+        conv.set_impulse_response(&[1.0; 101]),
+        Err(FftError::MismatchedLengths)
+    );
+    let mut out = [0.0; 8];
+    assert_eq!(
+        conv.process(&[0.0; 8], &mut out),
+        Err(FftError::MismatchedLengths)
+    );
+}
@@ -0,0 +1,120 @@
+use kofft::fft::{Complex32, FftImpl, FftPlan, FftPlanner, FftStrategy, PlanMode, ScalarFftImpl};
+
+mod common;
+use common::complex_signal;
+
+#[test]
+fn estimate_does_not_remember() {
+    let mut planner = FftPlanner::<f32>::new();
+    assert_eq!(planner.mode(), PlanMode::Estimate);
+    assert_eq!(planner.plan_strategy(1024), FftStrategy::SplitRadix);
+    assert_eq!(planner.plan_strategy(1000), FftStrategy::Auto);
+    assert_eq!(planner.remembered_strategy(1024), None);
+}
+
+#[test]
+fn measure_remembers_winner() {
+    let mut planner = FftPlanner::<f64>::with_mode(PlanMode::Measure);
+    for n in [256usize, 1000, 1009] {
+        let chosen = planner.plan_strategy(n);
+        assert_ne!(chosen, FftStrategy::Auto);
+        assert_eq!(planner.remembered_strategy(n), Some(chosen));
+        // A second request reuses the stored decision.
+        assert_eq!(planner.plan_strategy(n), chosen);
+    }
+    planner.clear_strategies();
+    assert_eq!(planner.remembered_strategy(256), None);
+}
+
+#[test]
+fn exhaustive_considers_radix4_and_bluestein() {
+    let mut planner = FftPlanner::<f32>::with_mode(PlanMode::Exhaustive);
+    let chosen = planner.plan_strategy(64);
+    assert!(matches!(
+        chosen,
+        FftStrategy::SplitRadix
+            | FftStrategy::Radix2
+            | FftStrategy::Radix4
+            | FftStrategy::MixedRadix
+            | FftStrategy::Bluestein
+    ));
+}
+
+#[test]
+fn measured_planner_results_match_estimate() {
+    for n in [64usize, 210, 1009, 4096] {
+        let input = complex_signal(n);
+        let mut expected = input.clone();
+        ScalarFftImpl::<f64>::default().fft(&mut expected).unwrap();
+
+        let mut planner = FftPlanner::<f64>::with_mode(PlanMode::Measure);
+        planner.plan_strategy(n);
+        let fft = ScalarFftImpl::with_planner(planner);
+        let mut data = input.clone();
+        fft.fft(&mut data).unwrap();
+        let mut auto = input.clone();
+        fft.fft_with_strategy(&mut auto, FftStrategy::Auto).unwrap();
+        for ((a, b), c) in data.iter().zip(&expected).zip(&auto) {
+            assert!((a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9);
+            assert!((c.re - b.re).abs() < 1e-9 && (c.im - b.im).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn every_strategy_is_correct_for_any_length() {
+    let fft = ScalarFftImpl::<f64>::default();
+    for n in [16usize, 64, 45, 97] {
+        let input = complex_signal(n);
+        let mut expected = input.clone();
+        fft.fft(&mut expected).unwrap();
+        for strategy in [
+            FftStrategy::Radix2,
+            FftStrategy::Radix4,
+            FftStrategy::SplitRadix,
+            FftStrategy::MixedRadix,
+            FftStrategy::Bluestein,
+        ] {
+            let mut data = input.clone();
+            fft.fft_with_strategy(&mut data, strategy).unwrap();
+            for (a, b) in data.iter().zip(&expected) {
+                assert!((a.re - b.re).abs() < 1e-9, "{strategy:?} n={n}");
+                assert!((a.im - b.im).abs() < 1e-9, "{strategy:?} n={n}");
+            }
+        }
+    }
+}
+
+#[test]
+fn remembered_radix_strategies_drive_fft() {
+    let n = 256;
+    let input = complex_signal(n);
+    let mut expected = input.clone();
+    ScalarFftImpl::<f64>::default().fft(&mut expected).unwrap();
+    for strategy in [FftStrategy::Radix2, FftStrategy::Radix4] {
+        let mut planner = FftPlanner::<f64>::new();
+        planner.remember_strategy(n, strategy);
+        let fft = ScalarFftImpl::with_planner(planner);
+        let mut data = input.clone();
+        fft.fft(&mut data).unwrap();
+        for (a, b) in data.iter().zip(&expected) {
+            assert!((a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn fft_plan_with_mode_round_trips() {
+    let n = 360;
+    let plan = FftPlan::<f32>::with_mode(n, PlanMode::Measure);
+    assert_ne!(plan.strategy, FftStrategy::Auto);
+    let input: Vec<Complex32> = (0..n)
+        .map(|i| Complex32::new(i as f32 / n as f32, 1.0 - i as f32 / n as f32))
+        .collect();
+    let mut data = input.clone();
+    plan.fft(&mut data).unwrap();
+    plan.ifft(&mut data).unwrap();
+    for (a, b) in data.iter().zip(&input) {
+        assert!((a.re - b.re).abs() < 1e-5 && (a.im - b.im).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,250 @@
+//! Spectral density estimates against scipy.signal's conventions.
+
+use kofft::fft::{Complex64, FftError};
+use kofft::psd::{self, Detrend, PsdConfig, Scaling, Sides};
+use kofft::window::{hamming, hann};
+use kofft::window_more::tukey;
+
+fn noise(n: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        })
+        .collect()
+}
+
+/// scipy's `_spectral_helper`, transcribed with a direct DFT.
+fn reference(x: &[f64], y: &[f64], window: &[f32], c: &PsdConfig) -> Vec<Complex64> {
+    let nperseg = window.len();
+    let overlap = c.overlap.unwrap_or(nperseg / 2);
+    let nfft = c.nfft.unwrap_or(nperseg);
+    let w: Vec<f64> = window.iter().map(|&v| v as f64).collect();
+    let scale = match c.scaling {
+        Scaling::Density => 1.0 / (c.fs * w.iter().map(|v| v * v).sum::<f64>()),
+        Scaling::Spectrum => 1.0 / w.iter().sum::<f64>().powi(2),
+    };
+    let detrend = |s: &[f64]| -> Vec<f64> {
+        let n = s.len() as f64;
+        let mean = s.iter().sum::<f64>() / n;
+        match c.detrend {
+            Detrend::None => s.to_vec(),
+            Detrend::Constant => s.iter().map(|v| v - mean).collect(),
+            Detrend::Linear => {
+                let t: Vec<f64> = (0..s.len()).map(|i| i as f64).collect();
+                let tm = (n - 1.0) / 2.0;
+                let slope = t.iter().zip(s).map(|(t, v)| (t - tm) * v).sum::<f64>()
+                    / t.iter().map(|t| (t - tm) * (t - tm)).sum::<f64>();
+                s.iter()
+                    .zip(&t)
+                    .map(|(v, t)| v - mean - slope * (t - tm))
+                    .collect()
+            }
+        }
+    };
+    let dft = |s: &[f64]| -> Vec<Complex64> {
+        let d = detrend(s);
+        (0..nfft)
+            .map(|k| {
+                d.iter()
+                    .zip(&w)
+                    .enumerate()
+                    .fold(Complex64::zero(), |acc, (i, (v, w))| {
+                        let a = -2.0 * std::f64::consts::PI * (k * i) as f64 / nfft as f64;
+                        Complex64::new(acc.re + v * w * a.cos(), acc.im + v * w * a.sin())
+                    })
+            })
+            .collect()
+    };
+    let step = nperseg - overlap;
+    let segments = (x.len() - nperseg) / step + 1;
+    let mut out = vec![Complex64::zero(); nfft];
+    for s in 0..segments {
+        let fx = dft(&x[s * step..s * step + nperseg]);
+        let fy = dft(&y[s * step..s * step + nperseg]);
+        for k in 0..nfft {
+            let p = Complex64::new(fx[k].re, -fx[k].im).mul(fy[k]);
+            out[k].re += p.re * scale / segments as f64;
+            out[k].im += p.im * scale / segments as f64;
+        }
+    }
+    if c.sides == Sides::OneSided {
+        out.truncate(nfft / 2 + 1);
+        // DC and an even length's Nyquist bin have no twin
+        for v in &mut out[1..nfft.div_ceil(2)] {
+            v.re *= 2.0;
+            v.im *= 2.0;
+        }
+    }
+    out
+}
+
+#[test]
+fn periodogram_matches_scipy_values() {
+    // scipy.signal.periodogram([1, 2, 3, 4])
+    let (f, p) = psd::periodogram(&[1.0f64, 2.0, 3.0, 4.0], None, &PsdConfig::default()).unwrap();
+    assert_eq!(f, [0.0, 0.25, 0.5]);
+    for (a, b) in p.iter().zip([0.0, 4.0, 1.0]) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    // detrend=False, return_onesided=False, scaling='spectrum', fs=8
+    let config = PsdConfig {
+        fs: 8.0,
+        detrend: Detrend::None,
+        sides: Sides::TwoSided,
+        scaling: Scaling::Spectrum,
+        ..Default::default()
+    };
+    let (f, p) = psd::periodogram(&[1.0f64, 2.0, 3.0, 4.0], None, &config).unwrap();
+    assert_eq!(f, [0.0, 2.0, -4.0, -2.0]);
+    for (a, b) in p.iter().zip([6.25, 0.5, 0.25, 0.5]) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    // nfft shorter than the signal truncates it
+    let (_, short) = psd::periodogram(
+        &[1.0f64, 2.0, 3.0, 4.0, 9.0],
+        None,
+        &PsdConfig {
+            nfft: Some(4),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(short.len(), 3);
+    assert!((short[1] - 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn welch_and_csd_match_reference() {
+    let x = noise(1000, 1);
+    let y: Vec<f64> = noise(1000, 2)
+        .iter()
+        .zip(&x)
+        .map(|(n, x)| 0.5 * n + x)
+        .collect();
+    let configs = [
+        PsdConfig::default(),
+        PsdConfig {
+            fs: 48_000.0,
+            overlap: Some(10),
+            nfft: Some(75),
+            detrend: Detrend::Linear,
+            ..Default::default()
+        },
+        PsdConfig {
+            overlap: Some(0),
+            nfft: Some(128),
+            detrend: Detrend::None,
+            sides: Sides::TwoSided,
+            scaling: Scaling::Spectrum,
+            ..Default::default()
+        },
+    ];
+    for (window, config) in [
+        (hann(64), &configs[0]),
+        (hamming(50), &configs[1]),
+        (tukey(100, 0.25), &configs[2]),
+    ] {
+        let expected = reference(&x, &y, &window, config);
+        let (f, pxy) = psd::csd(&x, &y, &window, config).unwrap();
+        assert_eq!(f.len(), expected.len());
+        for (a, b) in pxy.iter().zip(&expected) {
+            assert!((a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9);
+        }
+        let expected = reference(&x, &x, &window, config);
+        let (_, pxx) = psd::welch(&x, &window, config).unwrap();
+        for (a, b) in pxx.iter().zip(&expected) {
+            assert!((a - b.re).abs() < 1e-9);
+        }
+        let df = config.fs / config.nfft.unwrap_or(window.len()) as f64;
+        assert!((f[1] - df).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn white_noise_density_integrates_to_variance() {
+    let x = noise(1 << 15, 7);
+    let variance = x.iter().map(|v| v * v).sum::<f64>() / x.len() as f64;
+    let config = PsdConfig {
+        fs: 100.0,
+        ..Default::default()
+    };
+    let (f, p) = psd::welch(&x, &hann(256), &config).unwrap();
+    let power: f64 = p.iter().sum::<f64>() * (f[1] - f[0]);
+    assert!((power - variance).abs() < 0.02 * variance);
+    // flat at 2σ²/fs
+    let mid = p[20..100].iter().sum::<f64>() / 80.0;
+    assert!((mid - 2.0 * variance / config.fs).abs() < 0.1 * mid);
+}
+
+#[test]
+fn coherence_separates_related_and_independent_signals() {
+    let x = noise(8192, 3);
+    // y is x through a short FIR filter plus a little independent noise
+    let y: Vec<f64> = (0..x.len())
+        .map(|i| x[i] + 0.5 * x[i.saturating_sub(1)] + 0.01 * noise(1, i as u64)[0])
+        .collect();
+    let (_, related) = psd::coherence(&x, &y, &hann(128), &PsdConfig::default()).unwrap();
+    assert!(related[1..60].iter().all(|&c| c > 0.95 && c <= 1.0 + 1e-12));
+    let z = noise(8192, 4);
+    let (_, unrelated) = psd::coherence(&x, &z, &hann(128), &PsdConfig::default()).unwrap();
+    let mean = unrelated.iter().sum::<f64>() / unrelated.len() as f64;
+    assert!(mean < 0.1);
+
+    // the single pass agrees with the separate estimates
+    let config = PsdConfig::default();
+    let (_, pxy) = psd::csd(&x, &y, &hann(128), &config).unwrap();
+    let (_, pxx) = psd::welch(&x, &hann(128), &config).unwrap();
+    let (_, pyy) = psd::welch(&y, &hann(128), &config).unwrap();
+    for (k, &c) in related.iter().enumerate() {
+        let expected = (pxy[k].re * pxy[k].re + pxy[k].im * pxy[k].im) / pxx[k] / pyy[k];
+        assert!((c - expected).abs() < 1e-12, "bin {k}: {c} vs {expected}");
+    }
+}
+
+#[test]
+fn rejects_invalid_configuration() {
+    let x = noise(100, 5);
+    let w = hann(32);
+    let bad = |config: PsdConfig| psd::welch(&x, &w, &config).unwrap_err();
+    assert_eq!(
+        bad(PsdConfig {
+            overlap: Some(32),
+            ..Default::default()
+        }),
+        FftError::InvalidValue
+    );
+    assert_eq!(
+        bad(PsdConfig {
+            nfft: Some(16),
+            ..Default::default()
+        }),
+        FftError::InvalidValue
+    );
+    assert_eq!(
+        bad(PsdConfig {
+            fs: 0.0,
+            ..Default::default()
+        }),
+        FftError::InvalidValue
+    );
+    assert_eq!(
+        psd::welch(&x[..16], &w, &PsdConfig::default()),
+        Err(FftError::MismatchedLengths)
+    );
+    assert_eq!(
+        psd::csd(&x, &x[1..], &w, &PsdConfig::default()).unwrap_err(),
+        FftError::MismatchedLengths
+    );
+    assert_eq!(
+        psd::periodogram(&x, Some(&w), &PsdConfig::default()).unwrap_err(),
+        FftError::MismatchedLengths
+    );
+    assert_eq!(
+        psd::welch::<f32>(&[], &w, &PsdConfig::default()).unwrap_err(),
+        FftError::EmptyInput
+    );
+}
@@ -27,7 +27,7 @@ fn rfft_matches_scalar() {
     #[cfg(all(feature = "x86_64", target_arch = "x86_64"))]
     {
         use kofft::fft::SimdFftX86_64Impl;
-        let fft_simd = SimdFftX86_64Impl;
+        let fft_simd = SimdFftX86_64Impl::default();
         planner
             .rfft_with_scratch(&fft_simd, &mut input.clone(), &mut simd_out, &mut scratch)
             .unwrap();
@@ -35,7 +35,7 @@ fn rfft_matches_scalar() {
     #[cfg(all(feature = "aarch64", target_arch = "aarch64"))]
     {
         use kofft::fft::SimdFftAarch64Impl;
-        let fft_simd = SimdFftAarch64Impl;
+        let fft_simd = SimdFftAarch64Impl::default();
         planner
             .rfft_with_scratch(&fft_simd, &mut input.clone(), &mut simd_out, &mut scratch)
             .unwrap();
@@ -0,0 +1,121 @@
+use kofft::fft::{Complex32, Complex64, FftError, FftImpl, ScalarFftImpl, SharedFftPlan};
+use std::sync::Arc;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn shared_plan_is_send_and_sync() {
+    assert_send_sync::<SharedFftPlan<f32>>();
+    assert_send_sync::<Arc<SharedFftPlan<f64>>>();
+}
+
+#[test]
+fn matches_scalar_for_every_algorithm() {
+    let scalar = ScalarFftImpl::<f64>::default();
+    // small kernels, Stockham, mixed radix, Rader and Bluestein lengths
+    for n in [1usize, 2, 8, 16, 64, 1024, 12, 48, 1000, 97, 4099] {
+        let input: Vec<Complex64> = (0..n)
+            .map(|i| Complex64::new((i as f64 * 0.21).sin(), (i as f64 * 0.05).cos()))
+            .collect();
+        let mut expected = input.clone();
+        scalar.fft(&mut expected).unwrap();
+
+        let plan = SharedFftPlan::<f64>::new(n).unwrap();
+        assert_eq!(plan.size(), n);
+        let mut scratch = vec![Complex64::zero(); plan.scratch_len()];
+        let mut data = input.clone();
+        plan.fft(&mut data, &mut scratch).unwrap();
+        for (a, b) in data.iter().zip(&expected) {
+            assert!(
+                (a.re - b.re).abs() < 1e-8 && (a.im - b.im).abs() < 1e-8,
+                "n={n}"
+            );
+        }
+        plan.ifft(&mut data, &mut scratch).unwrap();
+        for (a, b) in data.iter().zip(&input) {
+            assert!(
+                (a.re - b.re).abs() < 1e-10 && (a.im - b.im).abs() < 1e-10,
+                "n={n}"
+            );
+        }
+    }
+}
+
+#[test]
+fn rejects_wrong_lengths() {
+    assert_eq!(
+        SharedFftPlan::<f32>::new(0).err(),
+        Some(FftError::EmptyInput)
+    );
+    let plan = SharedFftPlan::<f32>::new(32).unwrap();
+    let mut data = vec![Complex32::zero(); 16];
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    assert_eq!(
+        plan.fft(&mut data, &mut scratch),
+        Err(FftError::MismatchedLengths)
+    );
+    let mut data = vec![Complex32::zero(); 32];
+    assert_eq!(
+        plan.fft(&mut data, &mut scratch[..1]),
+        Err(FftError::MismatchedLengths)
+    );
+}
+
+#[test]
+fn one_plan_serves_many_threads() {
+    let n = 360;
+    let plan = Arc::new(SharedFftPlan::<f32>::new(n).unwrap());
+    let input: Vec<Complex32> = (0..n)
+        .map(|i| Complex32::new((i as f32 * 0.4).cos(), 0.0))
+        .collect();
+    let mut expected = input.clone();
+    ScalarFftImpl::<f32>::default().fft(&mut expected).unwrap();
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            let plan = Arc::clone(&plan);
+            let input = &input;
+            let expected = &expected;
+            s.spawn(move || {
+                let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+                for _ in 0..8 {
+                    let mut data = input.clone();
+                    plan.fft(&mut data, &mut scratch).unwrap();
+                    for (a, b) in data.iter().zip(expected) {
+                        assert!((a.re - b.re).abs() < 1e-3 && (a.im - b.im).abs() < 1e-3);
+                    }
+                }
+            });
+        }
+    });
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_stft_matches_serial() {
+    use kofft::stft::{inverse_parallel_with_plan, istft, parallel_with_plan, stft};
+    use kofft::window::hann;
+
+    let signal: Vec<f32> = (0..1000).map(|i| (i as f32 * 0.03).sin()).collect();
+    let window = hann(96);
+    let hop = 24;
+    let frames_needed = signal.len().div_ceil(hop);
+    let fft = ScalarFftImpl::<f32>::default();
+    let mut serial = vec![vec![]; frames_needed];
+    stft(&signal, &window, hop, &mut serial, &fft).unwrap();
+
+    let plan = SharedFftPlan::new(window.len()).unwrap();
+    let mut par = vec![vec![]; frames_needed];
+    parallel_with_plan(&signal, &window, hop, &mut par, &plan).unwrap();
+    for (a, b) in par.iter().flatten().zip(serial.iter().flatten()) {
+        assert!((a.re - b.re).abs() < 1e-3 && (a.im - b.im).abs() < 1e-3);
+    }
+
+    let mut expected = vec![0.0; signal.len()];
+    let mut scratch = vec![0.0; signal.len()];
+    istft(&mut serial, &window, hop, &mut expected, &mut scratch, &fft).unwrap();
+    let mut out = vec![0.0; signal.len()];
+    inverse_parallel_with_plan(&par, &window, hop, &mut out, &plan).unwrap();
+    for (a, b) in out.iter().zip(&expected) {
+        assert!((a - b).abs() < 1e-3);
+    }
+}
@@ -1,4 +1,4 @@
-use kofft::fft::{fft16, fft8, Complex32, ScalarFftImpl};
+use kofft::fft::{fft16, fft8, Complex32, Complex64, ScalarFftImpl};
 
 fn dft(input: &[Complex32]) -> Vec<Complex32> {
     let n = input.len();
@@ -55,3 +55,31 @@ fn direct_fft8_16_kernels() {
         assert!((a.im - b.im).abs() < 1e-2);
     }
 }
+
+#[test]
+fn fft8_16_kernels_are_exact_in_f64() {
+    // the kernels' constants are full f64 precision, not rounded f32 values
+    for n in [8usize, 16] {
+        let mut data: Vec<Complex64> = (0..n)
+            .map(|i| Complex64::new((i as f64).sin(), (i as f64).cos()))
+            .collect();
+        let expected: Vec<Complex64> = (0..n)
+            .map(|k| {
+                data.iter()
+                    .enumerate()
+                    .fold(Complex64::zero(), |acc, (j, x)| {
+                        let angle = -2.0 * core::f64::consts::PI * (k * j) as f64 / n as f64;
+                        acc.add(x.mul(Complex64::new(angle.cos(), angle.sin())))
+                    })
+            })
+            .collect();
+        if n == 8 {
+            fft8(&mut data);
+        } else {
+            fft16(&mut data);
+        }
+        for (a, b) in data.iter().zip(&expected) {
+            assert!((a.re - b.re).abs() < 1e-13 && (a.im - b.im).abs() < 1e-13);
+        }
+    }
+}
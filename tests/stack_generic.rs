@@ -0,0 +1,200 @@
+//! Generic const-size stack transforms against the heap implementations.
+
+use kofft::fft::{Complex32, Complex64, FftError, FftImpl, ScalarFftImpl};
+use kofft::stack;
+use kofft::window::{blackman_inplace_stack, hamming_inplace_stack, hann_inplace_stack};
+
+fn signal<const N: usize>() -> [f64; N] {
+    core::array::from_fn(|i| (i as f64 * 0.37).sin() + 0.5 * (i as f64 * 1.3).cos())
+}
+
+fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!(
+            (x - y).abs() <= tol * (1.0 + y.abs()),
+            "index {i}: {x} vs {y}"
+        );
+    }
+}
+
+fn complex_matches_heap<const N: usize>() {
+    let x = signal::<N>();
+    let input: [Complex64; N] = core::array::from_fn(|i| Complex64::new(x[i], -0.5 * x[i]));
+    let mut expected = input.to_vec();
+    ScalarFftImpl::<f64>::default().fft(&mut expected).unwrap();
+
+    let mut buf = input;
+    stack::fft(&mut buf).unwrap();
+    for (a, b) in buf.iter().zip(&expected) {
+        assert!(
+            (a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9,
+            "N={N}"
+        );
+    }
+    stack::ifft(&mut buf).unwrap();
+    for (a, b) in buf.iter().zip(&input) {
+        assert!(
+            (a.re - b.re).abs() < 1e-12 && (a.im - b.im).abs() < 1e-12,
+            "N={N}"
+        );
+    }
+
+    // The f32 instantiation shares the f64 twiddle tables.
+    let mut single: [Complex32; N] =
+        core::array::from_fn(|i| Complex32::new(input[i].re as f32, input[i].im as f32));
+    stack::fft(&mut single).unwrap();
+    for (a, b) in single.iter().zip(&expected) {
+        let tol = 1e-5 * (N as f64).max(1.0);
+        assert!((a.re as f64 - b.re).abs() < tol, "N={N}");
+    }
+}
+
+#[test]
+fn f64_fft_matches_heap_for_mixed_radix_and_prime_sizes() {
+    complex_matches_heap::<1>();
+    complex_matches_heap::<2>();
+    complex_matches_heap::<3>();
+    complex_matches_heap::<12>();
+    complex_matches_heap::<49>();
+    complex_matches_heap::<97>();
+    complex_matches_heap::<100>();
+    complex_matches_heap::<120>();
+    complex_matches_heap::<128>();
+    complex_matches_heap::<143>();
+}
+
+fn real_round_trip<const N: usize, const M: usize>() {
+    let x = signal::<N>();
+    let mut spectrum = [Complex64::zero(); M];
+    stack::rfft(&x, &mut spectrum).unwrap();
+    let mut expected: Vec<Complex64> = x.iter().map(|&v| Complex64::new(v, 0.0)).collect();
+    ScalarFftImpl::<f64>::default().fft(&mut expected).unwrap();
+    for (a, b) in spectrum.iter().zip(&expected) {
+        assert!((a.re - b.re).abs() < 1e-9 && (a.im - b.im).abs() < 1e-9);
+    }
+    let mut back = [0.0f64; N];
+    stack::irfft(&spectrum, &mut back).unwrap();
+    assert_close(&back, &x, 1e-12);
+}
+
+#[test]
+fn real_fft_round_trips_for_odd_and_even_sizes() {
+    real_round_trip::<100, 51>();
+    real_round_trip::<120, 61>();
+    real_round_trip::<15, 8>();
+    let mut short = [Complex64::zero(); 50];
+    assert_eq!(
+        stack::rfft(&[0.0f64; 100], &mut short),
+        Err(FftError::MismatchedLengths)
+    );
+}
+
+/// `Σ_i x_i f(i, k)` reference in f64.
+fn direct<const N: usize>(x: &[f64; N], f: impl Fn(f64, f64) -> f64) -> Vec<f64> {
+    (0..N)
+        .map(|k| {
+            x.iter()
+                .enumerate()
+                .map(|(i, &v)| v * f(i as f64, k as f64))
+                .sum()
+        })
+        .collect()
+}
+
+fn trig_transforms_match_direct<const N: usize>() {
+    use core::f64::consts::PI;
+    let n = N as f64;
+    let x = signal::<N>();
+    let mut y = [0.0f64; N];
+
+    stack::dct2(&x, &mut y).unwrap();
+    assert_close(
+        &y,
+        &direct(&x, |i, k| (PI * (i + 0.5) * k / n).cos()),
+        1e-10,
+    );
+    let mut back = [0.0f64; N];
+    stack::dct3(&y, &mut back).unwrap();
+    back.iter_mut().for_each(|v| *v *= 2.0 / n);
+    assert_close(&back, &x, 1e-10);
+
+    stack::dst2(&x, &mut y).unwrap();
+    assert_close(
+        &y,
+        &direct(&x, |i, k| (PI * (i + 0.5) * (k + 1.0) / n).sin()),
+        1e-10,
+    );
+    stack::dst3(&y, &mut back).unwrap();
+    back.iter_mut().for_each(|v| *v *= 2.0 / n);
+    assert_close(&back, &x, 1e-10);
+
+    stack::dct4(&x, &mut y).unwrap();
+    assert_close(
+        &y,
+        &direct(&x, |i, k| (PI * (i + 0.5) * (k + 0.5) / n).cos()),
+        1e-10,
+    );
+    stack::dst4(&x, &mut y).unwrap();
+    assert_close(
+        &y,
+        &direct(&x, |i, k| (PI * (i + 0.5) * (k + 0.5) / n).sin()),
+        1e-10,
+    );
+
+    if N > 1 {
+        stack::dct1(&x, &mut y).unwrap();
+        let m = n - 1.0;
+        let expected: Vec<f64> = (0..N)
+            .map(|k| {
+                let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                let inner: f64 = (1..N - 1)
+                    .map(|i| 2.0 * x[i] * (PI * i as f64 * k as f64 / m).cos())
+                    .sum();
+                x[0] + sign * x[N - 1] + inner
+            })
+            .collect();
+        assert_close(&y, &expected, 1e-10);
+    }
+}
+
+#[test]
+fn dct_and_dst_match_direct_sums() {
+    trig_transforms_match_direct::<1>();
+    trig_transforms_match_direct::<7>();
+    trig_transforms_match_direct::<100>();
+    trig_transforms_match_direct::<120>();
+}
+
+#[test]
+fn windows_match_f32_helpers() {
+    let mut generic = [0.0f32; 100];
+    let mut reference = [0.0f32; 100];
+    stack::hann(&mut generic);
+    hann_inplace_stack(&mut reference);
+    assert!(generic
+        .iter()
+        .zip(&reference)
+        .all(|(a, b)| (a - b).abs() < 1e-6));
+    stack::hamming(&mut generic);
+    hamming_inplace_stack(&mut reference);
+    assert!(generic
+        .iter()
+        .zip(&reference)
+        .all(|(a, b)| (a - b).abs() < 1e-6));
+    stack::blackman(&mut generic);
+    blackman_inplace_stack(&mut reference);
+    assert!(generic
+        .iter()
+        .zip(&reference)
+        .all(|(a, b)| (a - b).abs() < 1e-6));
+}
+
+#[test]
+fn empty_arrays_are_rejected() {
+    let mut empty: [Complex64; 0] = [];
+    assert_eq!(stack::fft(&mut empty), Err(FftError::EmptyInput));
+    assert_eq!(
+        stack::dct2::<f64, 0>(&[], &mut []),
+        Err(FftError::EmptyInput)
+    );
+}
@@ -0,0 +1,134 @@
+//! Heapless planner against the heap-backed FFT, real FFT and STFT paths.
+
+use core::mem::MaybeUninit;
+
+use kofft::fft::{Complex32, Complex64, FftError, FftImpl, ScalarFftImpl};
+use kofft::rfft::RealFftPlan;
+use kofft::static_planner::StaticPlanner;
+use kofft::stft;
+
+mod common;
+use common::signal;
+
+fn max_err(a: &[Complex64], b: &[Complex64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x.re - y.re).abs().max((x.im - y.im).abs()))
+        .fold(0.0, f64::max)
+}
+
+#[test]
+fn complex_fft_matches_scalar_impl_and_round_trips() {
+    let mut planner = StaticPlanner::<f64, 1024, 1>::new();
+    planner.plan(1024).unwrap();
+    let reference = ScalarFftImpl::<f64>::default();
+    for n in [1usize, 2, 4, 8, 16, 32, 64, 512, 1024] {
+        let x: Vec<Complex64> = signal(2 * n)
+            .chunks_exact(2)
+            .map(|p| Complex64::new(p[0], p[1]))
+            .collect();
+        let mut expected = x.clone();
+        reference.fft(&mut expected).unwrap();
+        let mut data = x.clone();
+        planner.fft(&mut data).unwrap();
+        assert!(max_err(&data, &expected) < 1e-9, "n={n}");
+        planner.ifft(&mut data).unwrap();
+        assert!(max_err(&data, &x) < 1e-12, "n={n}");
+    }
+    // Every length was served by the single 1024-point table.
+    assert_eq!(planner.planned().collect::<Vec<_>>(), [1024]);
+}
+
+#[test]
+fn real_fft_matches_real_plan() {
+    let mut planner = StaticPlanner::<f32, 256, 1>::new();
+    planner.plan(256).unwrap();
+    for n in [2usize, 4, 8, 32, 64, 256] {
+        let x: Vec<f32> = signal(n).iter().map(|&v| v as f32).collect();
+        let plan = RealFftPlan::<f32>::new(n).unwrap();
+        let mut expected = vec![Complex32::zero(); n / 2 + 1];
+        let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+        plan.rfft_with_scratch(&x, &mut expected, &mut scratch)
+            .unwrap();
+
+        let mut spectrum = vec![Complex32::zero(); n / 2 + 1];
+        planner.rfft(&x, &mut spectrum).unwrap();
+        for (s, e) in spectrum.iter().zip(&expected) {
+            assert!(
+                (s.re - e.re).abs() < 1e-4 && (s.im - e.im).abs() < 1e-4,
+                "n={n}"
+            );
+        }
+        let mut back = vec![0.0f32; n];
+        planner.irfft(&spectrum, &mut back).unwrap();
+        for (b, o) in back.iter().zip(&x) {
+            assert!((b - o).abs() < 1e-5, "n={n}");
+        }
+    }
+}
+
+#[test]
+fn stft_frames_match_stft_module() {
+    let mut planner = StaticPlanner::<f32, 64, 1>::new();
+    let fft = ScalarFftImpl::<f32>::default();
+    let x: Vec<f32> = signal(200).iter().map(|&v| v as f32).collect();
+    let mut window = [0.0f32; 64];
+    kofft::stack::hann(&mut window);
+    let mut out = vec![0.0f32; x.len()];
+    let mut expected_out = out.clone();
+    for start in (0..x.len()).step_by(32) {
+        let mut frame = [Complex32::zero(); 64];
+        planner.stft_frame(&x, &window, start, &mut frame).unwrap();
+        let mut expected = [Complex32::zero(); 64];
+        stft::frame(&x, &window, start, &mut expected, &fft).unwrap();
+        for (a, b) in frame.iter().zip(&expected) {
+            assert!((a.re - b.re).abs() < 1e-4 && (a.im - b.im).abs() < 1e-4);
+        }
+        planner
+            .istft_frame(&mut frame, &window, start, &mut out)
+            .unwrap();
+        stft::inverse_frame(&mut expected, &window, start, &mut expected_out, &fft).unwrap();
+    }
+    for (a, b) in out.iter().zip(&expected_out) {
+        assert!((a - b).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn init_in_static_storage() {
+    type Planner = StaticPlanner<f32, 512, 2>;
+    static mut STORAGE: MaybeUninit<Planner> = MaybeUninit::uninit();
+    // SAFETY: the only reference taken to `STORAGE`.
+    let planner = Planner::init(unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) });
+    let mut data = [Complex32::new(1.0, 0.0); 512];
+    planner.fft(&mut data).unwrap();
+    assert!((data[0].re - 512.0).abs() < 1e-3);
+    assert!(data[1..]
+        .iter()
+        .all(|c| c.re.abs() < 1e-3 && c.im.abs() < 1e-3));
+}
+
+#[test]
+fn capacity_and_length_errors() {
+    let mut planner = StaticPlanner::<f32, 64, 1>::new();
+    let mut empty: [Complex32; 0] = [];
+    assert_eq!(planner.fft(&mut empty), Err(FftError::EmptyInput));
+    let mut odd = [Complex32::zero(); 12];
+    assert_eq!(planner.fft(&mut odd), Err(FftError::NonPowerOfTwoNoStd));
+    let mut big = [Complex32::zero(); 128];
+    assert_eq!(planner.fft(&mut big), Err(FftError::MismatchedLengths));
+    let mut short = [Complex32::zero(); 4];
+    assert_eq!(
+        planner.rfft(&[0.0; 16], &mut short),
+        Err(FftError::MismatchedLengths)
+    );
+
+    // Rejected lengths plan nothing; the only slot then holds the 16-point
+    // table, which cannot serve 32.
+    assert_eq!(planner.planned().count(), 0);
+    planner.plan(16).unwrap();
+    assert_eq!(planner.planned().collect::<Vec<_>>(), [16]);
+    assert_eq!(planner.free_twiddles(), 56);
+    assert_eq!(planner.plan(32), Err(FftError::InvalidValue));
+    assert_eq!(planner.plan(8), Ok(()));
+}
@@ -0,0 +1,85 @@
+//! Compile-time twiddle tables against runtime evaluation and the planners.
+
+use kofft::fft::{Complex32, FftImpl, FftPlanner, ScalarFftImpl, Twiddles};
+use kofft::rfft::RfftPlanner;
+use kofft::static_planner::StaticPlanner;
+use kofft::twiddles::TwiddleTable;
+
+const TW_1000: TwiddleTable<f64, 1000> = TwiddleTable::new();
+
+#[test]
+fn tables_match_runtime_sincos_for_any_size() {
+    let tw: &'static TwiddleTable<f64, 4096> = kofft::twiddles!(f64, 4096);
+    for (k, w) in tw.as_slice().iter().enumerate() {
+        let (s, c) = (-2.0 * std::f64::consts::PI * k as f64 / 4096.0).sin_cos();
+        assert!(
+            (w.re - c).abs() < 1e-15 && (w.im - s).abs() < 1e-15,
+            "k={k}"
+        );
+    }
+    // Quarter turns are exact.
+    assert_eq!((tw.get(1024).re, tw.get(1024).im), (0.0, -1.0));
+    assert_eq!(tw.get(2048).re, -1.0);
+
+    for k in 0..1000 {
+        let (s, c) = (-2.0 * std::f64::consts::PI * k as f64 / 1000.0).sin_cos();
+        let w = TW_1000.get(k);
+        assert!(
+            (w.re - c).abs() < 1e-15 && (w.im - s).abs() < 1e-15,
+            "k={k}"
+        );
+    }
+    // Radix-5 stage twiddles of length 200 read with stride 1000/200.
+    let w = TW_1000.get(5 * 3);
+    let (s, c) = (-2.0 * std::f64::consts::PI * 3.0 / 200.0).sin_cos();
+    assert!((w.re - c).abs() < 1e-15 && (w.im - s).abs() < 1e-15);
+
+    // f32 tables are the f64 values rounded once.
+    let tw32 = kofft::twiddles!(f32, 1000);
+    for (a, b) in tw32.as_slice().iter().zip(TW_1000.as_slice()) {
+        assert_eq!((a.re, a.im), (b.re as f32, b.im as f32));
+    }
+
+    let pack = kofft::twiddles!(f64, 8, 16);
+    assert_eq!(pack.period(), 16);
+    let (s, c) = (-std::f64::consts::PI * 3.0 / 8.0).sin_cos();
+    assert!((pack.get(3).re - c).abs() < 1e-15 && (pack.get(3).im - s).abs() < 1e-15);
+}
+
+#[test]
+fn planners_accept_compile_time_tables() {
+    let tw = kofft::twiddles!(f32, 1024);
+    let x: Vec<Complex32> = (0..256)
+        .map(|i| Complex32::new((i as f32 * 0.3).sin(), (i as f32 * 0.7).cos()))
+        .collect();
+    let mut expected = x.clone();
+    ScalarFftImpl::<f32>::default().fft(&mut expected).unwrap();
+
+    let mut planner = StaticPlanner::<f32, 256, 1>::new();
+    planner.plan_with_table(tw).unwrap();
+    assert_eq!(planner.free_twiddles(), 256);
+    let mut data = x.clone();
+    planner.fft(&mut data).unwrap();
+    for (a, b) in data.iter().zip(&expected) {
+        assert!((a.re - b.re).abs() < 1e-3 && (a.im - b.im).abs() < 1e-3);
+    }
+    let odd = kofft::twiddles!(f32, 12);
+    assert!(StaticPlanner::<f32, 16, 1>::new()
+        .plan_with_table(odd)
+        .is_err());
+
+    let mut fft = FftPlanner::<f32>::new();
+    fft.load_twiddles(tw).unwrap();
+    assert_eq!(&*fft.get_twiddles(1024), tw.half());
+    // Borrowed in place rather than copied to the heap.
+    assert!(
+        matches!(fft.get_twiddles(1024), Twiddles::Static(t) if t.as_ptr() == tw.half().as_ptr())
+    );
+    assert!(fft.load_twiddles(odd).is_err());
+
+    let mut rfft = RfftPlanner::<f32>::new();
+    rfft.load_twiddles(tw).unwrap();
+    assert_eq!(&*rfft.get_pack_twiddles(512), tw.half());
+    assert_eq!(rfft.get_twiddles(512).as_ptr(), tw.half().as_ptr());
+    assert!(rfft.load_twiddles(kofft::twiddles!(f32, 8, 12)).is_err());
+}
@@ -0,0 +1,169 @@
+//! Window specs against the window functions, their textual form, and
+//! Harris's figures of merit.
+
+use std::f32::consts::FRAC_1_PI;
+
+use kofft::fft::FftError;
+use kofft::window::{self, blackman, hamming, hann, kaiser, Symmetry, WindowSpec};
+use kofft::window_more::{bartlett, nuttall, tukey};
+
+fn close(a: &[f32], b: &[f32], tol: f32) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).abs() < tol, "sample {i}: {x} vs {y}");
+    }
+}
+
+#[test]
+fn matches_the_window_functions() {
+    for len in [1usize, 8, 33, 256] {
+        let periodic = |spec: WindowSpec| spec.generate(len, Symmetry::Periodic);
+        let symmetric = |spec: WindowSpec| spec.generate(len, Symmetry::Symmetric);
+        close(&periodic(WindowSpec::Hann), &hann(len), 1e-6);
+        close(&periodic(WindowSpec::Hamming), &hamming(len), 1e-6);
+        close(&periodic(WindowSpec::Blackman), &blackman(len), 1e-6);
+        if len > 1 {
+            close(&symmetric(WindowSpec::Bartlett), &bartlett(len), 1e-6);
+            close(&symmetric(WindowSpec::Nuttall), &nuttall(len), 1e-6);
+        }
+
+        // periodic is the symmetric window one longer, minus its last sample
+        for spec in [
+            WindowSpec::Rectangular,
+            WindowSpec::Kaiser { beta: 8.6 },
+            WindowSpec::Bohman,
+            WindowSpec::Tukey { alpha: 1.0 },
+        ] {
+            let longer = spec.generate(len + 1, Symmetry::Symmetric);
+            close(&periodic(spec), &longer[..len], 1e-6);
+        }
+    }
+    // scipy.signal.windows.tukey(8, 0.3) and bohman(5)
+    let edge = 0.994_415_4;
+    close(
+        &WindowSpec::Tukey { alpha: 0.3 }.generate(8, Symmetry::Symmetric),
+        &[0.0, edge, 1.0, 1.0, 1.0, 1.0, edge, 0.0],
+        1e-6,
+    );
+    close(
+        &WindowSpec::Bohman.generate(5, Symmetry::Symmetric),
+        &[0.0, FRAC_1_PI, 1.0, FRAC_1_PI, 0.0],
+        1e-6,
+    );
+    close(
+        &WindowSpec::Tukey { alpha: 1.0 }.generate(16, Symmetry::Periodic),
+        &hann(16),
+        1e-6,
+    );
+
+    let mut frame = vec![2.0f64; 64];
+    WindowSpec::Hann.apply(&mut frame, Symmetry::Periodic);
+    for (x, w) in frame.iter().zip(hann(64)) {
+        assert!((x - 2.0 * w as f64).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn kaiser_and_tukey_are_the_symmetric_specs() {
+    for len in [0usize, 1, 2, 8, 33, 256] {
+        for beta in [0.0, 5.0, 8.6, 14.0] {
+            assert_eq!(
+                kaiser(len, beta),
+                WindowSpec::Kaiser { beta }.generate(len, Symmetry::Symmetric)
+            );
+        }
+        for alpha in [0.0, 0.3, 0.5, 1.0] {
+            assert_eq!(
+                tukey(len, alpha),
+                WindowSpec::Tukey { alpha }.generate(len, Symmetry::Symmetric)
+            );
+        }
+    }
+    // scipy.signal.windows.kaiser(5, 5.0) and tukey(8, 0.3)
+    close(
+        &kaiser(5, 5.0),
+        &[0.036_710_89, 0.552_851_8, 1.0, 0.552_851_8, 0.036_710_89],
+        1e-6,
+    );
+    let edge = 0.994_415_4;
+    close(
+        &tukey(8, 0.3),
+        &[0.0, edge, 1.0, 1.0, 1.0, 1.0, edge, 0.0],
+        1e-6,
+    );
+}
+
+#[test]
+fn parses_and_displays_specs() {
+    let cases = [
+        ("hann", WindowSpec::Hann),
+        (" Hanning ", WindowSpec::Hann),
+        ("rect", WindowSpec::Rectangular),
+        ("BOXCAR", WindowSpec::Rectangular),
+        ("kaiser:8.6", WindowSpec::Kaiser { beta: 8.6 }),
+        ("kaiser : 0", WindowSpec::Kaiser { beta: 0.0 }),
+        ("tukey", WindowSpec::Tukey { alpha: 0.5 }),
+        ("tukey:0.25", WindowSpec::Tukey { alpha: 0.25 }),
+        ("triangular", WindowSpec::Bartlett),
+        ("nuttall", WindowSpec::Nuttall),
+    ];
+    for (text, spec) in cases {
+        assert_eq!(text.parse::<WindowSpec>(), Ok(spec), "{text}");
+        assert_eq!(spec.to_string().parse::<WindowSpec>(), Ok(spec));
+    }
+    assert_eq!(WindowSpec::Kaiser { beta: 8.6 }.to_string(), "kaiser:8.6");
+
+    for text in [
+        "",
+        "gauss",
+        "kaiser",
+        "kaiser:",
+        "kaiser:-1",
+        "kaiser:inf",
+        "tukey:1.5",
+        "hann:2",
+        "blackman:x",
+    ] {
+        assert_eq!(
+            text.parse::<WindowSpec>(),
+            Err(FftError::InvalidValue),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn metrics_match_harris() {
+    // Harris, "On the use of windows for harmonic analysis with the DFT"
+    // (1978), Table 1: coherent gain, ENBW, scalloping loss, highest
+    // sidelobe, 6 dB bandwidth. Blackman's 6 dB width is 2.30 bins; the
+    // table's 2.35 is a known misprint.
+    let table = [
+        (WindowSpec::Rectangular, 1.00, 1.00, 3.92, -13.3, 1.21),
+        (WindowSpec::Bartlett, 0.50, 1.33, 1.82, -26.5, 1.78),
+        (WindowSpec::Hann, 0.50, 1.50, 1.42, -31.5, 2.00),
+        (WindowSpec::Hamming, 0.54, 1.36, 1.75, -42.7, 1.81),
+        (WindowSpec::Blackman, 0.42, 1.73, 1.10, -58.1, 2.30),
+    ];
+    for (spec, gain, enbw, scallop, sidelobe, bw6) in table {
+        let m = spec.metrics(1024, Symmetry::Periodic).unwrap();
+        assert!((m.coherent_gain - gain).abs() < 0.005, "{spec}");
+        assert!((m.enbw - enbw).abs() < 0.005, "{spec}: {}", m.enbw);
+        assert!((m.scalloping_loss - scallop).abs() < 0.005, "{spec}");
+        assert!((m.highest_sidelobe - sidelobe).abs() < 0.05, "{spec}");
+        assert!((m.bandwidth_6db - bw6).abs() < 0.01, "{spec}");
+    }
+
+    // exact for a periodic Hann window of any length
+    let m = window::metrics(&hann(100)).unwrap();
+    assert!((m.coherent_gain - 0.5).abs() < 1e-7 && (m.enbw - 1.5).abs() < 1e-6);
+    // a higher Kaiser beta trades main lobe width for lower sidelobes
+    let narrow = WindowSpec::Kaiser { beta: 4.0 }.metrics(512, Symmetry::Symmetric);
+    let wide = WindowSpec::Kaiser { beta: 12.0 }.metrics(512, Symmetry::Symmetric);
+    let (narrow, wide) = (narrow.unwrap(), wide.unwrap());
+    assert!(wide.highest_sidelobe < narrow.highest_sidelobe - 40.0);
+    assert!(wide.bandwidth_6db > narrow.bandwidth_6db);
+
+    assert_eq!(window::metrics(&[]), Err(FftError::EmptyInput));
+    assert_eq!(window::metrics(&[1.0, -1.0]), Err(FftError::InvalidValue));
+}
@@ -0,0 +1,99 @@
+use kofft::fft::{FftPlanner, FftStrategy};
+use kofft::wisdom::{cpu_signature, export_wisdom, import_wisdom, WisdomError};
+use kofft::PlanMode;
+
+#[test]
+fn wisdom_round_trips_strategies() {
+    let mut planner = FftPlanner::<f64>::with_mode(PlanMode::Measure);
+    for n in [128usize, 360, 1009] {
+        planner.plan_strategy(n);
+    }
+    let blob = export_wisdom(&planner);
+    assert!(blob.starts_with("kofft-wisdom 1\n"));
+
+    let mut restored = FftPlanner::<f64>::new();
+    assert_eq!(import_wisdom(&mut restored, &blob), Ok(3));
+    assert_eq!(restored.strategies(), planner.strategies());
+    // Imported decisions are used without measuring again, even in Estimate mode.
+    assert_eq!(
+        restored.plan_strategy(360),
+        planner.remembered_strategy(360).unwrap()
+    );
+    // Re-exporting yields the same blob.
+    assert_eq!(export_wisdom(&restored), blob);
+}
+
+#[test]
+fn wisdom_rejects_other_cpus_and_precisions() {
+    let mut planner = FftPlanner::<f32>::new();
+    planner.remember_strategy(48, FftStrategy::MixedRadix);
+    let blob = export_wisdom(&planner);
+
+    let foreign = blob.replace(&cpu_signature(), "riscv64 none");
+    let mut target = FftPlanner::<f32>::new();
+    assert!(matches!(
+        import_wisdom(&mut target, &foreign),
+        Err(WisdomError::CpuMismatch { .. })
+    ));
+    assert_eq!(target.remembered_strategy(48), None);
+
+    // The precision is written as a fixed tag, not a compiler type name.
+    assert!(blob.contains("\nfloat f32\n"));
+    let mut wide = FftPlanner::<f64>::new();
+    assert_eq!(
+        import_wisdom(&mut wide, &blob),
+        Err(WisdomError::PrecisionMismatch {
+            recorded: "f32".into(),
+            current: "f64".into(),
+        })
+    );
+}
+
+#[test]
+fn wisdom_rejects_bad_blobs() {
+    let mut planner = FftPlanner::<f32>::new();
+    assert_eq!(
+        import_wisdom(&mut planner, ""),
+        Err(WisdomError::MissingHeader)
+    );
+    assert_eq!(
+        import_wisdom(&mut planner, "kofft-wisdom 99\n"),
+        Err(WisdomError::UnsupportedVersion(99))
+    );
+    let good = export_wisdom(&planner);
+    let bad = format!("{good}strategy 64 fastest\n");
+    let line = bad.lines().count();
+    assert_eq!(
+        import_wisdom(&mut planner, &bad),
+        Err(WisdomError::Malformed { line })
+    );
+    let no_cpu = "kofft-wisdom 1\nfloat f32\nstrategy 8 radix2\n";
+    assert_eq!(
+        import_wisdom(&mut planner, no_cpu),
+        Err(WisdomError::MissingHeader)
+    );
+    assert!(planner.strategies().is_empty());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn wisdom_restores_parallel_knobs() {
+    use kofft::fft::{parallel_fft_config, set_parallel_fft_config, ParallelFftConfig};
+
+    let saved = parallel_fft_config();
+    let tuned = ParallelFftConfig {
+        threshold: 1 << 16,
+        l1_cache_bytes: 48 * 1024,
+        per_core_work: 8192,
+        block_size: 2048,
+        threads: 3,
+        calibrated_per_core_work: 123_456,
+    };
+    set_parallel_fft_config(&tuned);
+    let blob = export_wisdom(&FftPlanner::<f32>::new());
+    set_parallel_fft_config(&ParallelFftConfig::default());
+
+    import_wisdom(&mut FftPlanner::<f32>::new(), &blob).unwrap();
+    assert_eq!(parallel_fft_config(), tuned);
+    set_parallel_fft_config(&saved);
+}
@@ -0,0 +1,188 @@
+//! `*_with_scratch` execute paths: exact scratch sizes, parity with the
+//! allocating APIs, and zero heap allocations per call.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use kofft::dct::{self, DctPlan, DctType};
+use kofft::dst::{self, DstPlan, DstType};
+use kofft::fft::SharedFftPlan;
+use kofft::fft::{Complex32, Complex64, FftPlan, FftStrategy, ScalarFftImpl};
+use kofft::rfft::{RealFftImpl, RealFftPlan};
+use kofft::stft;
+
+mod common;
+use common::{assert_close, signal_f32};
+
+struct CountingAlloc;
+
+thread_local! {
+    // Only allocations made by the measuring thread are counted, so tests
+    // running in parallel do not disturb each other.
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if TRACKING.with(Cell::get) {
+            ALLOCS.with(|a| a.set(a.get() + 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn count_allocs(f: impl FnOnce()) -> usize {
+    ALLOCS.with(|a| a.set(0));
+    TRACKING.with(|t| t.set(true));
+    f();
+    TRACKING.with(|t| t.set(false));
+    ALLOCS.with(Cell::get)
+}
+
+#[test]
+fn complex_plan_matches_and_never_allocates() {
+    for n in [16usize, 64, 96, 127, 4099] {
+        let plan = FftPlan::<f64>::new(n, FftStrategy::Auto);
+        let input: Vec<Complex64> = (0..n)
+            .map(|i| Complex64::new((i as f64).cos(), 0.5))
+            .collect();
+        let mut expected = input.clone();
+        plan.fft(&mut expected).unwrap();
+
+        let mut scratch = vec![Complex64::zero(); plan.scratch_len()];
+        let mut data = input.clone();
+        let allocs = count_allocs(|| {
+            plan.fft_with_scratch(&mut data, &mut scratch).unwrap();
+        });
+        assert_eq!(allocs, 0, "n={n}");
+        for (a, b) in data.iter().zip(&expected) {
+            assert!((a.re - b.re).abs() < 1e-8 && (a.im - b.im).abs() < 1e-8);
+        }
+
+        let mut re: Vec<f64> = input.iter().map(|c| c.re).collect();
+        let mut im: Vec<f64> = input.iter().map(|c| c.im).collect();
+        let mut split_scratch = vec![Complex64::zero(); plan.split_scratch_len()];
+        let allocs = count_allocs(|| {
+            plan.fft_split_with_scratch(&mut re, &mut im, &mut split_scratch)
+                .unwrap();
+            plan.ifft_split_with_scratch(&mut re, &mut im, &mut split_scratch)
+                .unwrap();
+        });
+        assert_eq!(allocs, 0, "n={n}");
+        for (c, (r, i)) in input.iter().zip(re.iter().zip(&im)) {
+            assert!((c.re - r).abs() < 1e-10 && (c.im - i).abs() < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn real_plan_matches_and_never_allocates() {
+    let fft = ScalarFftImpl::<f32>::default();
+    for n in [2usize, 32, 100, 1024] {
+        let plan = RealFftPlan::<f32>::new(n).unwrap();
+        let x = signal_f32(n);
+        let mut expected = vec![Complex32::zero(); n / 2 + 1];
+        fft.rfft(&mut x.clone(), &mut expected).unwrap();
+
+        let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+        let mut spectrum = vec![Complex32::zero(); plan.spectrum_len()];
+        let mut back = vec![0.0f32; n];
+        let allocs = count_allocs(|| {
+            plan.rfft_with_scratch(&x, &mut spectrum, &mut scratch)
+                .unwrap();
+            plan.irfft_with_scratch(&spectrum, &mut back, &mut scratch)
+                .unwrap();
+        });
+        assert_eq!(allocs, 0, "n={n}");
+        for (a, b) in spectrum.iter().zip(&expected) {
+            assert!((a.re - b.re).abs() < 1e-2 && (a.im - b.im).abs() < 1e-2);
+        }
+        assert_close(&back, &x, 1e-4);
+    }
+}
+
+#[test]
+fn dct_and_dst_plans_match_reference_without_allocating() {
+    type Reference = fn(&[f32]) -> Vec<f32>;
+    let dcts: [(DctType, Reference); 4] = [
+        (DctType::I, dct::dct1),
+        (DctType::II, dct::dct2),
+        (DctType::III, dct::dct3),
+        (DctType::IV, dct::dct4),
+    ];
+    let dsts: [(DstType, Reference); 4] = [
+        (DstType::I, dst::dst1),
+        (DstType::II, dst::dst2),
+        (DstType::III, dst::dst3),
+        (DstType::IV, dst::dst4),
+    ];
+    for n in [1usize, 2, 5, 8, 31, 64] {
+        let x = signal_f32(n);
+        let mut out = vec![0.0f32; n];
+        for (kind, reference) in dcts {
+            let plan = DctPlan::new(kind, n).unwrap();
+            let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+            let allocs = count_allocs(|| {
+                plan.execute_with_scratch(&x, &mut out, &mut scratch)
+                    .unwrap();
+            });
+            assert_eq!(allocs, 0, "{kind:?} n={n}");
+            assert_close(&out, &reference(&x), 2e-3);
+        }
+        for (kind, reference) in dsts {
+            let plan = DstPlan::new(kind, n).unwrap();
+            let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+            let allocs = count_allocs(|| {
+                plan.execute_with_scratch(&x, &mut out, &mut scratch)
+                    .unwrap();
+            });
+            assert_eq!(allocs, 0, "{kind:?} n={n}");
+            assert_close(&out, &reference(&x), 2e-3);
+        }
+    }
+}
+
+#[test]
+fn stft_frames_never_allocate() {
+    let x = signal_f32(512);
+    let window = kofft::window::hann(64);
+    let plan = SharedFftPlan::<f32>::new(64).unwrap();
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len()];
+    let mut frame = vec![Complex32::zero(); 64];
+    let mut out = vec![0.0f32; 512];
+
+    let fft = ScalarFftImpl::<f32>::default();
+    let mut expected = vec![Complex32::zero(); 64];
+    stft::frame(&x, &window, 32, &mut expected, &fft).unwrap();
+
+    let allocs = count_allocs(|| {
+        stft::frame_with_scratch(&x, &window, 32, &mut frame, &plan, &mut scratch).unwrap();
+    });
+    assert_eq!(allocs, 0);
+    for (a, b) in frame.iter().zip(&expected) {
+        assert!((a.re - b.re).abs() < 1e-4 && (a.im - b.im).abs() < 1e-4);
+    }
+    let allocs = count_allocs(|| {
+        stft::inverse_frame_with_scratch(&mut frame, &window, 32, &mut out, &plan, &mut scratch)
+            .unwrap();
+    });
+    assert_eq!(allocs, 0);
+}
+
+#[test]
+fn short_scratch_is_rejected() {
+    let plan = DctPlan::new(DctType::IV, 16).unwrap();
+    let mut scratch = vec![Complex32::zero(); plan.scratch_len() - 1];
+    let mut out = [0.0f32; 16];
+    assert!(plan
+        .execute_with_scratch(&[0.0; 16], &mut out, &mut scratch)
+        .is_err());
+}